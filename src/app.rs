@@ -1,11 +1,15 @@
 //! Application state and main loop
 
+use std::collections::{HashSet, VecDeque};
 use std::io::{self, IsTerminal, Stdout};
-use std::path::PathBuf;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
 use crossterm::{
+    cursor::Show,
     event::{
         DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
         Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
@@ -13,17 +17,30 @@ use crossterm::{
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
 };
-use nix::sys::signal::{self, Signal};
+use nix::sys::signal::{self, SaFlags, SigAction, SigHandler, SigSet, Signal};
 use nix::unistd::Pid;
 use ratatui::{Terminal, backend::CrosstermBackend, layout::Rect};
 
-use crate::data::{Bead, BeadStatus, BeadStore, BrCli, build_tree_order};
+use crate::config;
+use crate::data::{
+    Bead, BeadStats, BeadStatus, BeadStore, BrCli, BrOp, BrQueue, DbWatcher, DependencyType,
+    GroupBy, LabelMatch, OnFailure, SortMode, TreeOptions, bead_stats, build_tree_order,
+    build_tree_rows, find_cycles, is_busy, not_closed_ids, open_descendants,
+};
 use crate::event;
-use crate::ui::layout::Focus;
+use crate::export;
+use crate::glyphs::{self, Profile};
+use crate::history::History;
+use crate::keymap::{Action, Keymap};
+use crate::ui::board::{self, BoardState};
+use crate::ui::layout::{Focus, MessageKind, RefreshState};
+use crate::ui::list::Density;
 use crate::ui::{
-    BeadListState, CreateModal, DetailState, ModalAction, THEMES, Theme, render_layout,
+    BeadListState, CreateModal, DetailState, LabelPicker, ModalAction, PickerAction, Theme,
+    render_layout,
 };
-use tui_textarea::TextArea;
+use crate::views::{View, Views};
+use tui_textarea::{CursorMove, TextArea};
 
 /// Input mode for the application
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -36,8 +53,135 @@ pub enum InputMode {
     ClosingBead,
     ReopeningBead,
     AddingComment,
+    PriorityFilter,
+    FilteringLabels,
+    SettingPriority,
+    Assigning,
+    JumpToId,
+    AddingDependency,
+    RemovingDependency,
+    ConfirmClose,
+    ConfirmQuit,
+    AddingLabel,
+    Exporting,
+    SavingView,
+    PickingView,
+    PickingWorkspace,
+}
+
+/// A single reversible change made through `br`, remembered for undo
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Mutation {
+    /// Status changed (including close and reopen); `old` is restored on undo
+    StatusChange {
+        id: String,
+        old: BeadStatus,
+    },
+    /// A plain field (title, assignee, ...) changed from `old`
+    FieldChange {
+        id: String,
+        field: &'static str,
+        old: String,
+    },
+    LabelAdd {
+        id: String,
+        label: String,
+    },
+    LabelRemove {
+        id: String,
+        label: String,
+    },
+    DependencyAdd {
+        from: String,
+        to: String,
+    },
+    DependencyRemove {
+        from: String,
+        to: String,
+        dep_type: DependencyType,
+    },
+}
+
+impl Mutation {
+    /// The br command that undoes this change
+    fn revert(&self) -> BrOp {
+        match self.clone() {
+            Mutation::StatusChange {
+                id,
+                old: BeadStatus::Closed,
+            } => BrOp::Close { id, reason: None },
+            Mutation::StatusChange { id, old } => BrOp::UpdateStatus {
+                id,
+                status: old.to_string(),
+            },
+            Mutation::FieldChange { id, field, old } => BrOp::UpdateField {
+                id,
+                field,
+                value: old,
+            },
+            Mutation::LabelAdd { id, label } => BrOp::RemoveLabel { id, label },
+            Mutation::LabelRemove { id, label } => BrOp::AddLabel { id, label },
+            Mutation::DependencyAdd { from, to } => BrOp::RemoveDependency { from, to },
+            Mutation::DependencyRemove { from, to, dep_type } => BrOp::AddDependency {
+                from,
+                to,
+                dep_type: dep_type.to_string(),
+            },
+        }
+    }
+
+    /// Bead the change was made to
+    fn bead_id(&self) -> &str {
+        match self {
+            Mutation::StatusChange { id, .. }
+            | Mutation::FieldChange { id, .. }
+            | Mutation::LabelAdd { id, .. }
+            | Mutation::LabelRemove { id, .. } => id,
+            Mutation::DependencyAdd { from, .. } | Mutation::DependencyRemove { from, .. } => from,
+        }
+    }
+}
+
+/// What to do once a job's commands have all succeeded, given the last
+/// command's output (a created bead's ID)
+type AfterJob = Box<dyn FnOnce(&mut App, &str)>;
+
+/// A change sent to the br queue, waiting for its commands to finish
+struct Job {
+    /// What each command changes, for undo (empty for ones that can't be undone)
+    undo: Vec<Vec<Mutation>>,
+    /// Bead each command changes, to name the ones that failed
+    beads: Vec<Option<String>>,
+    on_failure: OnFailure,
+    then: AfterJob,
+    /// When it was queued (drives the footer spinner)
+    sent: Instant,
 }
 
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// How long a footer status message stays visible
+const STATUS_MESSAGE_TTL: Duration = Duration::from_secs(4);
+
+/// Errors stay up longer so there's time to read them
+const ERROR_MESSAGE_TTL: Duration = Duration::from_secs(10);
+
+/// Largest count prefix kept (more digits are ignored)
+const MAX_COUNT: usize = 9999;
+
+/// Longest gap between two clicks on a row that still counts as a double-click
+const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(400);
+
+/// How long the footer shows ⟳ after an automatic refresh
+const REFRESH_FLASH: Duration = Duration::from_secs(1);
+
+/// Polling intervals `[` and `]` step through, in seconds
+const REFRESH_STEPS: [u64; 8] = [1, 2, 3, 5, 10, 15, 30, 60];
+
+/// Interval Ctrl+R resumes at when started with `--refresh 0` (matches the
+/// `--refresh` default)
+const DEFAULT_REFRESH_SECS: u64 = 3;
+
 const MIN_SPLIT_PERCENT: u16 = 20;
 const MAX_SPLIT_PERCENT: u16 = 80;
 
@@ -45,12 +189,22 @@ const MAX_SPLIT_PERCENT: u16 = 80;
 pub struct App {
     /// Path to the beads database
     db_path: PathBuf,
+    /// Long-lived read-only connection to the database
+    store: BeadStore,
+    /// Database data_version at the last load (to skip no-op refreshes)
+    data_version: Option<i64>,
+    /// Filesystem watcher on the database (None = fall back to polling)
+    watcher: Option<DbWatcher>,
     /// All loaded beads
     beads: Vec<Bead>,
+    /// Dependency cycles among the loaded beads (see `find_cycles`)
+    cycles: Vec<Vec<String>>,
     /// List widget state
     list_state: BeadListState,
     /// Detail panel state (scroll position)
     detail_state: DetailState,
+    /// Available themes (built-in followed by custom)
+    themes: Vec<Theme>,
     /// Current theme index
     theme_idx: usize,
     /// Current focus
@@ -61,6 +215,8 @@ pub struct App {
     input_mode: InputMode,
     /// Text input for search
     search_input: TextArea<'static>,
+    /// Recent search queries, recalled with Up/Down while searching
+    search_history: History,
     /// Create modal state
     create_modal: CreateModal,
     /// ID of bead being edited (if in Editing mode)
@@ -69,18 +225,106 @@ pub struct App {
     reason_input: TextArea<'static>,
     /// Comment input for adding comments
     comment_input: TextArea<'static>,
+    /// Assignee input for assigning beads
+    assignee_input: TextArea<'static>,
+    /// Bead ID input for jumping to a bead
+    jump_input: TextArea<'static>,
+    /// Target bead ID input for adding/removing dependencies
+    dep_input: TextArea<'static>,
+    /// Dependency type to add
+    dep_type: DependencyType,
+    /// Open descendants of the bead awaiting close confirmation
+    confirm_close_children: Vec<String>,
+    /// Beads whose children are hidden in the tree
+    collapsed: HashSet<String>,
+    /// Bead where the visual selection started (None = not selecting)
+    visual_anchor: Option<String>,
+    /// Label input for labeling the visual selection
+    label_input: TextArea<'static>,
+    /// File path input for exporting the visible beads
+    export_input: TextArea<'static>,
+    /// Bead being exported as text (None = export the whole view)
+    export_bead: Option<String>,
+    /// Show timestamps as "3 days ago" rather than dates
+    relative_times: bool,
+    /// User settings from config.toml
+    config: config::Config,
+    /// Normal-mode key bindings (defaults plus keys.toml)
+    keymap: Keymap,
+    /// Count typed before a movement (the 5 in 5j)
+    pending_count: Option<usize>,
+    /// First `g` of `gg` was pressed
+    pending_g: bool,
+    /// Command to run with the terminal handed over (see `open_in_terminal`)
+    pending_command: Option<String>,
+    /// System clipboard, opened on first copy and kept so X11 selections
+    /// stay available after copying
+    clipboard: Option<arboard::Clipboard>,
+    /// Changes made by the last action, for one-level undo (empty = nothing to undo)
+    last_action: Vec<Mutation>,
+    /// Runs br commands in the background
+    br: BrQueue,
+    /// Changes sent to `br`, oldest first, in the order their results come back
+    jobs: VecDeque<Job>,
+    /// Transient footer message and when it was set
+    status_message: Option<(String, MessageKind, Instant)>,
     /// Show labels in list view
     show_labels: bool,
+    /// Show assignees in list view
+    show_assignee: bool,
+    /// Lead list rows with an update recency dot
+    show_recency: bool,
+    /// Wrap long titles in list view (instead of truncating)
+    wrap_titles: bool,
+    /// How much each list row shows
+    density: Density,
     /// Show help overlay
     show_help: bool,
+    /// Show the stats overlay
+    show_stats: bool,
+    /// Stats cover only the beads the list shows, not everything loaded
+    stats_visible_only: bool,
+    /// Show the visible beads as a board of status columns instead of the panes
+    show_board: bool,
+    board_state: BoardState,
     /// Hide closed beads
     hide_closed: bool,
+    /// Only show beads with this status (None = all)
+    status_filter: Option<BeadStatus>,
+    /// Only show beads at this priority or more urgent (None = all)
+    max_priority: Option<u8>,
+    /// Only show beads that can be worked on now
+    ready_only: bool,
+    /// Only show beads with these labels (empty = all)
+    label_filter: Vec<String>,
+    /// Whether beads need any or all of `label_filter`
+    label_match: LabelMatch,
+    /// Label filter picker state
+    label_picker: LabelPicker,
+    /// List sort order
+    sort_mode: SortMode,
+    /// Reverse the list sort order
+    sort_reverse: bool,
+    /// Flat grouped list instead of the tree
+    group_by: GroupBy,
+    /// List every bead at the top level instead of as a tree
+    flat_list: bool,
     /// Show detail pane
     show_detail: bool,
+    /// Show the dependency graph in the detail pane instead of the details
+    show_graph: bool,
+    /// Disable all mutations (browse only)
+    read_only: bool,
+    /// `br` isn't on PATH, so mutations are disabled
+    br_missing: bool,
     /// Should the app quit
     should_quit: bool,
     /// Refresh interval
     refresh_interval: Duration,
+    /// Auto-refresh switched off at runtime (watcher and polling alike)
+    refresh_paused: bool,
+    /// When an automatic refresh last reloaded the beads (for the footer flash)
+    auto_refreshed_at: Option<Instant>,
     /// Last refresh time
     last_refresh: Instant,
     /// Layout areas for mouse handling
@@ -88,63 +332,463 @@ pub struct App {
     detail_area: Rect,
     /// Whether the pane split is currently being dragged with the mouse
     split_resize_active: bool,
+    /// Row and time of the last click in the list (for double-clicks)
+    last_click: Option<(usize, Instant)>,
+    /// Create modal contents last written to the draft file
+    saved_draft: Option<config::Draft>,
+    /// Saved filter combinations from views.toml
+    views: Views,
+    /// Name prompt for saving the current view
+    view_input: TextArea<'static>,
+    /// Highlighted row in the view picker
+    view_pick: usize,
+    /// Databases Ctrl+W switches between: the one bu started with, then
+    /// the configured workspaces
+    workspaces: Vec<(String, PathBuf)>,
+    /// Highlighted row in the workspace picker
+    workspace_pick: usize,
 }
 
 impl App {
     /// Create a new app instance
-    pub fn new(db_path: PathBuf, refresh_secs: u64) -> Result<Self> {
+    pub fn new(
+        db_path: PathBuf,
+        refresh_secs: u64,
+        themes: Vec<Theme>,
+        theme_idx: usize,
+        read_only: bool,
+        config: config::Config,
+        keymap: Keymap,
+    ) -> Result<Self> {
+        // Absolute, to match the paths in the workspace list
+        let db_path = std::fs::canonicalize(&db_path).unwrap_or(db_path);
+        let br = BrQueue::start(br_for(&db_path));
+        let workspaces = workspace_list(&db_path, &config);
+        remember_database(&db_path);
         let store = BeadStore::open(&db_path)?;
-        let beads = store.load_all()?;
+        // Nothing is drawn yet, so a locked database is worth waiting out
+        let beads = store.load_all_retrying()?;
+        let data_version = store.data_version().ok();
+        // Without br we can still browse the database, just not change it
+        let br_missing = !BrCli::is_available();
+        // Watching replaces interval polling; if it fails, polling still works
+        let watcher = if refresh_secs > 0 {
+            DbWatcher::start(&db_path).ok()
+        } else {
+            None
+        };
 
-        Ok(Self {
+        let mut app = Self {
             db_path,
+            store,
+            data_version,
+            watcher,
             beads,
+            cycles: Vec::new(),
             list_state: BeadListState::new(),
             detail_state: DetailState::new(),
-            theme_idx: 0,
+            theme_idx: theme_idx.min(themes.len().saturating_sub(1)),
+            themes,
             focus: Focus::List,
             split_percent: 40,
             input_mode: InputMode::Normal,
             search_input: TextArea::default(),
+            search_history: History::default(),
             create_modal: CreateModal::new(),
             editing_bead_id: None,
             reason_input: TextArea::default(),
             comment_input: TextArea::default(),
+            assignee_input: TextArea::default(),
+            jump_input: TextArea::default(),
+            dep_input: TextArea::default(),
+            dep_type: DependencyType::Blocks,
+            confirm_close_children: Vec::new(),
+            collapsed: HashSet::new(),
+            visual_anchor: None,
+            label_input: TextArea::default(),
+            export_input: TextArea::default(),
+            export_bead: None,
+            relative_times: config.relative_times,
+            density: config.density,
+            config,
+            keymap,
+            pending_count: None,
+            pending_g: false,
+            pending_command: None,
+            clipboard: None,
+            last_action: Vec::new(),
+            br,
+            jobs: VecDeque::new(),
+            status_message: None,
             show_labels: true,
+            show_assignee: false,
+            show_recency: false,
+            wrap_titles: false,
             show_help: false,
-            hide_closed: true,  // Start with closed beads hidden
+            show_stats: false,
+            show_board: false,
+            board_state: BoardState::default(),
+            stats_visible_only: false,
+            hide_closed: true, // Start with closed beads hidden
+            status_filter: None,
+            max_priority: None,
+            ready_only: false,
+            label_filter: Vec::new(),
+            label_match: LabelMatch::default(),
+            label_picker: LabelPicker::default(),
+            sort_mode: SortMode::default(),
+            sort_reverse: false,
+            group_by: GroupBy::default(),
+            flat_list: false,
             show_detail: false, // Start with only list visible
+            show_graph: false,
+            read_only: read_only || br_missing,
+            br_missing,
             should_quit: false,
             refresh_interval: Duration::from_secs(refresh_secs),
+            refresh_paused: false,
+            auto_refreshed_at: None,
             last_refresh: Instant::now(),
             list_area: Rect::default(),
             detail_area: Rect::default(),
             split_resize_active: false,
-        })
+            last_click: None,
+            saved_draft: None,
+            views: Views::load(),
+            view_input: TextArea::default(),
+            view_pick: 0,
+            workspaces,
+            workspace_pick: 0,
+        };
+        app.update_cycles();
+        // Icons switched to ASCII on their own; say why and how to pick
+        if app.config.glyphs.profile.is_none() && glyphs::get().profile == Profile::Ascii {
+            app.set_status(
+                "Locale isn't UTF-8, so icons are ASCII (set profile under [glyphs] in config.toml)",
+            );
+        }
+        // Bad key bindings keep their defaults; say what was ignored
+        if !app.keymap.warnings().is_empty() {
+            let warning = anyhow::anyhow!("keys.toml: {}", app.keymap.warnings().join("; "));
+            app.set_error(&warning);
+        }
+        if !app.views.warnings().is_empty() {
+            let warning = anyhow::anyhow!("views.toml: {}", app.views.warnings().join("; "));
+            app.set_error(&warning);
+        }
+        Ok(app)
+    }
+
+    /// Recompute dependency cycles, flagging any that weren't there before
+    fn update_cycles(&mut self) {
+        let cycles = find_cycles(&self.beads);
+        if let Some(new) = cycles.iter().find(|c| !self.cycles.contains(c)) {
+            self.set_status(format!("Dependency cycle: {}", new.join(", ")));
+        }
+        self.cycles = cycles;
     }
 
     /// Get the current theme
     fn theme(&self) -> &Theme {
-        &THEMES[self.theme_idx]
+        &self.themes[self.theme_idx]
     }
 
     /// Reload beads from database
     fn refresh(&mut self) -> Result<()> {
-        let store = BeadStore::open(&self.db_path)?;
-        self.beads = store.load_all()?;
+        // Reuse the connection unless the file was swapped out underneath it
+        if self.store.file_replaced() {
+            self.store = BeadStore::open(&self.db_path)?;
+        }
+        // Keep the cursor on the same bead even if the ordering changes
+        let selected_id = self.get_selected_bead().map(|b| b.id.clone());
+        self.beads = match self.store.load_all() {
+            Ok(beads) => beads,
+            // The connection can go stale when br rewrites the file in place;
+            // reopen it once before giving up. A locked database isn't stale,
+            // and the next refresh tries again.
+            Err(e) if is_busy(&e) => return Err(e),
+            Err(_) => {
+                self.store = BeadStore::open(&self.db_path)
+                    .context("Lost the database connection and couldn't reopen it")?;
+                self.store
+                    .load_all()
+                    .context("Failed to load beads, even after reopening the database")?
+            }
+        };
+        self.update_cycles();
+        self.data_version = self.store.data_version().ok();
+        self.last_refresh = Instant::now();
+        if !selected_id.is_some_and(|id| self.select_bead_id(&id)) {
+            // The bead is gone or filtered out: stay at the same row, clamped
+            self.clamp_selection();
+        }
+        // A selection whose anchor disappeared can't be extended any more
+        if self.marked_rows().is_none() {
+            self.visual_anchor = None;
+        }
+        Ok(())
+    }
+
+    /// Open the workspace at `idx` in the picker: load its database, run br
+    /// from its project directory and start over at the top of its list
+    fn switch_workspace(&mut self, idx: usize) -> Result<()> {
+        let Some((name, db_path)) = self.workspaces.get(idx).cloned() else {
+            return Ok(());
+        };
+        if db_path == self.db_path {
+            self.set_status(format!("Already in {}", name));
+            return Ok(());
+        }
+        // Queued changes belong to this workspace's br, so let them finish
+        // before it's replaced
+        if self.br.in_flight() > 0 {
+            self.set_status("Wait for changes to finish before switching workspaces");
+            return Ok(());
+        }
+        let store = BeadStore::open(&db_path)?;
+        let beads = store
+            .load_all()
+            .with_context(|| format!("Failed to load beads from {:?}", db_path))?;
+        self.br = BrQueue::start(br_for(&db_path));
+
+        if self.watcher.is_some() {
+            self.watcher = DbWatcher::start(&db_path).ok();
+        }
+        self.data_version = store.data_version().ok();
+        self.store = store;
+        self.beads = beads;
+        self.db_path = db_path;
         self.last_refresh = Instant::now();
+        // Nothing bead-specific carries over from the old database, and its
+        // filters could hide everything in the new one
+        self.collapsed.clear();
+        self.visual_anchor = None;
+        self.last_action.clear();
+        self.detail_state = DetailState::new();
+        self.reset_filters();
+        self.update_cycles();
+        remember_database(&self.db_path);
+        self.set_status(format!("Switched to {}", name));
+        Ok(())
+    }
+
+    /// Rows covered by the visual selection, from its anchor to the cursor
+    fn marked_rows(&self) -> Option<RangeInclusive<usize>> {
+        let anchor_id = self.visual_anchor.as_ref()?;
+        let tree_order = build_tree_order(&self.beads, &self.tree_options());
+        let anchor = tree_order.iter().position(|(b, _)| &b.id == anchor_id)?;
+        let cursor = self.list_state.selected()?.min(tree_order.len() - 1);
+        Some(anchor.min(cursor)..=anchor.max(cursor))
+    }
+
+    /// Beads an action applies to: the visual selection if there is one,
+    /// otherwise the bead under the cursor
+    fn action_targets(&self) -> Vec<String> {
+        if let Some(rows) = self.marked_rows() {
+            let tree_order = build_tree_order(&self.beads, &self.tree_options());
+            return tree_order
+                .into_iter()
+                .skip(*rows.start())
+                .take(rows.count())
+                .map(|(b, _)| b.id.clone())
+                .collect();
+        }
+        self.get_selected_bead()
+            .map(|b| vec![b.id.clone()])
+            .unwrap_or_default()
+    }
+
+    /// Whether the automatic refresh should run now: the watched database
+    /// changed, or (without a watcher) the polling interval elapsed.
+    ///
+    /// Refreshes are held back while typing in a prompt or modal, or while the
+    /// help overlay is open, so a reload can't stutter input or shift the bead
+    /// a pending action applies to. Once back in normal mode the overdue
+    /// refresh runs on the next tick.
+    fn auto_refresh_due(&mut self) -> bool {
+        let allowed = self.refresh_interval.as_secs() > 0
+            && !self.refresh_paused
+            && self.input_mode == InputMode::Normal
+            && !self.show_help;
+        if !allowed {
+            return false;
+        }
+        match self.watcher.as_mut() {
+            Some(watcher) => watcher.poll_changed(),
+            None => self.last_refresh.elapsed() >= self.refresh_interval,
+        }
+    }
+
+    /// Step the polling interval through `REFRESH_STEPS`, shortest first
+    fn step_refresh_interval(&mut self, faster: bool) {
+        let secs = self.refresh_interval.as_secs();
+        let next = if faster {
+            REFRESH_STEPS.iter().rev().find(|&&s| s < secs)
+        } else {
+            REFRESH_STEPS.iter().find(|&&s| s > secs)
+        };
+        // Already at the fastest or slowest step
+        let secs = next.copied().unwrap_or(secs.max(REFRESH_STEPS[0]));
+        self.refresh_interval = Duration::from_secs(secs);
+        self.refresh_paused = false;
+        self.show_refresh_status();
+    }
+
+    /// Show the auto-refresh setting in the footer
+    fn show_refresh_status(&mut self) {
+        let message = if self.refresh_paused {
+            "Auto-refresh paused (Ctrl+R resumes)".to_string()
+        } else if self.watcher.is_some() {
+            // The interval only matters once the watcher is gone
+            format!(
+                "Auto-refresh on: watching the database (polling every {}s without it)",
+                self.refresh_interval.as_secs()
+            )
+        } else {
+            format!("Auto-refresh every {}s", self.refresh_interval.as_secs())
+        };
+        self.set_status(message);
+    }
+
+    /// Reload only if the database changed since the last load
+    fn refresh_if_changed(&mut self) -> Result<()> {
+        let unchanged = !self.store.file_replaced()
+            && self.data_version.is_some()
+            && self.store.data_version().ok() == self.data_version;
+        if unchanged {
+            self.last_refresh = Instant::now();
+            return Ok(());
+        }
+        if let Err(e) = self.refresh() {
+            // Try again next interval rather than on every tick
+            self.last_refresh = Instant::now();
+            return Err(e);
+        }
+        self.auto_refreshed_at = Some(Instant::now());
         Ok(())
     }
 
+    /// Auto-refresh state for the footer marker
+    fn refresh_state(&self) -> RefreshState {
+        if self.refresh_interval.is_zero() || self.refresh_paused {
+            RefreshState::Off
+        } else if self
+            .auto_refreshed_at
+            .is_some_and(|at| at.elapsed() < REFRESH_FLASH)
+        {
+            RefreshState::JustRefreshed
+        } else {
+            RefreshState::Live
+        }
+    }
+
+    /// Show a transient message in the footer
+    fn set_status(&mut self, message: impl Into<String>) {
+        self.status_message = Some((message.into(), MessageKind::Info, Instant::now()));
+    }
+
+    /// Show a failed action's error in the footer instead of exiting
+    fn set_error(&mut self, err: &anyhow::Error) {
+        // Include the context chain, flattened to fit the one-line footer
+        let message = format!("{:#}", err)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.status_message = Some((message, MessageKind::Error, Instant::now()));
+    }
+
+    /// Get the footer status message if it hasn't expired yet
+    fn status_message(&self) -> Option<(&str, MessageKind)> {
+        self.status_message
+            .as_ref()
+            .filter(|(_, kind, at)| {
+                let ttl = match kind {
+                    MessageKind::Info => STATUS_MESSAGE_TTL,
+                    MessageKind::Error => ERROR_MESSAGE_TTL,
+                };
+                at.elapsed() < ttl
+            })
+            .map(|(msg, kind, _)| (msg.as_str(), *kind))
+    }
+
     /// Get the current filter text (if searching or has active filter)
     fn filter(&self) -> Option<String> {
         let text = self.search_input.lines().join("\n");
         if text.is_empty() { None } else { Some(text) }
     }
 
+    /// Options for building the visible tree order
+    fn tree_options(&self) -> TreeOptions {
+        TreeOptions {
+            hide_closed: self.hide_closed,
+            filter: self.filter(),
+            status: self.status_filter,
+            max_priority: self.max_priority,
+            ready_only: self.ready_only,
+            labels: self.label_filter.clone(),
+            label_match: self.label_match,
+            sort: self.sort_mode,
+            reverse: self.sort_reverse,
+            group_by: self.group_by,
+            flat: self.flat_list,
+            collapsed: self.collapsed.clone(),
+        }
+    }
+
+    /// The current filters and sort order, as a view that can be saved
+    fn current_view(&self) -> View {
+        View {
+            search: self.filter().unwrap_or_default(),
+            show_closed: !self.hide_closed,
+            status: self.status_filter,
+            max_priority: self.max_priority,
+            ready_only: self.ready_only,
+            labels: self.label_filter.clone(),
+            label_match: self.label_match,
+            sort: self.sort_mode,
+            reverse: self.sort_reverse,
+        }
+    }
+
+    /// Replace the filters and sort order with a saved view's
+    fn apply_view(&mut self, view: &View) {
+        self.search_input = TextArea::from(view.search.lines());
+        self.search_input.move_cursor(CursorMove::End);
+        self.hide_closed = !view.show_closed;
+        self.status_filter = view.status;
+        self.max_priority = view.max_priority;
+        self.ready_only = view.ready_only;
+        self.label_filter = view.labels.clone();
+        self.label_match = view.label_match;
+        self.sort_mode = view.sort;
+        self.sort_reverse = view.reverse;
+        self.list_state.first();
+    }
+
+    /// Back to the default view at the top of the list: no search or filters
+    /// and closed beads hidden, keeping the sort order
+    fn reset_filters(&mut self) {
+        self.apply_view(&View {
+            sort: self.sort_mode,
+            reverse: self.sort_reverse,
+            ..Default::default()
+        });
+    }
+
     /// Get filtered beads count (uses tree order for consistency)
     fn filtered_len(&self) -> usize {
-        build_tree_order(&self.beads, self.hide_closed, self.filter().as_deref()).len()
+        build_tree_order(&self.beads, &self.tree_options()).len()
+    }
+
+    /// Clamp the list selection to the current filtered length
+    fn clamp_selection(&mut self) {
+        let len = self.filtered_len();
+        if let Some(idx) = self.list_state.selected()
+            && idx >= len
+            && len > 0
+        {
+            self.list_state.select(Some(len - 1));
+        }
     }
 
     /// Handle a key event
@@ -158,17 +802,45 @@ impl App {
             return Ok(());
         }
 
+        // So do stats: f switches between all and visible beads, anything else closes
+        if self.show_stats {
+            match key.code {
+                KeyCode::Char('f') => self.stats_visible_only = !self.stats_visible_only,
+                _ => self.show_stats = false,
+            }
+            return Ok(());
+        }
+
         // Input mode handling (search, create, close/reopen)
         match self.input_mode {
             InputMode::Search => {
                 match key.code {
                     KeyCode::Esc => {
                         self.input_mode = InputMode::Normal;
+                        self.search_history
+                            .push(&self.search_input.lines().join("\n"));
                         self.search_input = TextArea::default();
                     }
                     KeyCode::Enter => {
                         self.input_mode = InputMode::Normal;
                         // Keep the filter text in search_input
+                        self.search_history
+                            .push(&self.search_input.lines().join("\n"));
+                    }
+                    // Recall earlier queries (instead of moving between lines)
+                    KeyCode::Up | KeyCode::Down => {
+                        let current = self.search_input.lines().join("\n");
+                        let recalled = if key.code == KeyCode::Up {
+                            self.search_history.older(&current)
+                        } else {
+                            self.search_history.newer()
+                        };
+                        if let Some(query) = recalled.map(str::to_string) {
+                            self.search_input = TextArea::from(query.lines());
+                            self.search_input.move_cursor(CursorMove::Bottom);
+                            self.search_input.move_cursor(CursorMove::End);
+                            self.list_state.first();
+                        }
                     }
                     // Ctrl+J (Shift+Enter in some terminals) - insert newline instead of delete
                     KeyCode::Char('j') if ctrl => {
@@ -179,6 +851,7 @@ impl App {
                         self.search_input.input(key);
                         // Reset selection when filter changes
                         if self.search_input.lines().join("\n").len() != old_len {
+                            self.search_history.reset();
                             self.list_state.first();
                         }
                     }
@@ -196,8 +869,9 @@ impl App {
                     ModalAction::Cancelled => {
                         self.input_mode = InputMode::Normal;
                         self.editing_bead_id = None;
+                        self.discard_draft();
                     }
-                    ModalAction::None => {}
+                    ModalAction::None => self.save_draft(),
                 }
                 return Ok(());
             }
@@ -224,9 +898,26 @@ impl App {
                         self.reason_input = TextArea::default();
                     }
                     KeyCode::Enter if !shift => {
-                        self.close_bead()?;
-                        self.input_mode = InputMode::Normal;
-                        self.reason_input = TextArea::default();
+                        // Open children outside the set being closed need confirming
+                        let targets = self.action_targets();
+                        let mut children: Vec<String> = Vec::new();
+                        for child in targets
+                            .iter()
+                            .flat_map(|id| open_descendants(&self.beads, id))
+                        {
+                            if !targets.contains(&child) && !children.contains(&child) {
+                                children.push(child);
+                            }
+                        }
+                        if children.is_empty() {
+                            self.close_bead()?;
+                            self.input_mode = InputMode::Normal;
+                            self.reason_input = TextArea::default();
+                        } else {
+                            // Keep the reason around until the user confirms
+                            self.confirm_close_children = children;
+                            self.input_mode = InputMode::ConfirmClose;
+                        }
                     }
                     // Ctrl+J (Shift+Enter in some terminals) - insert newline instead of delete
                     KeyCode::Char('j') if ctrl => {
@@ -238,6 +929,20 @@ impl App {
                 }
                 return Ok(());
             }
+            InputMode::ConfirmQuit => {
+                self.should_quit = matches!(key.code, KeyCode::Char('y') | KeyCode::Char('q'));
+                self.input_mode = InputMode::Normal;
+                return Ok(());
+            }
+            InputMode::ConfirmClose => {
+                if matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+                    self.close_bead()?;
+                }
+                self.input_mode = InputMode::Normal;
+                self.reason_input = TextArea::default();
+                self.confirm_close_children.clear();
+                return Ok(());
+            }
             InputMode::ReopeningBead => {
                 match key.code {
                     KeyCode::Esc => {
@@ -280,160 +985,696 @@ impl App {
                 }
                 return Ok(());
             }
-            InputMode::Normal => {}
-        }
-
-        // Normal mode
-        match key.code {
-            // Quit
-            KeyCode::Char('q') => self.should_quit = true,
-            KeyCode::Char('c') if ctrl => {
-                self.should_quit = true;
+            InputMode::AddingLabel => {
+                match key.code {
+                    KeyCode::Esc => {
+                        self.input_mode = InputMode::Normal;
+                        self.label_input = TextArea::default();
+                    }
+                    KeyCode::Enter => {
+                        self.label_marked()?;
+                        self.input_mode = InputMode::Normal;
+                        self.label_input = TextArea::default();
+                    }
+                    _ => {
+                        self.label_input.input(key);
+                    }
+                }
+                return Ok(());
             }
-
-            // Suspend (Ctrl+Z)
-            KeyCode::Char('z') if ctrl => {
-                return Err(anyhow::anyhow!("__SUSPEND__"));
+            InputMode::Exporting => {
+                match key.code {
+                    KeyCode::Esc => {
+                        self.input_mode = InputMode::Normal;
+                    }
+                    KeyCode::Enter => {
+                        self.input_mode = InputMode::Normal;
+                        match self.export_bead.take() {
+                            Some(id) => self.export_bead_text(&id)?,
+                            None => self.export_view()?,
+                        }
+                    }
+                    _ => {
+                        self.export_input.input(key);
+                    }
+                }
+                return Ok(());
             }
-
-            // Navigation - single line (focus-aware)
-            KeyCode::Up | KeyCode::Char('k') if !ctrl => match self.focus {
-                Focus::List => self.list_state.previous(self.filtered_len()),
-                Focus::Detail => self.detail_state.scroll_up(1),
-            },
-            KeyCode::Down | KeyCode::Char('j') if !ctrl => match self.focus {
-                Focus::List => self.list_state.next(self.filtered_len()),
-                Focus::Detail => self.detail_state.scroll_down(1),
-            },
-
-            // Navigation - page (10 lines, focus-aware)
-            KeyCode::Char('u') | KeyCode::Char('b') => match self.focus {
-                Focus::List => self.scroll_up(10),
-                Focus::Detail => self.detail_state.scroll_up(10),
-            },
-            KeyCode::Char('d') | KeyCode::Char('f') => match self.focus {
-                Focus::List => self.scroll_down(10),
-                Focus::Detail => self.detail_state.scroll_down(10),
-            },
-            KeyCode::Char('k') if ctrl => match self.focus {
-                Focus::List => self.scroll_up(10),
-                Focus::Detail => self.detail_state.scroll_up(10),
-            },
-            KeyCode::Char('j') if ctrl => match self.focus {
-                Focus::List => self.scroll_down(10),
-                Focus::Detail => self.detail_state.scroll_down(10),
-            },
-            KeyCode::PageUp => match self.focus {
-                Focus::List => self.scroll_up(10),
-                Focus::Detail => self.detail_state.scroll_up(10),
-            },
-            KeyCode::PageDown => match self.focus {
-                Focus::List => self.scroll_down(10),
-                Focus::Detail => self.detail_state.scroll_down(10),
-            },
-
-            // Navigation - first/last
-            KeyCode::Home | KeyCode::Char('g') => match self.focus {
-                Focus::List => self.list_state.first(),
-                Focus::Detail => self.detail_state.reset(),
-            },
-            KeyCode::End | KeyCode::Char('G') => match self.focus {
-                Focus::List => self.list_state.last(self.filtered_len()),
-                Focus::Detail => {
-                    // Scroll to a very large number - ratatui will clamp it
-                    self.detail_state.scroll_down(10000);
+            InputMode::Assigning => {
+                match key.code {
+                    KeyCode::Esc => {
+                        self.input_mode = InputMode::Normal;
+                        self.assignee_input = TextArea::default();
+                    }
+                    KeyCode::Enter => {
+                        self.assign_bead()?;
+                        self.input_mode = InputMode::Normal;
+                        self.assignee_input = TextArea::default();
+                    }
+                    _ => {
+                        self.assignee_input.input(key);
+                    }
                 }
-            },
-
-            // Open detail pane
-            KeyCode::Enter | KeyCode::Char('l') | KeyCode::Right if self.focus == Focus::List => {
-                self.show_detail = true;
-                self.focus = Focus::Detail;
-                self.detail_state.reset();
+                return Ok(());
             }
-
-            // Close detail pane
-            KeyCode::Esc | KeyCode::Char('h') | KeyCode::Left if self.focus == Focus::Detail => {
-                self.show_detail = false;
-                self.focus = Focus::List;
+            InputMode::JumpToId => {
+                match key.code {
+                    KeyCode::Esc => {
+                        self.input_mode = InputMode::Normal;
+                        self.jump_input = TextArea::default();
+                    }
+                    KeyCode::Enter => {
+                        let query = self.jump_input.lines().join("").trim().to_string();
+                        self.input_mode = InputMode::Normal;
+                        self.jump_input = TextArea::default();
+                        if !query.is_empty() {
+                            self.jump_to_bead(&query);
+                        }
+                    }
+                    _ => {
+                        self.jump_input.input(key);
+                    }
+                }
+                return Ok(());
             }
-
-            // Focus toggle (only when detail is shown)
-            KeyCode::Tab if self.show_detail => {
-                self.focus = match self.focus {
-                    Focus::List => Focus::Detail,
-                    Focus::Detail => Focus::List,
-                };
-                if self.focus == Focus::Detail {
-                    self.detail_state.reset();
+            InputMode::AddingDependency | InputMode::RemovingDependency => {
+                match key.code {
+                    KeyCode::Esc => {
+                        self.input_mode = InputMode::Normal;
+                        self.dep_input = TextArea::default();
+                    }
+                    KeyCode::Tab if self.input_mode == InputMode::AddingDependency => {
+                        self.dep_type = self.dep_type.next();
+                    }
+                    KeyCode::Enter => {
+                        if self.input_mode == InputMode::AddingDependency {
+                            self.add_dependency()?;
+                        } else {
+                            self.remove_dependency()?;
+                        }
+                        self.input_mode = InputMode::Normal;
+                        self.dep_input = TextArea::default();
+                    }
+                    _ => {
+                        self.dep_input.input(key);
+                    }
                 }
+                return Ok(());
             }
-
-            // Pane resizing (only when detail is shown)
-            KeyCode::Char('<') if self.show_detail => {
-                self.split_percent = self
-                    .split_percent
-                    .saturating_sub(5)
-                    .clamp(MIN_SPLIT_PERCENT, MAX_SPLIT_PERCENT);
+            InputMode::PriorityFilter => {
+                match key.code {
+                    KeyCode::Char(c @ '0'..='4') => {
+                        self.max_priority = c.to_digit(10).map(|p| p as u8);
+                    }
+                    KeyCode::Esc | KeyCode::Backspace => {
+                        self.max_priority = None;
+                    }
+                    _ => {}
+                }
+                self.input_mode = InputMode::Normal;
+                self.clamp_selection();
+                return Ok(());
             }
-            KeyCode::Char('>') if self.show_detail => {
-                self.split_percent = self
-                    .split_percent
-                    .saturating_add(5)
-                    .clamp(MIN_SPLIT_PERCENT, MAX_SPLIT_PERCENT);
+            InputMode::FilteringLabels => {
+                match self.label_picker.handle_key(key) {
+                    PickerAction::Apply(labels) => {
+                        self.label_filter = labels;
+                        self.label_match = self.label_picker.mode;
+                        self.input_mode = InputMode::Normal;
+                        self.clamp_selection();
+                    }
+                    PickerAction::Cancelled => self.input_mode = InputMode::Normal,
+                    PickerAction::None => {}
+                }
+                return Ok(());
             }
-
-            // Search
-            KeyCode::Char('/') => {
-                self.input_mode = InputMode::Search;
-                self.search_input = TextArea::default();
+            InputMode::SavingView => {
+                match key.code {
+                    KeyCode::Esc => self.input_mode = InputMode::Normal,
+                    KeyCode::Enter => {
+                        self.input_mode = InputMode::Normal;
+                        let name = self.view_input.lines().join(" ").trim().to_string();
+                        if !name.is_empty() {
+                            self.views.insert(&name, self.current_view());
+                            self.views.save()?;
+                            self.set_status(format!("Saved view {:?}", name));
+                        }
+                    }
+                    _ => {
+                        self.view_input.input(key);
+                    }
+                }
+                return Ok(());
             }
-
+            InputMode::PickingView => {
+                let last = self.views.list().len().saturating_sub(1);
+                match key.code {
+                    KeyCode::Esc => self.input_mode = InputMode::Normal,
+                    KeyCode::Enter => {
+                        self.input_mode = InputMode::Normal;
+                        if let Some((name, view)) = self.views.list().get(self.view_pick).cloned() {
+                            self.apply_view(&view);
+                            self.set_status(format!("View: {}", name));
+                        }
+                    }
+                    KeyCode::Char('j') | KeyCode::Down => {
+                        self.view_pick = (self.view_pick + 1).min(last)
+                    }
+                    KeyCode::Char('k') | KeyCode::Up => {
+                        self.view_pick = self.view_pick.saturating_sub(1)
+                    }
+                    KeyCode::Char('g') | KeyCode::Home => self.view_pick = 0,
+                    KeyCode::Char('G') | KeyCode::End => self.view_pick = last,
+                    _ => {}
+                }
+                return Ok(());
+            }
+            InputMode::PickingWorkspace => {
+                let last = self.workspaces.len().saturating_sub(1);
+                match key.code {
+                    KeyCode::Esc => self.input_mode = InputMode::Normal,
+                    KeyCode::Enter => {
+                        self.input_mode = InputMode::Normal;
+                        self.switch_workspace(self.workspace_pick)?;
+                    }
+                    KeyCode::Char('j') | KeyCode::Down => {
+                        self.workspace_pick = (self.workspace_pick + 1).min(last)
+                    }
+                    KeyCode::Char('k') | KeyCode::Up => {
+                        self.workspace_pick = self.workspace_pick.saturating_sub(1)
+                    }
+                    KeyCode::Char('g') | KeyCode::Home => self.workspace_pick = 0,
+                    KeyCode::Char('G') | KeyCode::End => self.workspace_pick = last,
+                    _ => {}
+                }
+                return Ok(());
+            }
+            InputMode::SettingPriority => {
+                self.input_mode = InputMode::Normal;
+                if let KeyCode::Char(c @ '0'..='4') = key.code {
+                    self.set_priority(c as u8 - b'0')?;
+                }
+                return Ok(());
+            }
+            InputMode::Normal => {}
+        }
+
+        // Vim-style count prefix (5j, 10k, 3G). Digits bound to an action in
+        // keys.toml keep that action, and a leading 0 isn't a count.
+        if let KeyCode::Char(c @ '0'..='9') = key.code
+            && !ctrl
+            && (c != '0' || self.pending_count.is_some())
+            && self.keymap.actions(key).is_empty()
+        {
+            let digit = c as usize - '0' as usize;
+            let count = self.pending_count.unwrap_or(0) * 10 + digit;
+            self.pending_count = Some(count.min(MAX_COUNT));
+            return Ok(());
+        }
+        let count = self.pending_count.take();
+        let pending_g = std::mem::take(&mut self.pending_g);
+        if key.code == KeyCode::Esc && (count.is_some() || pending_g) {
+            return Ok(());
+        }
+
+        // Normal mode: the first bound action that applies here wins
+        let Some(action) = self
+            .keymap
+            .actions(key)
+            .iter()
+            .copied()
+            .find(|&action| self.action_applies(action))
+        else {
+            return Ok(());
+        };
+
+        // A character key for First has to be pressed twice, as in vim's gg
+        if action == Action::First && matches!(key.code, KeyCode::Char(_)) && !pending_g {
+            self.pending_g = true;
+            self.pending_count = count;
+            return Ok(());
+        }
+
+        // Read-only mode: actions that would change beads only explain why they don't
+        if self.read_only && action.changes_beads() {
+            if self.br_missing {
+                self.set_status("br not found on PATH: changes are disabled");
+            } else {
+                self.set_status("Read-only mode: changes are disabled");
+            }
+            return Ok(());
+        }
+
+        self.perform(action, count)
+    }
+
+    /// Whether an action makes sense in the current focus and selection
+    fn action_applies(&self, action: Action) -> bool {
+        // The board has no tree, panes or visual selection to act on
+        if self.show_board {
+            return !matches!(
+                action,
+                Action::PageUp
+                    | Action::PageDown
+                    | Action::HalfPageUp
+                    | Action::HalfPageDown
+                    | Action::First
+                    | Action::Last
+                    | Action::VisualSelect
+                    | Action::ClearSelection
+                    | Action::AddLabel
+                    | Action::Close
+                    | Action::ToggleCollapse
+                    | Action::CloseDetail
+                    | Action::SwitchFocus
+                    | Action::ShrinkList
+                    | Action::GrowList
+                    | Action::CycleGroup
+                    | Action::ToggleFlat
+                    | Action::ToggleLabels
+                    | Action::ToggleAssignee
+                    | Action::ToggleRecency
+                    | Action::ToggleWrap
+                    | Action::CycleDensity
+                    | Action::ToggleGraph
+            );
+        }
+        match action {
+            Action::ColumnLeft | Action::ColumnRight | Action::MoveLeft | Action::MoveRight => {
+                false
+            }
+            Action::ClearSelection => self.visual_anchor.is_some(),
+            Action::AddLabel => self.marked_rows().is_some(),
+            Action::Close => self.marked_rows().is_some() || self.focus == Focus::Detail,
+            Action::SwitchFocus | Action::ShrinkList | Action::GrowList => self.show_detail,
+            Action::ToggleCollapse
+            | Action::OpenDetail
+            | Action::ClearFilter
+            | Action::ToggleClosed
+            | Action::CycleStatusFilter => self.focus == Focus::List,
+            Action::CloseDetail
+            | Action::Edit
+            | Action::WriteBead
+            | Action::Assign
+            | Action::ToggleInProgress
+            | Action::ToggleDeferred => self.focus == Focus::Detail,
+            Action::AddDependency | Action::RemoveDependency | Action::AddComment => {
+                self.focus == Focus::Detail && self.get_selected_bead().is_some()
+            }
+            _ => true,
+        }
+    }
+
+    /// Carry out a normal-mode action. `count` repeats movements, or picks
+    /// the row for First/Last.
+    fn perform(&mut self, action: Action, count: Option<usize>) -> Result<()> {
+        let times = count.unwrap_or(1);
+        // Counts are capped at MAX_COUNT, so this always fits
+        let lines = times as u16;
+        match action {
+            Action::Quit if self.config.confirm_quit => self.input_mode = InputMode::ConfirmQuit,
+            Action::Quit | Action::ForceQuit => self.should_quit = true,
+
+            // Suspend (Ctrl+Z)
+            Action::Suspend => {
+                return Err(anyhow::anyhow!("__SUSPEND__"));
+            }
+
+            // On the board, up and down stay in the selected bead's column
+            Action::Up if self.show_board => self.step_board_row(-(times as isize)),
+            Action::Down if self.show_board => self.step_board_row(times as isize),
+
+            // Navigation - single line (focus-aware)
+            Action::Up => match self.focus {
+                Focus::List if self.wraps_around(false) => {
+                    self.list_state.last(self.filtered_len())
+                }
+                Focus::List => self.scroll_up(times),
+                Focus::Detail => self.detail_state.scroll_up(lines),
+            },
+            Action::Down => match self.focus {
+                Focus::List if self.wraps_around(true) => self.list_state.first(),
+                Focus::List => self.scroll_down(times),
+                Focus::Detail => self.detail_state.scroll_down(lines),
+            },
+
+            // Navigation - page (10 lines, focus-aware)
+            Action::PageUp => match self.focus {
+                Focus::List => self.scroll_up(10 * times),
+                Focus::Detail => self.detail_state.scroll_up(lines.saturating_mul(10)),
+            },
+            Action::PageDown => match self.focus {
+                Focus::List => self.scroll_down(10 * times),
+                Focus::Detail => self.detail_state.scroll_down(lines.saturating_mul(10)),
+            },
+
+            // Navigation - half the visible height (vim's Ctrl+U/Ctrl+D)
+            Action::HalfPageUp => match self.focus {
+                Focus::List => self.scroll_up(self.list_half_page() * times),
+                Focus::Detail => {
+                    let half = self.detail_state.half_page();
+                    self.detail_state.scroll_up(half.saturating_mul(lines));
+                }
+            },
+            Action::HalfPageDown => match self.focus {
+                Focus::List => self.scroll_down(self.list_half_page() * times),
+                Focus::Detail => {
+                    let half = self.detail_state.half_page();
+                    self.detail_state.scroll_down(half.saturating_mul(lines));
+                }
+            },
+
+            // Navigation - first/last, or row N with a count (5gg, 5G)
+            Action::First | Action::Last if self.focus == Focus::List && count.is_some() => {
+                let len = self.filtered_len();
+                if len > 0 {
+                    self.list_state.select(Some((times - 1).min(len - 1)));
+                }
+            }
+            Action::First => match self.focus {
+                Focus::List => self.list_state.first(),
+                Focus::Detail => self.detail_state.reset(),
+            },
+            Action::Last => match self.focus {
+                Focus::List => self.list_state.last(self.filtered_len()),
+                Focus::Detail => {
+                    // Scroll to a very large number - ratatui will clamp it
+                    self.detail_state.scroll_down(10000);
+                }
+            },
+
+            // Visual selection: V marks rows from here to the cursor for bulk actions
+            Action::VisualSelect => {
+                self.visual_anchor = match self.visual_anchor {
+                    Some(_) => None,
+                    None => self.get_selected_bead().map(|b| b.id.clone()),
+                };
+            }
+            Action::ClearSelection => {
+                self.visual_anchor = None;
+            }
+            Action::AddLabel => {
+                self.input_mode = InputMode::AddingLabel;
+                self.label_input = TextArea::default();
+            }
+
+            // Collapse/expand the selected bead's children
+            Action::ToggleCollapse => {
+                self.toggle_collapsed();
+            }
+
+            // Open detail pane
+            Action::OpenDetail => {
+                self.show_board = false;
+                self.show_detail = true;
+                self.focus = Focus::Detail;
+            }
+
+            // Close detail pane
+            Action::CloseDetail => {
+                self.show_detail = false;
+                self.focus = Focus::List;
+            }
+
+            // Focus toggle (only when detail is shown)
+            Action::SwitchFocus => {
+                self.focus = match self.focus {
+                    Focus::List => Focus::Detail,
+                    Focus::Detail => Focus::List,
+                };
+            }
+
+            // Pane resizing (only when detail is shown)
+            Action::ShrinkList => {
+                self.split_percent = self
+                    .split_percent
+                    .saturating_sub(5)
+                    .clamp(MIN_SPLIT_PERCENT, MAX_SPLIT_PERCENT);
+            }
+            Action::GrowList => {
+                self.split_percent = self
+                    .split_percent
+                    .saturating_add(5)
+                    .clamp(MIN_SPLIT_PERCENT, MAX_SPLIT_PERCENT);
+            }
+
+            // Search
+            Action::Search => {
+                self.input_mode = InputMode::Search;
+                self.search_input = TextArea::default();
+            }
+
+            // Jump to a bead by ID
+            Action::JumpToId => {
+                self.input_mode = InputMode::JumpToId;
+                self.jump_input = TextArea::default();
+            }
+
             // Clear filter (when list focused or no detail)
-            KeyCode::Esc if self.focus == Focus::List => {
+            Action::ClearFilter => {
                 self.search_input = TextArea::default();
             }
 
+            // Back to the default view: every filter off, closed beads hidden
+            Action::ResetFilters => {
+                self.reset_filters();
+                self.set_status("Filters cleared");
+            }
+
             // Add new bead
-            KeyCode::Char('a') => {
+            Action::AddBead => {
                 self.input_mode = InputMode::Creating;
                 self.editing_bead_id = None;
-                self.create_modal.open();
+                self.create_modal.open(&self.beads);
+                self.create_modal.offered_draft = config::Draft::load();
+                self.saved_draft = self.create_modal.offered_draft.clone();
             }
 
             // Edit selected bead
-            KeyCode::Char('e') if self.focus == Focus::Detail => {
+            Action::Edit => {
                 // Clone the bead to avoid borrow issues
                 if let Some(bead) = self.get_selected_bead().cloned() {
                     self.input_mode = InputMode::Editing;
                     self.editing_bead_id = Some(bead.id.clone());
-                    self.create_modal.open_with_bead(&bead);
+                    self.create_modal.open_with_bead(&bead, &self.beads);
                 }
             }
 
             // Theme
-            KeyCode::Char('t') => {
-                self.theme_idx = (self.theme_idx + 1) % THEMES.len();
+            // Colors are off, so there's nothing to cycle (or remember)
+            Action::CycleTheme if self.config.no_color => {
+                self.set_status("Colors are off (NO_COLOR or --no-color)");
+            }
+            Action::CycleTheme => {
+                self.theme_idx = (self.theme_idx + 1) % self.themes.len();
+                // Remembering the theme is best-effort; never interrupt the UI for it
+                let mut state = config::State::load();
+                state.theme = Some(self.theme().name.to_string());
+                let _ = state.save();
+            }
+
+            // Cycle / reverse sort order (keep the same bead selected)
+            Action::CycleSort | Action::ReverseSort => {
+                let selected_id = self.get_selected_bead().map(|b| b.id.clone());
+                if action == Action::ReverseSort {
+                    self.sort_reverse = !self.sort_reverse;
+                } else {
+                    self.sort_mode = self.sort_mode.next();
+                }
+                if let Some(id) = selected_id {
+                    self.select_bead_id(&id);
+                }
+            }
+
+            // Cycle grouping: tree -> by status -> by type
+            Action::CycleGroup => {
+                let selected_id = self.get_selected_bead().map(|b| b.id.clone());
+                self.group_by = self.group_by.next();
+                if let Some(id) = selected_id {
+                    self.select_bead_id(&id);
+                }
+                self.set_status(match self.group_by {
+                    GroupBy::None => "Showing the tree".to_string(),
+                    group => format!("Grouped by {}", group),
+                });
+            }
+
+            // Flat list: drop the tree, keeping the filters and sort order
+            Action::ToggleFlat => {
+                let selected_id = self.get_selected_bead().map(|b| b.id.clone());
+                self.flat_list = !self.flat_list;
+                if let Some(id) = selected_id {
+                    self.select_bead_id(&id);
+                }
+                self.set_status(if self.flat_list {
+                    "Flat list"
+                } else {
+                    "Showing the tree"
+                });
+            }
+
+            // Priority filter: prompt for a 0-4 cutoff
+            Action::PriorityFilter => {
+                self.input_mode = InputMode::PriorityFilter;
             }
 
             // Toggle labels in list view
-            KeyCode::Char('L') => {
+            Action::ToggleLabels => {
                 self.show_labels = !self.show_labels;
             }
 
+            // Toggle assignees in list view
+            Action::ToggleAssignee => {
+                self.show_assignee = !self.show_assignee;
+            }
+
+            // Toggle update recency dots in list view
+            Action::ToggleRecency => {
+                self.show_recency = !self.show_recency;
+            }
+
+            // Toggle the dependency graph in the detail pane (opening it if needed)
+            Action::ToggleGraph => {
+                self.show_graph = !self.show_graph || !self.show_detail;
+                self.show_detail = true;
+            }
+
+            // Export the visible beads to Markdown, prompting for the file
+            Action::Export => {
+                self.export_input = TextArea::from(vec![export::DEFAULT_EXPORT_PATH.to_string()]);
+                self.export_input.move_cursor(tui_textarea::CursorMove::End);
+                self.export_bead = None;
+                self.input_mode = InputMode::Exporting;
+            }
+
+            // Write the selected bead's full detail to a text file (detail pane only)
+            Action::WriteBead => {
+                if let Some(id) = self.get_selected_bead().map(|b| b.id.clone()) {
+                    self.export_input = TextArea::from(vec![format!("{}.txt", id)]);
+                    self.export_input.move_cursor(tui_textarea::CursorMove::End);
+                    self.export_bead = Some(id);
+                    self.input_mode = InputMode::Exporting;
+                }
+            }
+
+            // Run the configured open command for the selected bead
+            Action::RunOpenCommand => {
+                self.run_open_command()?;
+            }
+
+            // Copy the selected bead's ID or "ID: Title" line
+            Action::CopyId | Action::CopyIdTitle => {
+                if let Some(bead) = self.get_selected_bead() {
+                    let text = if action == Action::CopyIdTitle {
+                        format!("{}: {}", bead.id, bead.title)
+                    } else {
+                        bead.id.clone()
+                    };
+                    self.copy_to_clipboard(text);
+                }
+            }
+
+            // Switch timestamps between "3 days ago" and dates
+            Action::ToggleRelativeTimes => {
+                self.relative_times = !self.relative_times;
+            }
+
+            // Toggle wrapping long titles in list view
+            Action::ToggleWrap => {
+                self.wrap_titles = !self.wrap_titles;
+            }
+
+            // Save the current filters and sort order under a name
+            Action::SaveView => {
+                self.input_mode = InputMode::SavingView;
+                self.view_input = TextArea::default();
+            }
+
+            // Pick a saved view to switch to
+            Action::PickView => {
+                if self.views.list().is_empty() {
+                    self.set_status("No saved views (m saves the current filters)");
+                } else {
+                    self.view_pick = self.view_pick.min(self.views.list().len() - 1);
+                    self.input_mode = InputMode::PickingView;
+                }
+            }
+
+            // Pick another beads database to switch to
+            Action::PickWorkspace => {
+                if self.workspaces.len() < 2 {
+                    self.set_status(
+                        "No other workspaces (add them under [workspaces] in config.toml)",
+                    );
+                } else {
+                    self.workspace_pick = self
+                        .workspaces
+                        .iter()
+                        .position(|(_, path)| *path == self.db_path)
+                        .unwrap_or(0);
+                    self.input_mode = InputMode::PickingWorkspace;
+                }
+            }
+
+            // Cycle list density: normal -> compact -> comfortable
+            Action::CycleDensity => {
+                self.density = self.density.next();
+                self.set_status(format!("List density: {}", self.density));
+            }
+
+            Action::Undo => {
+                self.undo()?;
+            }
+
             // Refresh
-            KeyCode::Char('r') => {
+            Action::Refresh => {
                 self.refresh()?;
             }
 
+            // Poll more or less often, or stop auto-refreshing altogether
+            Action::RefreshFaster => self.step_refresh_interval(true),
+            Action::RefreshSlower => self.step_refresh_interval(false),
+            Action::ToggleAutoRefresh => {
+                // Started with --refresh 0: resuming needs an interval to poll at
+                if self.refresh_interval.is_zero() {
+                    self.refresh_interval = Duration::from_secs(DEFAULT_REFRESH_SECS);
+                    self.refresh_paused = false;
+                } else {
+                    self.refresh_paused = !self.refresh_paused;
+                }
+                self.show_refresh_status();
+            }
+
+            Action::Stats => {
+                self.show_stats = true;
+            }
+
+            Action::ToggleBoard => {
+                self.show_board = !self.show_board;
+                if self.show_board {
+                    self.focus = Focus::List;
+                    self.visual_anchor = None;
+                    self.set_status(
+                        "Board: h/l switches columns, H/L moves the bead, Enter opens it",
+                    );
+                }
+            }
+            Action::ColumnLeft => self.step_board_column(-1),
+            Action::ColumnRight => self.step_board_column(1),
+            Action::MoveLeft => self.move_to_column(-1),
+            Action::MoveRight => self.move_to_column(1),
+
             // Help
-            KeyCode::Char('?') => {
+            Action::Help => {
                 self.show_help = true;
             }
 
-            // Close/reopen bead (only when detail pane is focused)
-            KeyCode::Char('x') if self.focus == Focus::Detail => {
-                if let Some(bead) = self.get_selected_bead() {
+            // Close the visual selection, or close/reopen the bead in the detail pane
+            Action::Close => {
+                if self.marked_rows().is_some() {
+                    self.input_mode = InputMode::ClosingBead;
+                    self.reason_input = TextArea::default();
+                } else if let Some(bead) = self.get_selected_bead() {
                     if bead.status == BeadStatus::Closed {
                         // Reopen the bead
                         self.input_mode = InputMode::ReopeningBead;
@@ -446,34 +1687,78 @@ impl App {
                 }
             }
 
+            // Set assignee (detail pane only), pre-filled with the current one
+            Action::Assign => {
+                if let Some(bead) = self.get_selected_bead() {
+                    let current = bead.assignee.clone().unwrap_or_default();
+                    self.assignee_input = TextArea::from(vec![current]);
+                    self.assignee_input
+                        .move_cursor(tui_textarea::CursorMove::End);
+                    self.input_mode = InputMode::Assigning;
+                }
+            }
+
+            // Add/remove a dependency on another bead (detail pane only)
+            Action::AddDependency => {
+                self.input_mode = InputMode::AddingDependency;
+                self.dep_input = TextArea::default();
+                self.dep_type = DependencyType::Blocks;
+            }
+            Action::RemoveDependency => {
+                self.input_mode = InputMode::RemovingDependency;
+                self.dep_input = TextArea::default();
+            }
+
+            // Toggle in progress/open for selected bead (detail pane only)
+            Action::ToggleInProgress => {
+                self.toggle_in_progress()?;
+            }
+
+            // Set priority of the selected bead (or visual selection): prompt for 0-4
+            Action::SetPriority => {
+                self.input_mode = InputMode::SettingPriority;
+            }
+
             // Toggle deferred/open for selected bead (detail pane only)
-            KeyCode::Char('D') if self.focus == Focus::Detail => {
+            Action::ToggleDeferred => {
                 self.toggle_deferred()?;
             }
 
-            // 'c' key - context dependent:
+            // 'c' by default - context dependent:
             // - List focused: toggle closed visibility
             // - Detail focused: add comment
-            KeyCode::Char('c') if self.focus == Focus::List => {
+            Action::ToggleClosed => {
                 self.hide_closed = !self.hide_closed;
-                // Clamp selection to new filtered length
-                let len = self.filtered_len();
-                if let Some(idx) = self.list_state.selected()
-                    && idx >= len
-                    && len > 0
-                {
-                    self.list_state.select(Some(len - 1));
-                }
+                self.clamp_selection();
             }
-            KeyCode::Char('c') if self.focus == Focus::Detail => {
-                // Only allow comments if we have a selected bead
-                if self.get_selected_bead().is_some() {
-                    self.input_mode = InputMode::AddingComment;
-                    self.comment_input = TextArea::default();
+
+            // Label filter: pick one or more labels from those in use
+            Action::LabelFilter => {
+                self.label_picker
+                    .open(&self.beads, &self.label_filter, self.label_match);
+                if self.label_picker.labels.is_empty() {
+                    self.set_status("No beads have labels");
+                } else {
+                    self.input_mode = InputMode::FilteringLabels;
                 }
             }
 
-            _ => {}
+            // Ready queue: only open/in progress beads with no open blockers
+            Action::ToggleReady => {
+                self.ready_only = !self.ready_only;
+                self.clamp_selection();
+            }
+
+            // Cycle status filter: all -> open -> in progress -> blocked -> closed -> all
+            Action::CycleStatusFilter => {
+                self.status_filter = next_status_filter(self.status_filter);
+                self.clamp_selection();
+            }
+
+            Action::AddComment => {
+                self.input_mode = InputMode::AddingComment;
+                self.comment_input = TextArea::default();
+            }
         }
 
         Ok(())
@@ -482,8 +1767,9 @@ impl App {
     /// Handle pasted text (bracketed paste mode)
     fn handle_paste(&mut self, text: &str) -> Result<()> {
         // Help overlay consumes the next interaction
-        if self.show_help {
+        if self.show_help || self.show_stats {
             self.show_help = false;
+            self.show_stats = false;
             return Ok(());
         }
 
@@ -497,10 +1783,15 @@ impl App {
                     .join(" ");
                 let _ = self.search_input.insert_str(single_line);
                 if self.search_input.lines().join("\n").len() != old_len {
+                    self.search_history.reset();
                     self.list_state.first();
                 }
             }
-            InputMode::Creating | InputMode::Editing => {
+            InputMode::Creating => {
+                self.create_modal.handle_paste(text);
+                self.save_draft();
+            }
+            InputMode::Editing => {
                 self.create_modal.handle_paste(text);
             }
             InputMode::ClosingBead | InputMode::ReopeningBead => {
@@ -509,7 +1800,38 @@ impl App {
             InputMode::AddingComment => {
                 let _ = self.comment_input.insert_str(text);
             }
-            InputMode::Normal => {}
+            InputMode::Assigning => {
+                let single_line = text.lines().next().unwrap_or("").trim();
+                let _ = self.assignee_input.insert_str(single_line);
+            }
+            InputMode::AddingLabel => {
+                let single_line = text.lines().next().unwrap_or("").trim();
+                let _ = self.label_input.insert_str(single_line);
+            }
+            InputMode::Exporting => {
+                let single_line = text.lines().next().unwrap_or("").trim();
+                let _ = self.export_input.insert_str(single_line);
+            }
+            InputMode::JumpToId => {
+                let single_line = text.lines().next().unwrap_or("").trim();
+                let _ = self.jump_input.insert_str(single_line);
+            }
+            InputMode::SavingView => {
+                let single_line = text.lines().next().unwrap_or("").trim();
+                let _ = self.view_input.insert_str(single_line);
+            }
+            InputMode::AddingDependency | InputMode::RemovingDependency => {
+                let single_line = text.lines().next().unwrap_or("").trim();
+                let _ = self.dep_input.insert_str(single_line);
+            }
+            InputMode::ConfirmClose
+            | InputMode::ConfirmQuit
+            | InputMode::PriorityFilter
+            | InputMode::FilteringLabels
+            | InputMode::PickingView
+            | InputMode::PickingWorkspace
+            | InputMode::SettingPriority
+            | InputMode::Normal => {}
         }
 
         Ok(())
@@ -526,6 +1848,20 @@ impl App {
         self.list_state.select(Some(new_pos));
     }
 
+    /// Rows in half the visible list (inside its borders), at least one
+    fn list_half_page(&self) -> usize {
+        (self.list_area.height.saturating_sub(2) / 2).max(1) as usize
+    }
+
+    /// Whether moving one step (down, or up) from the selection wraps to the
+    /// other end of the list: only with `wrap_around` set, from the last bead
+    /// (or the first)
+    fn wraps_around(&self, down: bool) -> bool {
+        let len = self.filtered_len();
+        let edge = if down { len.saturating_sub(1) } else { 0 };
+        self.config.wrap_around && len > 1 && self.list_state.selected() == Some(edge)
+    }
+
     /// Scroll down by n lines
     fn scroll_down(&mut self, n: usize) {
         let len = self.filtered_len();
@@ -555,18 +1891,38 @@ impl App {
 
                 // Check which pane was clicked
                 if self.list_area.contains((x, y).into()) {
-                    // Calculate which item was clicked
+                    // Calculate which item was clicked (items may span several rows)
                     let inner_y = y.saturating_sub(self.list_area.y + 1); // +1 for border
-                    let idx = inner_y as usize;
-                    if idx < self.filtered_len() {
+                    if let Some(idx) = self.list_state.index_at_row(inner_y)
+                        && idx < self.filtered_len()
+                    {
+                        // A second click on the same row soon after opens the detail pane
+                        let now = Instant::now();
+                        let double = self.last_click.is_some_and(|(row, at)| {
+                            row == idx && now.duration_since(at) <= DOUBLE_CLICK_TIME
+                        });
                         self.list_state.select(Some(idx));
-                        // Open detail pane on click
-                        self.show_detail = true;
-                        self.focus = Focus::Detail;
-                        self.detail_state.reset();
+                        if double {
+                            self.last_click = None;
+                            self.show_detail = true;
+                            self.focus = Focus::Detail;
+                        } else {
+                            self.last_click = Some((idx, now));
+                            self.focus = Focus::List;
+                        }
                     }
                 } else if self.detail_area.contains((x, y).into()) {
+                    // An empty detail pane has nothing to scroll or follow, so
+                    // focus stays where it was
+                    if self.get_selected_bead().is_none() {
+                        return Ok(());
+                    }
                     self.focus = Focus::Detail;
+                    // Clicking a dependency line jumps to that bead
+                    let inner_y = y.saturating_sub(self.detail_area.y + 1);
+                    if let Some(id) = self.detail_state.link_at(inner_y).map(str::to_string) {
+                        self.jump_to_bead(&id);
+                    }
                 }
             }
             MouseEventKind::Drag(MouseButton::Left) if self.split_resize_active => {
@@ -640,10 +1996,172 @@ impl App {
         self.split_percent = raw_percent.clamp(MIN_SPLIT_PERCENT, MAX_SPLIT_PERCENT);
     }
 
+    /// Select the row showing the bead with this ID, if it is visible
+    fn select_bead_id(&mut self, id: &str) -> bool {
+        let tree_order = build_tree_order(&self.beads, &self.tree_options());
+        match tree_order.iter().position(|(b, _)| b.id == id) {
+            Some(idx) => {
+                self.list_state.select(Some(idx));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Resolve user input to a bead ID: an exact ID, or a unique ID suffix like "3jg"
+    fn resolve_bead_id(&self, query: &str) -> std::result::Result<String, String> {
+        let query = query.trim().to_lowercase();
+        if let Some(bead) = self.beads.iter().find(|b| b.id.to_lowercase() == query) {
+            return Ok(bead.id.clone());
+        }
+
+        let mut matches = self
+            .beads
+            .iter()
+            .filter(|b| b.id.to_lowercase().ends_with(&query));
+        match (matches.next(), matches.next()) {
+            (Some(bead), None) => Ok(bead.id.clone()),
+            (Some(_), Some(_)) => Err(format!("Ambiguous bead ID: {}", query)),
+            _ => Err(format!("No bead with ID {}", query)),
+        }
+    }
+
+    /// Select a bead by ID (exact, or a unique ID suffix like "3jg") and open it.
+    ///
+    /// If the bead is hidden by the current filters, they are cleared so it
+    /// becomes visible.
+    fn jump_to_bead(&mut self, query: &str) {
+        let id = match self.resolve_bead_id(query) {
+            Ok(id) => id,
+            Err(message) => {
+                self.set_status(message);
+                return;
+            }
+        };
+        let is_closed = self
+            .beads
+            .iter()
+            .any(|b| b.id == id && b.status == BeadStatus::Closed);
+
+        if !self.select_bead_id(&id) {
+            // It may just be under a collapsed parent
+            self.collapsed.clear();
+        }
+        if !self.select_bead_id(&id) {
+            // Hidden by filters: clear them so the bead shows up
+            self.search_input = TextArea::default();
+            self.status_filter = None;
+            self.max_priority = None;
+            self.ready_only = false;
+            self.label_filter.clear();
+            if is_closed {
+                self.hide_closed = false;
+            }
+            self.select_bead_id(&id);
+            self.set_status(format!("Cleared filters to show {}", id));
+        }
+
+        self.show_detail = true;
+        self.focus = Focus::Detail;
+    }
+
+    /// Write the visible beads, in list order, to the path in the export prompt
+    fn export_view(&mut self) -> Result<()> {
+        let path = self.export_input.lines().join("").trim().to_string();
+        if path.is_empty() {
+            return Ok(());
+        }
+        let options = self.tree_options();
+        let rows = build_tree_order(&self.beads, &options);
+        std::fs::write(&path, export::to_markdown(&rows))
+            .with_context(|| format!("Failed to write {}", path))?;
+        self.set_status(format!("Exported {} beads to {}", rows.len(), path));
+        Ok(())
+    }
+
+    /// Run `open_command` for the selected bead: in the background, or queued
+    /// for the main loop when it needs the terminal
+    fn run_open_command(&mut self) -> Result<()> {
+        let Some(id) = self.get_selected_bead().map(|b| b.id.clone()) else {
+            return Ok(());
+        };
+        let Some(command) = self.config.open_command_for(&id) else {
+            self.set_status("No open_command set in config.toml");
+            return Ok(());
+        };
+        if self.config.open_in_terminal {
+            self.pending_command = Some(command);
+            return Ok(());
+        }
+
+        let mut child = std::process::Command::new("sh")
+            .arg("-c")
+            .arg(&command)
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn()
+            .with_context(|| format!("Failed to run {:?}", command))?;
+        // Reap it in the background so it doesn't linger as a zombie
+        std::thread::spawn(move || child.wait());
+        self.set_status(format!("Ran {}", command));
+        Ok(())
+    }
+
+    /// Put text on the system clipboard; a missing clipboard is only reported
+    fn copy_to_clipboard(&mut self, text: String) {
+        if self.clipboard.is_none() {
+            match arboard::Clipboard::new() {
+                Ok(clipboard) => self.clipboard = Some(clipboard),
+                Err(e) => {
+                    self.set_status(format!("Clipboard unavailable: {}", e));
+                    return;
+                }
+            }
+        }
+        let Some(clipboard) = self.clipboard.as_mut() else {
+            return;
+        };
+        match clipboard.set_text(text.clone()) {
+            Ok(()) => self.set_status(format!("Copied {}", text)),
+            Err(e) => self.set_status(format!("Copy failed: {}", e)),
+        }
+    }
+
+    /// Write one bead's detail and comments as plain text to the path in the export prompt
+    fn export_bead_text(&mut self, id: &str) -> Result<()> {
+        let path = self.export_input.lines().join("").trim().to_string();
+        let Some(bead) = self.beads.iter().find(|b| b.id == id) else {
+            return Ok(());
+        };
+        if path.is_empty() {
+            return Ok(());
+        }
+        std::fs::write(&path, export::bead_to_text(bead))
+            .with_context(|| format!("Failed to write {}", path))?;
+        self.set_status(format!("Wrote {} to {}", id, path));
+        Ok(())
+    }
+
+    /// Collapse or expand the selected bead's subtree
+    fn toggle_collapsed(&mut self) {
+        let Some(idx) = self.list_state.selected() else {
+            return;
+        };
+        let rows = build_tree_rows(&self.beads, &self.tree_options());
+        let Some(row) = rows.get(idx).filter(|row| row.has_children()) else {
+            return;
+        };
+        let id = row.bead.id.clone();
+        if !self.collapsed.remove(&id) {
+            self.collapsed.insert(id);
+        }
+    }
+
     /// Get the currently selected bead
     fn get_selected_bead(&self) -> Option<&Bead> {
         let idx = self.list_state.selected()?;
-        let tree_order = build_tree_order(&self.beads, self.hide_closed, self.filter().as_deref());
+        let tree_order = build_tree_order(&self.beads, &self.tree_options());
         tree_order.get(idx).map(|(bead, _)| *bead)
     }
 
@@ -651,37 +2169,389 @@ impl App {
     fn toggle_deferred(&mut self) -> Result<()> {
         if let Some(bead) = self.get_selected_bead() {
             let id = bead.id.clone();
-            let next_status = match bead.status {
+            let old = bead.status;
+            let next_status = match old {
                 BeadStatus::Open => Some("deferred"),
                 BeadStatus::Deferred => Some("open"),
                 _ => None,
             };
 
             if let Some(status) = next_status {
-                BrCli::update_status(&id, status)?;
-                self.refresh()?;
+                let op = BrOp::UpdateStatus {
+                    id: id.clone(),
+                    status: status.to_string(),
+                };
+                self.submit(
+                    vec![(op, vec![Mutation::StatusChange { id, old }])],
+                    |_, _| {},
+                );
             }
         }
 
         Ok(())
     }
 
-    /// Close the selected bead with a reason
-    fn close_bead(&mut self) -> Result<()> {
-        if let Some(bead) = self.get_selected_bead() {
-            let id = bead.id.clone();
-            let reason = self.reason_input.lines().join("\n");
-            let reason_opt = if reason.is_empty() {
-                None
-            } else {
-                Some(reason)
+    /// Toggle selected bead between in progress and open.
+    ///
+    /// Blocked beads can only be started once their blockers are closed.
+    fn toggle_in_progress(&mut self) -> Result<()> {
+        let Some(bead) = self.get_selected_bead() else {
+            return Ok(());
+        };
+        let id = bead.id.clone();
+        let next_status = match bead.status {
+            BeadStatus::InProgress => "open",
+            BeadStatus::Open | BeadStatus::Deferred => "in_progress",
+            BeadStatus::Blocked => {
+                let open_blockers = self.open_blockers(bead);
+                if !open_blockers.is_empty() {
+                    let message = format!("{} is blocked by {}", id, open_blockers.join(", "));
+                    self.set_status(message);
+                    return Ok(());
+                }
+                "in_progress"
+            }
+            BeadStatus::Closed => {
+                self.set_status(format!("{} is closed; reopen it with x first", id));
+                return Ok(());
+            }
+        };
+
+        let old = bead.status;
+        let op = BrOp::UpdateStatus {
+            id: id.clone(),
+            status: next_status.to_string(),
+        };
+        self.submit(
+            vec![(op, vec![Mutation::StatusChange { id, old }])],
+            |_, _| {},
+        );
+        Ok(())
+    }
+
+    /// IDs of the beads blocking `bead` that aren't closed yet
+    fn open_blockers<'a>(&self, bead: &'a Bead) -> Vec<&'a str> {
+        bead.blocked_by
+            .iter()
+            .filter(|blocker| {
+                self.beads
+                    .iter()
+                    .any(|b| &b.id == *blocker && b.status != BeadStatus::Closed)
+            })
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// The visible beads split into board columns, with the selected bead's
+    /// column and row in it
+    fn board_position(&self) -> ([Vec<&Bead>; 4], Option<(usize, usize)>) {
+        let tree_order = build_tree_order(&self.beads, &self.tree_options());
+        let not_closed = not_closed_ids(&self.beads);
+        let columns = board::columns(tree_order.iter().map(|(b, _)| *b), &not_closed);
+        let selected = self.get_selected_bead().and_then(|selected| {
+            columns.iter().enumerate().find_map(|(col, beads)| {
+                let row = beads.iter().position(|b| b.id == selected.id)?;
+                Some((col, row))
+            })
+        });
+        (columns, selected)
+    }
+
+    /// Move the board selection up or down within its column
+    fn step_board_row(&mut self, delta: isize) {
+        let (columns, selected) = self.board_position();
+        let Some((col, row)) = selected else {
+            return;
+        };
+        let row = row.saturating_add_signed(delta).min(columns[col].len() - 1);
+        let id = columns[col][row].id.clone();
+        self.select_bead_id(&id);
+    }
+
+    /// Move the board selection to the next column in `dir` that has beads,
+    /// keeping the row where it can
+    fn step_board_column(&mut self, dir: isize) {
+        let (columns, selected) = self.board_position();
+        let Some((mut col, row)) = selected else {
+            return;
+        };
+        let id = loop {
+            col = match col.checked_add_signed(dir) {
+                Some(next) if next < columns.len() => next,
+                _ => return,
             };
-            BrCli::close(&id, reason_opt.as_deref())?;
-            self.refresh()?;
+            if let Some(bead) = columns[col].get(row).or(columns[col].last()) {
+                break bead.id.clone();
+            }
+        };
+        self.select_bead_id(&id);
+    }
+
+    /// Move the selected bead to the neighbouring board column by giving it
+    /// that column's status. Like `i`, beads can't start while they have open
+    /// blockers.
+    fn move_to_column(&mut self, dir: isize) {
+        let (_, selected) = self.board_position();
+        let (Some((col, _)), Some(bead)) = (selected, self.get_selected_bead()) else {
+            return;
+        };
+        let Some(target) = col
+            .checked_add_signed(dir)
+            .filter(|c| *c < board::COLUMNS.len())
+        else {
+            return;
+        };
+        let status = board::COLUMNS[target];
+        let id = bead.id.clone();
+        let open_blockers = self.open_blockers(bead);
+        if status == BeadStatus::InProgress && !open_blockers.is_empty() {
+            let message = format!("{} is blocked by {}", id, open_blockers.join(", "));
+            self.set_status(message);
+            return;
+        }
+
+        let op = if status == BeadStatus::Closed {
+            BrOp::Close {
+                id: id.clone(),
+                reason: None,
+            }
+        } else {
+            BrOp::UpdateStatus {
+                id: id.clone(),
+                status: status.to_string(),
+            }
+        };
+        let undo = Mutation::StatusChange {
+            id: id.clone(),
+            old: bead.status,
+        };
+        self.submit(vec![(op, vec![undo])], move |app, _| {
+            app.set_status(format!("Moved {} to {}", id, board::HEADINGS[target]));
+        });
+    }
+
+    /// Close the selected bead (or every bead in the visual selection) with a reason
+    fn close_bead(&mut self) -> Result<()> {
+        let reason = self.reason_input.lines().join("\n");
+        let reason_opt = if reason.is_empty() {
+            None
+        } else {
+            Some(reason)
+        };
+        let targets: Vec<(String, BeadStatus)> = self
+            .action_targets()
+            .into_iter()
+            .filter_map(|id| {
+                let bead = self.beads.iter().find(|b| b.id == id)?;
+                (bead.status != BeadStatus::Closed).then_some((id, bead.status))
+            })
+            .collect();
+        let bulk = self.visual_anchor.take().is_some();
+
+        let steps: Vec<_> = targets
+            .into_iter()
+            .map(|(id, old)| {
+                let op = BrOp::Close {
+                    id: id.clone(),
+                    reason: reason_opt.clone(),
+                };
+                (op, vec![Mutation::StatusChange { id, old }])
+            })
+            .collect();
+        let count = steps.len();
+        self.submit_each(steps, move |app, _| {
+            if bulk {
+                app.set_status(format!("Closed {} beads", count));
+            }
+        });
+        Ok(())
+    }
+
+    /// Add the label typed into the prompt to every bead in the visual selection
+    fn label_marked(&mut self) -> Result<()> {
+        let label = self.label_input.lines().join("").trim().to_string();
+        if label.is_empty() {
+            return Ok(());
         }
+        let targets: Vec<String> = self
+            .action_targets()
+            .into_iter()
+            .filter(|id| {
+                self.beads
+                    .iter()
+                    .any(|b| &b.id == id && !b.labels.contains(&label))
+            })
+            .collect();
+
+        let steps: Vec<_> = targets
+            .into_iter()
+            .map(|id| {
+                let op = BrOp::AddLabel {
+                    id: id.clone(),
+                    label: label.clone(),
+                };
+                let label = label.clone();
+                (op, vec![Mutation::LabelAdd { id, label }])
+            })
+            .collect();
+        let message = format!("Labeled {} beads [{}]", steps.len(), label);
+        self.submit_each(steps, move |app, _| app.set_status(message));
+        Ok(())
+    }
+
+    /// Set the priority of the selected bead, or every bead in the visual selection
+    fn set_priority(&mut self, priority: u8) -> Result<()> {
+        let targets: Vec<(String, u8)> = self
+            .action_targets()
+            .into_iter()
+            .filter_map(|id| {
+                let bead = self.beads.iter().find(|b| b.id == id)?;
+                (bead.priority != priority).then_some((id, bead.priority))
+            })
+            .collect();
+
+        let message = match targets.as_slice() {
+            [(only, _)] => format!("{} is now P{}", only, priority),
+            _ => format!("Set {} beads to P{}", targets.len(), priority),
+        };
+        let steps = targets
+            .into_iter()
+            .map(|(id, old)| {
+                let op = BrOp::UpdateField {
+                    id: id.clone(),
+                    field: "priority",
+                    value: priority.to_string(),
+                };
+                let old = old.to_string();
+                (
+                    op,
+                    vec![Mutation::FieldChange {
+                        id,
+                        field: "priority",
+                        old,
+                    }],
+                )
+            })
+            .collect();
+        self.submit_each(steps, move |app, _| app.set_status(message));
         Ok(())
     }
 
+    /// Queue br commands for a change, each with what it changes for undo.
+    /// Undo, the refresh and `then` happen once they've run (see `finish_jobs`).
+    /// A failed command skips the ones after it.
+    fn submit(
+        &mut self,
+        steps: Vec<(BrOp, Vec<Mutation>)>,
+        then: impl FnOnce(&mut App, &str) + 'static,
+    ) {
+        self.queue(steps, OnFailure::Stop, then);
+    }
+
+    /// Like `submit`, for commands that don't depend on each other (one per
+    /// bead in a selection): all of them run even if some fail
+    fn submit_each(
+        &mut self,
+        steps: Vec<(BrOp, Vec<Mutation>)>,
+        then: impl FnOnce(&mut App, &str) + 'static,
+    ) {
+        self.queue(steps, OnFailure::Continue, then);
+    }
+
+    fn queue(
+        &mut self,
+        steps: Vec<(BrOp, Vec<Mutation>)>,
+        on_failure: OnFailure,
+        then: impl FnOnce(&mut App, &str) + 'static,
+    ) {
+        if steps.is_empty() {
+            return;
+        }
+        let beads = steps
+            .iter()
+            .map(|(op, _)| op.bead_id().map(str::to_string))
+            .collect();
+        let (ops, undo) = steps.into_iter().unzip();
+        self.br.send(ops, on_failure);
+        self.jobs.push_back(Job {
+            undo,
+            beads,
+            on_failure,
+            then: Box::new(then),
+            sent: Instant::now(),
+        });
+    }
+
+    /// Apply the results of jobs whose br commands have finished: remember
+    /// whatever succeeded for undo, refresh, then run the job's follow-up or
+    /// report what failed
+    fn finish_jobs(&mut self) {
+        while let Some(results) = self.br.try_finished() {
+            let Some(job) = self.jobs.pop_front() else {
+                continue;
+            };
+            let count = job.undo.len();
+            let mut done = Vec::new();
+            let mut output = String::new();
+            let mut error = None;
+            let mut failed = 0;
+            let mut failed_beads = Vec::new();
+            for ((result, undo), bead) in results.into_iter().zip(job.undo).zip(job.beads) {
+                match result {
+                    Ok(out) => {
+                        output = out;
+                        done.extend(undo);
+                    }
+                    Err(e) => {
+                        failed += 1;
+                        failed_beads.extend(bead);
+                        error.get_or_insert(e);
+                    }
+                }
+            }
+            if !done.is_empty() {
+                self.last_action = done;
+            }
+            if let Err(e) = self.refresh() {
+                self.set_error(&e);
+            }
+            match error {
+                // Say how many of a selection's changes didn't go through
+                Some(e) if job.on_failure == OnFailure::Continue && count > 1 => {
+                    let e = e.context(format!(
+                        "{} of {} changes failed ({})",
+                        failed,
+                        count,
+                        failed_beads.join(", ")
+                    ));
+                    self.set_error(&e);
+                }
+                Some(e) => self.set_error(&e),
+                None => (job.then)(self, &output),
+            }
+        }
+    }
+
+    /// Counts for the stats overlay, over everything loaded or just what the list shows
+    fn stats(&self) -> BeadStats {
+        if self.stats_visible_only {
+            let visible = build_tree_order(&self.beads, &self.tree_options());
+            bead_stats(visible.into_iter().map(|(bead, _)| bead))
+        } else {
+            bead_stats(&self.beads)
+        }
+    }
+
+    /// Footer spinner while br commands are running
+    fn busy_label(&self) -> Option<String> {
+        let job = self.jobs.front()?;
+        let frame = SPINNER[(job.sent.elapsed().as_millis() / 100) as usize % SPINNER.len()];
+        Some(match self.jobs.len() {
+            1 => format!("{} saving", frame),
+            n => format!("{} saving {} changes", frame, n),
+        })
+    }
+
     /// Reopen the selected bead with a reason
     fn reopen_bead(&mut self) -> Result<()> {
         if let Some(bead) = self.get_selected_bead() {
@@ -693,13 +2563,152 @@ impl App {
                 Some(reason)
             };
             // Use update_status to set back to open and add a comment with the reason
-            BrCli::update_status(&id, "open")?;
+            let old = bead.status;
+            let mut steps = vec![(
+                BrOp::UpdateStatus {
+                    id: id.clone(),
+                    status: "open".to_string(),
+                },
+                vec![Mutation::StatusChange {
+                    id: id.clone(),
+                    old,
+                }],
+            )];
             if let Some(r) = reason_opt {
-                // Add the reason as a comment
-                let _ = BrCli::add_comment(&id, &format!("Reopened: {}", r));
+                let text = format!("Reopened: {}", r);
+                steps.push((BrOp::AddComment { id, text }, vec![]));
+            }
+            self.submit(steps, |_, _| {});
+        }
+        Ok(())
+    }
+
+    /// Set (or clear, when empty) the selected bead's assignee
+    fn assign_bead(&mut self) -> Result<()> {
+        if let Some(bead) = self.get_selected_bead() {
+            let id = bead.id.clone();
+            let assignee = self.assignee_input.lines().join("").trim().to_string();
+
+            if bead.assignee.as_deref().unwrap_or("") == assignee {
+                return Ok(());
             }
-            self.refresh()?;
+
+            let old = bead.assignee.clone().unwrap_or_default();
+            let op = BrOp::UpdateField {
+                id: id.clone(),
+                field: "assignee",
+                value: assignee,
+            };
+            let undo = Mutation::FieldChange {
+                id,
+                field: "assignee",
+                old,
+            };
+            self.submit(vec![(op, vec![undo])], |_, _| {});
+        }
+        Ok(())
+    }
+
+    /// Make the selected bead depend on the bead typed into the prompt
+    fn add_dependency(&mut self) -> Result<()> {
+        let query = self.dep_input.lines().join("");
+        if query.trim().is_empty() {
+            return Ok(());
         }
+        let Some(id) = self.get_selected_bead().map(|b| b.id.clone()) else {
+            return Ok(());
+        };
+        let target = match self.resolve_bead_id(&query) {
+            Ok(target) if target == id => {
+                self.set_status("A bead can't depend on itself");
+                return Ok(());
+            }
+            Ok(target) => target,
+            Err(message) => {
+                self.set_status(message);
+                return Ok(());
+            }
+        };
+
+        let op = BrOp::AddDependency {
+            from: id.clone(),
+            to: target.clone(),
+            dep_type: self.dep_type.to_string(),
+        };
+        let undo = Mutation::DependencyAdd {
+            from: id.clone(),
+            to: target.clone(),
+        };
+        let mut message = format!("{} is now {} {}", id, self.dep_type.describe(), target);
+        self.submit(vec![(op, vec![undo])], move |app, _| {
+            if app
+                .cycles
+                .iter()
+                .any(|c| c.contains(&id) && c.contains(&target))
+            {
+                message.push_str(" (this creates a dependency cycle!)");
+            }
+            app.set_status(message);
+        });
+        Ok(())
+    }
+
+    /// Remove the dependency between the selected bead and the bead typed into the prompt
+    fn remove_dependency(&mut self) -> Result<()> {
+        let query = self.dep_input.lines().join("");
+        if query.trim().is_empty() {
+            return Ok(());
+        }
+        let Some(id) = self.get_selected_bead().map(|b| b.id.clone()) else {
+            return Ok(());
+        };
+        let target = match self.resolve_bead_id(&query) {
+            Ok(target) => target,
+            Err(message) => {
+                self.set_status(message);
+                return Ok(());
+            }
+        };
+
+        // The edge is stored on the dependent bead, which may be the target
+        // (e.g. a bead this one blocks, or one of its children)
+        let target_depends_on_us = self
+            .beads
+            .iter()
+            .any(|b| b.id == target && (b.blocked_by.contains(&id) || b.parent_ids.contains(&id)));
+        let (from, to) = if target_depends_on_us {
+            (target.as_str(), id.as_str())
+        } else {
+            (id.as_str(), target.as_str())
+        };
+
+        let dep_type = self
+            .beads
+            .iter()
+            .find(|b| b.id == from)
+            .map(|b| {
+                if b.parent_ids.iter().any(|p| p == to) {
+                    DependencyType::ParentChild
+                } else if b.blocked_by.iter().any(|p| p == to) {
+                    DependencyType::Blocks
+                } else {
+                    DependencyType::Related
+                }
+            })
+            .unwrap_or(DependencyType::Blocks);
+        let op = BrOp::RemoveDependency {
+            from: from.to_string(),
+            to: to.to_string(),
+        };
+        let undo = Mutation::DependencyRemove {
+            from: from.to_string(),
+            to: to.to_string(),
+            dep_type,
+        };
+        let message = format!("Removed dependency between {} and {}", id, target);
+        self.submit(vec![(op, vec![undo])], move |app, _| {
+            app.set_status(message)
+        });
         Ok(())
     }
 
@@ -714,12 +2723,41 @@ impl App {
                 return Ok(());
             }
 
-            BrCli::add_comment(&id, &comment_text)?;
-            self.refresh()?;
+            // Comments can't be taken back, so don't offer to undo anything older
+            self.last_action.clear();
+            let op = BrOp::AddComment {
+                id,
+                text: comment_text,
+            };
+            self.submit(vec![(op, vec![])], |_, _| {});
         }
         Ok(())
     }
 
+    /// Write the create modal's contents to the draft file when they change
+    fn save_draft(&mut self) {
+        if self.create_modal.offered_draft.is_some() {
+            return;
+        }
+        let draft = self.create_modal.draft();
+        if self.saved_draft.as_ref() == Some(&draft) {
+            return;
+        }
+        // Best-effort like the theme state; a failed write shouldn't interrupt typing
+        let _ = if draft.is_empty() {
+            config::Draft::clear()
+        } else {
+            draft.save()
+        };
+        self.saved_draft = Some(draft);
+    }
+
+    /// Forget the draft once the bead is created or deliberately thrown away
+    fn discard_draft(&mut self) {
+        let _ = config::Draft::clear();
+        self.saved_draft = None;
+    }
+
     /// Create a new bead from the create modal
     fn create_bead(&mut self) -> Result<()> {
         let title = self.create_modal.get_title().to_string();
@@ -732,20 +2770,23 @@ impl App {
         let priority = self.create_modal.priority;
         let labels = self.create_modal.get_labels();
 
-        // Create the bead
-        let id = BrCli::create(&title, bead_type, priority, description.as_deref(), None)?;
-
-        // Add labels if any
-        if !labels.is_empty() && !id.is_empty() {
-            for label in &labels {
-                let _ = BrCli::add_label(&id, label);
+        let op = BrOp::Create {
+            title,
+            bead_type,
+            priority,
+            description,
+            parent: self.create_modal.parent.clone(),
+            labels,
+        };
+        self.last_action.clear();
+        // The draft is kept until the bead exists, so a failed create can be retried
+        self.submit(vec![(op, vec![])], |app, id| {
+            app.discard_draft();
+            // Select the newly created bead (falls back to the top if it's filtered out)
+            if !app.select_bead_id(id) {
+                app.list_state.first();
             }
-        }
-
-        self.refresh()?;
-
-        // Select the newly created bead (should be near the top after refresh)
-        self.list_state.first();
+        });
 
         Ok(())
     }
@@ -770,63 +2811,175 @@ impl App {
         let new_description = self.create_modal.get_description();
         let new_type = self.create_modal.bead_type;
         let new_priority = self.create_modal.priority;
+        let new_status = self.create_modal.status;
+        let new_assignee = self.create_modal.get_assignee();
         let new_labels: std::collections::HashSet<String> =
             self.create_modal.get_labels().into_iter().collect();
         let old_labels: std::collections::HashSet<String> =
             original.labels.iter().cloned().collect();
 
-        // Build update command with only changed fields
-        let mut updates_needed = false;
-
-        // Check title
-        if new_title != original.title {
-            BrCli::update_field(&id, "title", &new_title)?;
-            updates_needed = true;
+        // Collect the changed fields and labels into a single br update,
+        // recording each change for undo
+        let mut fields = Vec::new();
+        let mut undo = Vec::new();
+        let mut field = |field: &'static str, old: &str, new: &str| {
+            if new != old {
+                fields.push((field, new.to_string()));
+                undo.push(Mutation::FieldChange {
+                    id: id.clone(),
+                    field,
+                    old: old.to_string(),
+                });
+            }
+        };
+        field("title", &original.title, &new_title);
+        field(
+            "description",
+            original.description.as_deref().unwrap_or(""),
+            new_description.as_deref().unwrap_or(""),
+        );
+        field(
+            "type",
+            &original.bead_type.to_string(),
+            &new_type.to_string(),
+        );
+        field(
+            "priority",
+            &original.priority.to_string(),
+            &new_priority.to_string(),
+        );
+        field(
+            "assignee",
+            original.assignee.as_deref().unwrap_or(""),
+            &new_assignee,
+        );
+
+        // Status rides along too, except closing, which goes through br close like x
+        let status_change = (new_status != original.status).then(|| Mutation::StatusChange {
+            id: id.clone(),
+            old: original.status,
+        });
+        let closing = new_status == BeadStatus::Closed && status_change.is_some();
+        if !closing && let Some(change) = status_change.clone() {
+            fields.push(("status", new_status.to_string()));
+            undo.push(change);
         }
 
-        // Check description
-        let old_desc = original.description.as_deref().unwrap_or("");
-        let new_desc_str = new_description.as_deref().unwrap_or("");
-        if new_desc_str != old_desc {
-            BrCli::update_field(&id, "description", new_desc_str)?;
-            updates_needed = true;
+        // Labels: add new ones, remove old ones
+        let mut add_labels: Vec<String> = new_labels.difference(&old_labels).cloned().collect();
+        let mut remove_labels: Vec<String> = old_labels.difference(&new_labels).cloned().collect();
+        add_labels.sort();
+        remove_labels.sort();
+        for label in &add_labels {
+            let (id, label) = (id.clone(), label.clone());
+            undo.push(Mutation::LabelAdd { id, label });
         }
-
-        // Check type
-        if new_type != original.bead_type {
-            BrCli::update_field(&id, "type", &new_type.to_string())?;
-            updates_needed = true;
+        for label in &remove_labels {
+            let (id, label) = (id.clone(), label.clone());
+            undo.push(Mutation::LabelRemove { id, label });
         }
 
-        // Check priority
-        if new_priority != original.priority {
-            BrCli::update_field(&id, "priority", &new_priority.to_string())?;
-            updates_needed = true;
+        let mut steps = Vec::new();
+        if !undo.is_empty() {
+            let op = BrOp::Update {
+                id: id.clone(),
+                fields,
+                add_labels,
+                remove_labels,
+            };
+            steps.push((op, undo));
         }
-
-        // Handle labels: add new ones, remove old ones
-        let labels_to_add: Vec<_> = new_labels.difference(&old_labels).collect();
-        let labels_to_remove: Vec<_> = old_labels.difference(&new_labels).collect();
-
-        for label in labels_to_add {
-            BrCli::add_label(&id, label)?;
-            updates_needed = true;
+        if closing && let Some(change) = status_change {
+            steps.push((BrOp::Close { id, reason: None }, vec![change]));
         }
 
-        for label in labels_to_remove {
-            BrCli::remove_label(&id, label)?;
-            updates_needed = true;
-        }
+        self.submit(steps, |_, _| {});
+        Ok(())
+    }
 
-        // Refresh if we made any changes
-        if updates_needed {
-            self.refresh()?;
+    /// Revert the changes made by the last action
+    fn undo(&mut self) -> Result<()> {
+        // The last action is only known once its commands have finished
+        if !self.jobs.is_empty() {
+            self.set_status("Still saving; undo once the last change is done");
+            return Ok(());
         }
+        let action = std::mem::take(&mut self.last_action);
+        let Some(first) = action.first() else {
+            self.set_status("Nothing to undo");
+            return Ok(());
+        };
+        let id = first.bead_id().to_string();
+        let mut ids: Vec<&str> = action.iter().map(Mutation::bead_id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        let message = match ids.len() {
+            1 => format!("Undid last change to {}", id),
+            n => format!("Undid last change to {} beads", n),
+        };
 
+        // Revert in reverse order so multi-field edits unwind cleanly
+        let steps = action.iter().rev().map(|m| (m.revert(), vec![])).collect();
+        self.submit(steps, move |app, _| {
+            app.select_bead_id(&id);
+            app.set_status(message);
+        });
         Ok(())
     }
 }
 
+/// Next status in the list filter cycle (None = show all statuses)
+fn next_status_filter(current: Option<BeadStatus>) -> Option<BeadStatus> {
+    match current {
+        None => Some(BeadStatus::Open),
+        Some(BeadStatus::Open) => Some(BeadStatus::InProgress),
+        Some(BeadStatus::InProgress) => Some(BeadStatus::Blocked),
+        Some(BeadStatus::Blocked) => Some(BeadStatus::Closed),
+        Some(BeadStatus::Closed) | Some(BeadStatus::Deferred) => None,
+    }
+}
+
+/// Workspaces for the picker: the configured ones, led by the database bu
+/// started with unless it's among them, then recently opened databases that
+/// still exist. Unconfigured ones are named after their project directory.
+fn workspace_list(db_path: &Path, config: &config::Config) -> Vec<(String, PathBuf)> {
+    let named = |path: &Path| {
+        let name = config::workspace_root(path)
+            .and_then(Path::file_name)
+            .map_or("current".to_string(), |name| {
+                name.to_string_lossy().into_owned()
+            });
+        (name, path.to_path_buf())
+    };
+    let mut workspaces: Vec<(String, PathBuf)> = config
+        .workspace_dbs()
+        .into_iter()
+        .map(|(name, path)| (name, std::fs::canonicalize(&path).unwrap_or(path)))
+        .collect();
+    if !workspaces.iter().any(|(_, path)| path == db_path) {
+        workspaces.insert(0, named(db_path));
+    }
+    for recent in config::State::load().recent {
+        if recent.exists() && !workspaces.iter().any(|(_, path)| *path == recent) {
+            workspaces.push(named(&recent));
+        }
+    }
+    workspaces
+}
+
+/// br for the project holding `db_path`
+fn br_for(db_path: &Path) -> BrCli {
+    BrCli::new(config::workspace_root(db_path).unwrap_or(Path::new(".")))
+}
+
+/// Note `db_path` as the most recently opened database. Best-effort, like
+/// remembering the theme.
+fn remember_database(db_path: &Path) {
+    let mut state = config::State::load();
+    state.remember_database(db_path);
+    let _ = state.save();
+}
+
 /// Setup the terminal
 fn setup_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>> {
     // Check if we have a TTY
@@ -848,29 +3001,131 @@ fn setup_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>> {
     Ok(terminal)
 }
 
-/// Restore the terminal
-fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result<()> {
+/// Undo [`setup_terminal`]: leave raw mode and the alternate screen, stop
+/// capturing the mouse and pastes, and show the cursor again
+fn leave_terminal() -> io::Result<()> {
     disable_raw_mode()?;
     execute!(
-        terminal.backend_mut(),
+        io::stdout(),
         LeaveAlternateScreen,
         DisableMouseCapture,
-        DisableBracketedPaste
-    )?;
+        DisableBracketedPaste,
+        Show
+    )
+}
+
+/// Restore the terminal
+fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result<()> {
+    leave_terminal()?;
     terminal.show_cursor()?;
     Ok(())
 }
 
+/// Restore the terminal before a panic's message is printed, so a crash
+/// doesn't leave it in raw mode on the alternate screen
+fn restore_terminal_on_panic() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = leave_terminal();
+        default_hook(info);
+    }));
+}
+
+/// Set by SIGTERM or SIGHUP; the main loop sees it and quits as `q` would
+static TERMINATED: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn note_termination(_signal: nix::libc::c_int) {
+    TERMINATED.store(true, Ordering::Relaxed);
+}
+
+/// Quit cleanly on SIGTERM (`kill`) and SIGHUP (the terminal closing), so the
+/// terminal is restored and changes still being saved get to finish
+fn quit_on_termination() -> Result<()> {
+    let action = SigAction::new(
+        SigHandler::Handler(note_termination),
+        SaFlags::SA_RESTART,
+        SigSet::empty(),
+    );
+    for sig in [Signal::SIGTERM, Signal::SIGHUP] {
+        // SAFETY: the handler only stores to an atomic, which is signal-safe
+        unsafe { signal::sigaction(sig, &action) }
+            .with_context(|| format!("Failed to handle {}", sig))?;
+    }
+    Ok(())
+}
+
 /// Run the application
-pub async fn run(db_path: PathBuf, refresh_secs: u64) -> Result<()> {
+pub async fn run(
+    db_path: PathBuf,
+    refresh_secs: u64,
+    themes: Vec<Theme>,
+    theme_idx: usize,
+    read_only: bool,
+    config: config::Config,
+    keymap: Keymap,
+) -> Result<()> {
+    restore_terminal_on_panic();
+    quit_on_termination()?;
     let mut terminal = setup_terminal()?;
-    let mut app = App::new(db_path, refresh_secs)?;
+    let app = App::new(
+        db_path,
+        refresh_secs,
+        themes,
+        theme_idx,
+        read_only,
+        config,
+        keymap,
+    );
+    let mut app = match app {
+        Ok(app) => app,
+        Err(e) => {
+            restore_terminal(&mut terminal)?;
+            return Err(e);
+        }
+    };
 
     let result = run_loop(&mut terminal, &mut app).await;
 
-    restore_terminal(&mut terminal)?;
+    // After a hangup there's no terminal left to restore, but pending
+    // changes should still be saved
+    let restored = restore_terminal(&mut terminal);
+
+    // Let changes still being saved finish rather than cutting them off. The
+    // note is only printed on a terminal that's back to normal; in raw mode
+    // or on the alternate screen it would be garbled or lost.
+    if app.br.in_flight() > 0 {
+        if restored.is_ok() {
+            eprintln!("Waiting for br to finish saving changes...");
+        }
+        app.br.drain().await;
+    }
 
-    result
+    result.and(restored)
+}
+
+/// Run a shell command with the terminal handed over, then take it back
+fn run_in_terminal(terminal: &mut Terminal<CrosstermBackend<Stdout>>, command: &str) -> Result<()> {
+    restore_terminal(terminal)?;
+    let status = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .status();
+
+    enable_raw_mode().context("Failed to enable raw mode after command")?;
+    execute!(
+        terminal.backend_mut(),
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableBracketedPaste
+    )
+    .context("Failed to enter alternate screen after command")?;
+    terminal.clear()?;
+
+    let status = status.with_context(|| format!("Failed to run {:?}", command))?;
+    if !status.success() {
+        anyhow::bail!("{:?} exited with {}", command, status);
+    }
+    Ok(())
 }
 
 /// Suspend the process (Ctrl+Z behavior)
@@ -904,18 +3159,55 @@ async fn run_loop(terminal: &mut Terminal<CrosstermBackend<Stdout>>, app: &mut A
         let theme = app.theme().clone();
         let focus = app.focus;
         let split_percent = app.split_percent;
-        let filter = app.filter().map(|s| s.to_string());
+        let tree_options = app.tree_options();
         let show_help = app.show_help;
-        let hide_closed = app.hide_closed;
         let show_labels = app.show_labels;
+        let show_assignee = app.show_assignee;
+        let show_recency = app.show_recency;
+        let wrap_titles = app.wrap_titles;
+        let density = app.density;
         let show_detail = app.show_detail;
+        let show_graph = app.show_graph;
+        let relative_times = app.relative_times;
+        let read_only = app.read_only;
+        let banner = app.br_missing.then_some(
+            "br not found on PATH: browsing works, but edits are disabled. Install beads_rust to enable them.",
+        );
         let input_mode = app.input_mode;
-        let search_text = app.search_input.lines().join("\n").to_string();
-        let search_cursor = app.search_input.cursor().1; // Column position only
         let reason_text = app.reason_input.lines().join("\n").to_string();
-        let reason_cursor = app.reason_input.cursor().1; // Column position only
-        let comment_text = app.comment_input.lines().join("\n").to_string();
-        let comment_cursor = app.comment_input.cursor().1; // Column position only
+        let reason_cursor = app.reason_input.cursor();
+        let assignee_text = app.assignee_input.lines().join("");
+        let assignee_cursor = app.assignee_input.cursor().1; // Column position only
+        let jump_text = app.jump_input.lines().join("");
+        let jump_cursor = app.jump_input.cursor().1; // Column position only
+        let dep_text = app.dep_input.lines().join("");
+        let dep_cursor = app.dep_input.cursor().1; // Column position only
+        let dep_type = app.dep_type;
+        let export_text = app.export_input.lines().join("");
+        let export_cursor = app.export_input.cursor().1; // Column position only
+        let label_text = app.label_input.lines().join("");
+        let label_cursor = app.label_input.cursor().1; // Column position only
+        let view_text = app.view_input.lines().join("");
+        let view_cursor = app.view_input.cursor().1; // Column position only
+        // Workspace picker rows: the name, then the project directory
+        let workspaces: Vec<(String, String)> = app
+            .workspaces
+            .iter()
+            .map(|(name, path)| {
+                let dir = config::workspace_root(path).unwrap_or(path);
+                let open = if *path == app.db_path { "  (open)" } else { "" };
+                (name.clone(), format!("{}{}", dir.display(), open))
+            })
+            .collect();
+        let marked = app.marked_rows();
+        let refresh_state = app.refresh_state();
+        let busy = app.busy_label();
+        let stats = app
+            .show_stats
+            .then(|| (app.stats(), app.stats_visible_only));
+        let status_message = app
+            .status_message()
+            .map(|(msg, kind)| (msg.to_string(), kind));
 
         // Draw
         terminal.draw(|frame| {
@@ -927,19 +3219,55 @@ async fn run_loop(terminal: &mut Terminal<CrosstermBackend<Stdout>>, app: &mut A
                 &theme,
                 focus,
                 split_percent,
-                filter.as_deref(),
+                &tree_options,
                 show_help,
-                hide_closed,
+                stats.as_ref().map(|(stats, visible)| (stats, *visible)),
+                app.show_board.then_some(&mut app.board_state),
                 show_labels,
+                show_assignee,
+                show_recency,
+                wrap_titles,
+                density,
                 show_detail,
+                show_graph,
+                relative_times,
+                read_only,
                 input_mode,
-                &search_text,
-                search_cursor,
+                &app.search_input,
                 &app.create_modal,
+                &app.label_picker,
                 &reason_text,
                 reason_cursor,
-                &comment_text,
-                comment_cursor,
+                &app.comment_input,
+                &assignee_text,
+                assignee_cursor,
+                &jump_text,
+                jump_cursor,
+                &dep_text,
+                dep_cursor,
+                dep_type,
+                &label_text,
+                label_cursor,
+                &export_text,
+                export_cursor,
+                app.export_bead.as_deref(),
+                app.views.list(),
+                app.view_pick,
+                &view_text,
+                view_cursor,
+                &workspaces,
+                app.workspace_pick,
+                marked,
+                &app.confirm_close_children,
+                &app.cycles,
+                app.config.stale_after(),
+                app.config.scrolloff,
+                status_message
+                    .as_ref()
+                    .map(|(msg, kind)| (msg.as_str(), *kind)),
+                refresh_state,
+                busy.as_deref(),
+                banner,
             );
             // Store areas for mouse handling
             app.list_area = list_area;
@@ -954,25 +3282,47 @@ async fn run_loop(terminal: &mut Terminal<CrosstermBackend<Stdout>>, app: &mut A
                     Err(e) if e.to_string() == "__SUSPEND__" => {
                         suspend(terminal)?;
                     }
-                    Err(e) => return Err(e),
+                    // Failed actions (usually a br command) are reported, not fatal
+                    Err(e) => app.set_error(&e),
                 },
                 Event::Mouse(mouse) => {
-                    app.handle_mouse(mouse)?;
+                    if let Err(e) = app.handle_mouse(mouse) {
+                        app.set_error(&e);
+                    }
                 }
                 Event::Paste(text) => {
-                    app.handle_paste(&text)?;
+                    if let Err(e) = app.handle_paste(&text) {
+                        app.set_error(&e);
+                    }
                 }
+                // Repaint from scratch at the new size; cells left over from the
+                // old layout would otherwise linger until something overwrote them
+                Event::Resize(_, _) => terminal.clear()?,
                 _ => {}
             }
         }
 
+        // Apply whatever br commands have finished in the background
+        app.finish_jobs();
+
+        // An open_command that needs the terminal runs between frames
+        if let Some(command) = app.pending_command.take() {
+            match run_in_terminal(terminal, &command) {
+                Ok(()) => app.set_status(format!("Ran {}", command)),
+                Err(e) => app.set_error(&e),
+            }
+            // The command may well have changed beads
+            let _ = app.refresh();
+        }
+
         // Auto-refresh
-        if app.refresh_interval.as_secs() > 0 && app.last_refresh.elapsed() >= app.refresh_interval
+        if app.auto_refresh_due()
+            && let Err(e) = app.refresh_if_changed()
         {
-            let _ = app.refresh();
+            app.set_error(&e);
         }
 
-        if app.should_quit {
+        if app.should_quit || TERMINATED.load(Ordering::Relaxed) {
             break;
         }
     }
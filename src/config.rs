@@ -0,0 +1,470 @@
+//! User config and persisted UI state
+//!
+//! Files live in `$XDG_CONFIG_HOME/bu` (or `~/.config/bu`). Missing or
+//! unreadable files fall back to defaults so they never block startup.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::data::BeadType;
+use crate::glyphs::GlyphConfig;
+use crate::ui::list::Density;
+
+/// Directory holding bu's config and state files
+pub fn config_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("XDG_CONFIG_HOME").filter(|d| !d.is_empty()) {
+        return Some(PathBuf::from(dir).join("bu"));
+    }
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config").join("bu"))
+}
+
+/// User settings from `config.toml`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Shell command run by `!`, with `{id}` replaced by the selected bead's ID
+    pub open_command: Option<String>,
+    /// Hand the terminal to `open_command` until it exits (for editors and
+    /// other interactive programs) instead of running it in the background
+    pub open_in_terminal: bool,
+    /// Start with timestamps like "3 days ago" instead of dates (`T` toggles)
+    pub relative_times: bool,
+    /// Mark open beads not updated for this many days (0 turns it off)
+    pub stale_days: u32,
+    /// Starting list density: "compact", "normal" or "comfortable" (`z` cycles)
+    pub density: Density,
+    /// Beads kept in view above and below the selection when the list
+    /// scrolls, like vim's `scrolloff`; a large number keeps it centered
+    pub scrolloff: usize,
+    /// Moving down from the last bead goes back to the first (and up from the
+    /// first to the last) instead of stopping there
+    pub wrap_around: bool,
+    /// `q` asks before quitting (`Q` and Ctrl+C still quit right away)
+    pub confirm_quit: bool,
+    /// Draw without colors, as when `NO_COLOR` is set (`--no-color`)
+    pub no_color: bool,
+    /// Status, type and marker glyphs (`[glyphs]`)
+    pub glyphs: GlyphConfig,
+    /// Workspaces to switch between with Ctrl+W (`[workspaces]`), by name:
+    /// a project directory, its `.beads` directory or the database file
+    pub workspaces: BTreeMap<String, PathBuf>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            open_command: None,
+            open_in_terminal: false,
+            relative_times: false,
+            stale_days: 30,
+            density: Density::Normal,
+            scrolloff: 0,
+            wrap_around: false,
+            confirm_quit: false,
+            no_color: false,
+            glyphs: GlyphConfig::default(),
+            workspaces: BTreeMap::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Default location of the config file
+    pub fn path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join("config.toml"))
+    }
+
+    /// Load the config from the default location (defaults if there is none)
+    pub fn load() -> Result<Self> {
+        match Self::path() {
+            Some(path) if path.exists() => Self::load_from(&path),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    /// Load the config from a file. Unlike the state file, a broken config
+    /// is an error so typos don't go unnoticed.
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {:?}", path))?;
+        toml::from_str(&text).with_context(|| format!("Invalid config file {:?}", path))
+    }
+
+    /// How long an open bead can go without updates before it's marked stale
+    pub fn stale_after(&self) -> Option<chrono::Duration> {
+        (self.stale_days > 0).then(|| chrono::Duration::days(self.stale_days.into()))
+    }
+
+    /// Configured workspaces by name, each resolved to its database path
+    pub fn workspace_dbs(&self) -> Vec<(String, PathBuf)> {
+        self.workspaces
+            .iter()
+            .map(|(name, path)| (name.clone(), workspace_db(path)))
+            .collect()
+    }
+
+    /// The open command for a bead, if one is configured. The ID comes from
+    /// the database, so it's shell-quoted unless it's plain letters, digits,
+    /// `.`, `_` and `-`.
+    pub fn open_command_for(&self, id: &str) -> Option<String> {
+        self.open_command
+            .as_ref()
+            .map(|command| command.replace("{id}", &shell_quote(id)))
+    }
+}
+
+/// `word` as a single `sh` word: unchanged if it has no special characters,
+/// otherwise in single quotes (an embedded `'` becomes `'\''`)
+fn shell_quote(word: &str) -> String {
+    let plain = !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'));
+    if plain {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', "'\\''"))
+    }
+}
+
+/// Database for a `[workspaces]` entry: anything but a `.db` file is a
+/// directory and gets `.beads/beads.db` appended (just `beads.db` for a
+/// `.beads` directory), and a leading `~` is the home directory
+fn workspace_db(path: &Path) -> PathBuf {
+    let path = match (path.strip_prefix("~"), std::env::var_os("HOME")) {
+        (Ok(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => path.to_path_buf(),
+    };
+    if path.is_file() || path.extension().is_some_and(|ext| ext == "db") {
+        path
+    } else if path.ends_with(".beads") {
+        path.join("beads.db")
+    } else {
+        path.join(".beads").join("beads.db")
+    }
+}
+
+/// Project directory of a database: the one holding its `.beads` directory,
+/// where `br` has to run to change that database
+pub fn workspace_root(db_path: &Path) -> Option<&Path> {
+    let dir = db_path.parent()?;
+    let root = if dir.ends_with(".beads") {
+        dir.parent()?
+    } else {
+        dir
+    };
+    // A relative path with no directory in front is in the working directory
+    Some(if root.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        root
+    })
+}
+
+/// The nearest `.beads` database at or above `start`, the way git finds
+/// `.git`, so bu works from anywhere inside a project
+pub fn find_database(start: &Path) -> Option<PathBuf> {
+    start
+        .ancestors()
+        .map(|dir| dir.join(".beads"))
+        .find(|beads| beads.is_dir())
+        .map(|beads| beads.join("beads.db"))
+}
+
+/// Most databases remembered in [`State::recent`]
+const MAX_RECENT: usize = 10;
+
+/// UI state remembered between sessions
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct State {
+    /// Name of the last selected theme
+    pub theme: Option<String>,
+    /// Databases opened most recently, newest first (offered by Ctrl+W)
+    pub recent: Vec<PathBuf>,
+}
+
+impl State {
+    /// Default location of the state file
+    pub fn path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join("state.toml"))
+    }
+
+    /// Load state from the default location
+    pub fn load() -> Self {
+        Self::path()
+            .map(|path| Self::load_from(&path))
+            .unwrap_or_default()
+    }
+
+    /// Load state from a file, falling back to defaults if it is missing or corrupt
+    pub fn load_from(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|text| toml::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    /// Put `db_path` at the front of the recent databases
+    pub fn remember_database(&mut self, db_path: &Path) {
+        self.recent.retain(|path| path != db_path);
+        self.recent.insert(0, db_path.to_path_buf());
+        self.recent.truncate(MAX_RECENT);
+    }
+
+    /// Save state to the default location
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path().context("No config directory (HOME is not set)")?;
+        self.save_to(&path)
+    }
+
+    /// Save state to a file, creating its directory if needed
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create config directory {:?}", dir))?;
+        }
+        let text = toml::to_string(self).context("Failed to serialize state")?;
+        fs::write(path, text).with_context(|| format!("Failed to write state file {:?}", path))
+    }
+}
+
+/// A new bead being typed into the create modal, kept until it's created or discarded
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Draft {
+    pub title: String,
+    pub description: String,
+    /// Comma-separated, as typed
+    pub labels: String,
+    pub bead_type: BeadType,
+    pub priority: u8,
+    /// ID of the chosen parent bead
+    pub parent: Option<String>,
+}
+
+impl Draft {
+    /// Default location of the draft file
+    pub fn path() -> Option<PathBuf> {
+        config_dir().map(|dir| dir.join("draft.toml"))
+    }
+
+    /// Load the saved draft, if there is one worth restoring
+    pub fn load() -> Option<Self> {
+        Self::path().and_then(|path| Self::load_from(&path))
+    }
+
+    /// Load a draft from a file; missing, corrupt or empty drafts count as none
+    pub fn load_from(path: &Path) -> Option<Self> {
+        let text = fs::read_to_string(path).ok()?;
+        toml::from_str::<Self>(&text)
+            .ok()
+            .filter(|draft| !draft.is_empty())
+    }
+
+    /// Whether nothing has been typed
+    pub fn is_empty(&self) -> bool {
+        [&self.title, &self.description, &self.labels]
+            .iter()
+            .all(|text| text.trim().is_empty())
+    }
+
+    /// Save the draft to the default location
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path().context("No config directory (HOME is not set)")?;
+        self.save_to(&path)
+    }
+
+    /// Save the draft to a file, creating its directory if needed
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create config directory {:?}", dir))?;
+        }
+        let text = toml::to_string(self).context("Failed to serialize draft")?;
+        fs::write(path, text).with_context(|| format!("Failed to write draft file {:?}", path))
+    }
+
+    /// Remove the draft from the default location
+    pub fn clear() -> Result<()> {
+        match Self::path() {
+            Some(path) => Self::clear_at(&path),
+            None => Ok(()),
+        }
+    }
+
+    /// Remove a draft file; a missing file is already clear
+    pub fn clear_at(path: &Path) -> Result<()> {
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("Failed to remove draft file {:?}", path))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn state_round_trips_through_file() {
+        let dir = TempDir::new().expect("temp dir");
+        let path = dir.path().join("nested").join("state.toml");
+        let state = State {
+            theme: Some("Tokyo Night".to_string()),
+            recent: vec![PathBuf::from("/src/api/.beads/beads.db")],
+        };
+
+        state.save_to(&path).expect("save state");
+        assert_eq!(State::load_from(&path), state);
+    }
+
+    #[test]
+    fn recent_databases_are_newest_first_without_repeats() {
+        let mut state = State::default();
+        for n in 0..12 {
+            state.remember_database(Path::new(&format!("/db{}", n)));
+        }
+        state.remember_database(Path::new("/db5"));
+
+        assert_eq!(state.recent.len(), MAX_RECENT);
+        assert_eq!(state.recent[0], PathBuf::from("/db5"));
+        assert_eq!(state.recent[1], PathBuf::from("/db11"));
+        assert_eq!(
+            state.recent.iter().filter(|p| p.ends_with("db5")).count(),
+            1
+        );
+    }
+
+    #[test]
+    fn database_is_found_in_the_nearest_parent_with_beads() {
+        let dir = TempDir::new().expect("temp dir");
+        let nested = dir.path().join("src").join("ui");
+        fs::create_dir_all(&nested).expect("create dirs");
+        assert_eq!(find_database(&nested), None);
+
+        fs::create_dir_all(dir.path().join(".beads")).expect("create .beads");
+        assert_eq!(
+            find_database(&nested),
+            Some(dir.path().join(".beads").join("beads.db"))
+        );
+    }
+
+    #[test]
+    fn config_substitutes_bead_id_into_open_command() {
+        let dir = TempDir::new().expect("temp dir");
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            "open_command = \"xdg-open https://tracker/{id}?ref={id}\"\n",
+        )
+        .expect("write config");
+
+        let config = Config::load_from(&path).expect("load config");
+        assert!(!config.open_in_terminal);
+        assert_eq!(
+            config.open_command_for("bd-42").as_deref(),
+            Some("xdg-open https://tracker/bd-42?ref=bd-42")
+        );
+        assert_eq!(Config::default().open_command_for("bd-42"), None);
+
+        // IDs from someone else's database can't run their own commands
+        assert_eq!(
+            config.open_command_for("bd-1;rm -rf ~").as_deref(),
+            Some("xdg-open https://tracker/'bd-1;rm -rf ~'?ref='bd-1;rm -rf ~'")
+        );
+        assert_eq!(
+            config.open_command_for("it's$(id)").as_deref(),
+            Some("xdg-open https://tracker/'it'\\''s$(id)'?ref='it'\\''s$(id)'")
+        );
+    }
+
+    #[test]
+    fn config_rejects_unknown_settings() {
+        let dir = TempDir::new().expect("temp dir");
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "open_comand = \"typo\"\n").expect("write config");
+
+        assert!(Config::load_from(&path).is_err());
+    }
+
+    #[test]
+    fn workspaces_resolve_to_their_databases() {
+        let dir = TempDir::new().expect("temp dir");
+        let project = dir.path().join("project");
+        fs::create_dir_all(project.join(".beads")).expect("create .beads");
+        let path = dir.path().join("config.toml");
+        fs::write(
+            &path,
+            format!(
+                "[workspaces]\nproject = {:?}\nbeads = {:?}\nfile = \"/srv/other.db\"\n",
+                project,
+                project.join(".beads")
+            ),
+        )
+        .expect("write config");
+
+        let config = Config::load_from(&path).expect("load config");
+        let db = project.join(".beads").join("beads.db");
+        assert_eq!(
+            config.workspace_dbs(),
+            vec![
+                ("beads".to_string(), db.clone()),
+                ("file".to_string(), PathBuf::from("/srv/other.db")),
+                ("project".to_string(), db.clone()),
+            ]
+        );
+        assert_eq!(workspace_root(&db), Some(project.as_path()));
+        assert_eq!(
+            workspace_root(Path::new("/srv/other.db")),
+            Some(Path::new("/srv"))
+        );
+        // A relative --db is relative to where bu runs
+        assert_eq!(
+            workspace_root(Path::new(".beads/beads.db")),
+            Some(Path::new("."))
+        );
+    }
+
+    #[test]
+    fn draft_round_trips_until_cleared() {
+        let dir = TempDir::new().expect("temp dir");
+        let path = dir.path().join("nested").join("draft.toml");
+        assert_eq!(Draft::load_from(&path), None);
+
+        let draft = Draft {
+            title: "Half-written bead".to_string(),
+            description: "line one\nline two".to_string(),
+            labels: "ui, bug".to_string(),
+            bead_type: BeadType::Bug,
+            priority: 1,
+            parent: Some("bd-a1".to_string()),
+        };
+        draft.save_to(&path).expect("save draft");
+        assert_eq!(Draft::load_from(&path), Some(draft));
+
+        Draft::clear_at(&path).expect("clear draft");
+        assert_eq!(Draft::load_from(&path), None);
+        Draft::clear_at(&path).expect("clearing twice is fine");
+
+        // Only a type or priority change isn't worth offering back
+        Draft::default().save_to(&path).expect("save empty draft");
+        assert_eq!(Draft::load_from(&path), None);
+    }
+
+    #[test]
+    fn missing_or_corrupt_state_falls_back_to_default() {
+        let dir = TempDir::new().expect("temp dir");
+        let path = dir.path().join("state.toml");
+        assert_eq!(State::load_from(&path), State::default());
+
+        fs::write(&path, "theme = [not valid").expect("write state");
+        assert_eq!(State::load_from(&path), State::default());
+    }
+}
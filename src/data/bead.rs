@@ -4,9 +4,56 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::collections::{HashMap, HashSet};
 use std::fmt;
 
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use regex::{Regex, RegexBuilder};
+
+use crate::glyphs;
+
+/// Priority level of a bead, most urgent first. Beads store the level as a
+/// number (`P0`-`P4`); anything out of range from external `br` use is
+/// clamped into it on load.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    Critical,
+    High,
+    Medium,
+    Low,
+    Backlog,
+}
+
+impl Priority {
+    /// Least urgent level number
+    pub const MAX_LEVEL: u8 = Priority::Backlog as u8;
+
+    /// Priority for a level as stored in the database, clamped to `P0`-`P4`
+    pub fn clamped(level: i64) -> Self {
+        Self::from(level.clamp(0, Self::MAX_LEVEL.into()) as u8)
+    }
+
+    /// Level number, 0 (critical) to 4 (backlog)
+    pub fn level(self) -> u8 {
+        self as u8
+    }
+}
+
+impl From<u8> for Priority {
+    /// Levels past backlog count as backlog
+    fn from(level: u8) -> Self {
+        match level {
+            0 => Priority::Critical,
+            1 => Priority::High,
+            2 => Priority::Medium,
+            3 => Priority::Low,
+            _ => Priority::Backlog,
+        }
+    }
+}
+
 /// Status of a bead
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
@@ -20,15 +67,9 @@ pub enum BeadStatus {
 }
 
 impl BeadStatus {
-    /// Get the display icon for this status (simple unicode, no emojis)
+    /// Get the display icon for this status (see [`glyphs`])
     pub fn icon(&self) -> &'static str {
-        match self {
-            BeadStatus::Open => "\u{25cb}",       // ○ (open circle)
-            BeadStatus::InProgress => "\u{25cf}", // ● (filled circle)
-            BeadStatus::Blocked => "\u{25a0}",    // ■ (filled square - blocked)
-            BeadStatus::Deferred => "\u{2744}",   // ❄ (snowflake - deferred)
-            BeadStatus::Closed => "\u{2713}",     // ✓ (check mark)
-        }
+        glyphs::get().status(*self)
     }
 
     /// Get all possible statuses
@@ -83,51 +124,26 @@ pub enum BeadType {
 }
 
 impl BeadType {
-    /// Get the outline (open/blocked-open) icon for this type
+    /// Get the outline (open) icon for this type
     pub fn icon_outline(&self) -> &'static str {
-        match self {
-            BeadType::Task => "\u{25b7}",    // ▷ right triangle outline
-            BeadType::Bug => "\u{2298}",     // ⊘ circled slash
-            BeadType::Feature => "\u{2606}", // ☆ star outline
-            BeadType::Epic => "\u{25c7}",    // ◇ diamond outline
-            BeadType::Story => "\u{2630}",   // ☰ trigram
-        }
+        &glyphs::get().bead_type(*self)[0]
     }
 
-    /// Get the filled (in-progress/blocked-in-progress) icon for this type
+    /// Get the filled (in-progress) icon for this type
     pub fn icon_filled(&self) -> &'static str {
-        match self {
-            BeadType::Task => "\u{25b6}",    // ▶ right triangle filled
-            BeadType::Bug => "\u{25cf}",     // ● filled circle
-            BeadType::Feature => "\u{2605}", // ★ star filled
-            BeadType::Epic => "\u{25c6}",    // ◆ diamond filled
-            BeadType::Story => "\u{25e4}",   // ▤ square with lines
-        }
-    }
-
-    /// Get the closed (done) icon for this type
-    pub fn icon_closed(&self) -> &'static str {
-        match self {
-            BeadType::Task => "\u{25b6}",    // ▶ right triangle filled
-            BeadType::Bug => "\u{25cf}",     // ● filled circle
-            BeadType::Feature => "\u{2605}", // ★ star filled
-            BeadType::Epic => "\u{25c6}",    // ◆ diamond filled
-            BeadType::Story => "\u{25a0}",   // ■ filled square
-        }
+        &glyphs::get().bead_type(*self)[1]
     }
 
-    /// Get the appropriate icon based on status
-    /// - Open: outline
-    /// - InProgress: filled
-    /// - Blocked: outline (color will be red)
-    /// - Closed: closed variant (filled, grayed)
+    /// Get the appropriate icon based on status, so that every status has
+    /// its own shape and none relies on color alone:
+    /// - Open: the type's outline
+    /// - InProgress: the type's filled icon
+    /// - Blocked, Deferred, Closed: the status icon (■ ❄ ✓)
     pub fn icon_for_status(&self, status: &BeadStatus) -> &'static str {
         match status {
             BeadStatus::Open => self.icon_outline(),
             BeadStatus::InProgress => self.icon_filled(),
-            BeadStatus::Blocked => self.icon_outline(), // Outline but red
-            BeadStatus::Deferred => self.icon_outline(), // Outline but dim
-            BeadStatus::Closed => self.icon_closed(),
+            BeadStatus::Blocked | BeadStatus::Deferred | BeadStatus::Closed => status.icon(),
         }
     }
 }
@@ -168,6 +184,36 @@ pub enum DependencyType {
     Related,
 }
 
+impl DependencyType {
+    /// Cycle to the next dependency type
+    pub fn next(self) -> Self {
+        match self {
+            DependencyType::Blocks => DependencyType::ParentChild,
+            DependencyType::ParentChild => DependencyType::Related,
+            DependencyType::Related => DependencyType::Blocks,
+        }
+    }
+
+    /// How the dependency reads from the dependent bead's side
+    pub fn describe(&self) -> &'static str {
+        match self {
+            DependencyType::Blocks => "blocked by",
+            DependencyType::ParentChild => "child of",
+            DependencyType::Related => "related to",
+        }
+    }
+}
+
+/// How recently a bead was last updated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recency {
+    /// Earlier today, local time
+    Today,
+    /// Within the last seven days
+    ThisWeek,
+    Older,
+}
+
 impl fmt::Display for DependencyType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -222,7 +268,7 @@ pub struct Bead {
     pub title: String,
     /// Current status
     pub status: BeadStatus,
-    /// Priority (0 = critical, 4 = backlog)
+    /// Priority level (0 = critical, 4 = backlog; see [`Priority`])
     pub priority: u8,
     /// Type of bead
     pub bead_type: BeadType,
@@ -253,20 +299,78 @@ pub struct Bead {
 }
 
 impl Bead {
-    /// Check if this bead is blocked by any open beads
-    pub fn is_blocked(&self) -> bool {
-        self.status == BeadStatus::Blocked || !self.blocked_by.is_empty()
+    /// Priority level, clamped into the known range
+    pub fn priority(&self) -> Priority {
+        Priority::from(self.priority)
     }
 
     /// Get a priority label
     pub fn priority_label(&self) -> String {
-        format!("P{}", self.priority)
+        format!("P{}", self.priority().level())
     }
 
     /// Check if this bead has deferred status
     pub fn is_deferred(&self) -> bool {
         self.status == BeadStatus::Deferred
     }
+
+    /// Whether this bead can be picked up now: open or in progress, and not
+    /// waiting on any bead in `not_closed` (blockers that aren't loaded
+    /// don't count)
+    pub fn is_ready(&self, not_closed: &HashSet<&str>) -> bool {
+        matches!(self.status, BeadStatus::Open | BeadStatus::InProgress)
+            && !self
+                .blocked_by
+                .iter()
+                .any(|id| not_closed.contains(id.as_str()))
+    }
+
+    /// Whether this bead carries any (or, with [`LabelMatch::All`], every) label in `labels`
+    pub fn has_labels(&self, labels: &[String], mode: LabelMatch) -> bool {
+        let carries = |label: &String| self.labels.contains(label);
+        match mode {
+            LabelMatch::Any => labels.iter().any(carries),
+            LabelMatch::All => labels.iter().all(carries),
+        }
+    }
+
+    /// The status to show: an open bead still waiting on a bead in
+    /// `not_closed` is effectively blocked, whatever its stored status says
+    pub fn effective_status(&self, not_closed: &HashSet<&str>) -> BeadStatus {
+        let waiting = self
+            .blocked_by
+            .iter()
+            .any(|id| not_closed.contains(id.as_str()));
+        if self.status == BeadStatus::Open && waiting {
+            BeadStatus::Blocked
+        } else {
+            self.status
+        }
+    }
+
+    /// How recently this bead was updated (or created, if it never was)
+    pub fn recency(&self, now: DateTime<Utc>) -> Recency {
+        let Some(updated) = self.updated_at.or(self.created_at) else {
+            return Recency::Older;
+        };
+        let day = |at: DateTime<Utc>| at.with_timezone(&chrono::Local).date_naive();
+        if day(updated) >= day(now) {
+            Recency::Today
+        } else if now.signed_duration_since(updated) < chrono::Duration::days(7) {
+            Recency::ThisWeek
+        } else {
+            Recency::Older
+        }
+    }
+
+    /// Whether this bead is still open but hasn't been updated for `max_age`
+    pub fn is_stale(&self, max_age: chrono::Duration, now: DateTime<Utc>) -> bool {
+        self.status != BeadStatus::Closed
+            && self
+                .updated_at
+                .or(self.created_at)
+                .is_some_and(|updated| now.signed_duration_since(updated) > max_age)
+    }
 }
 
 impl Default for Bead {
@@ -293,14 +397,579 @@ impl Default for Bead {
     }
 }
 
+/// Sort order for the bead list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortMode {
+    /// Priority, then title (closed beads: most recently closed first)
+    #[default]
+    Priority,
+    /// Newest created first
+    Created,
+    /// Most recently updated first
+    Updated,
+    /// Alphabetical by title
+    Title,
+}
+
+impl SortMode {
+    /// Next sort mode in the cycle
+    pub fn next(self) -> Self {
+        match self {
+            SortMode::Priority => SortMode::Created,
+            SortMode::Created => SortMode::Updated,
+            SortMode::Updated => SortMode::Title,
+            SortMode::Title => SortMode::Priority,
+        }
+    }
+
+    /// Compare two beads under this sort mode
+    pub fn compare(self, a: &Bead, b: &Bead) -> Ordering {
+        // Newest first; beads without a timestamp sort last
+        fn newest_first<T: Ord>(a: &Option<T>, b: &Option<T>) -> Ordering {
+            match (a, b) {
+                (Some(a), Some(b)) => b.cmp(a),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+        }
+
+        match self {
+            SortMode::Priority
+                if a.status == BeadStatus::Closed && b.status == BeadStatus::Closed =>
+            {
+                newest_first(&a.closed_at, &b.closed_at).then(a.title.cmp(&b.title))
+            }
+            SortMode::Priority => a.priority.cmp(&b.priority).then(a.title.cmp(&b.title)),
+            SortMode::Created => {
+                newest_first(&a.created_at, &b.created_at).then(a.title.cmp(&b.title))
+            }
+            SortMode::Updated => {
+                newest_first(&a.updated_at, &b.updated_at).then(a.title.cmp(&b.title))
+            }
+            SortMode::Title => a.title.cmp(&b.title).then(a.priority.cmp(&b.priority)),
+        }
+    }
+}
+
+impl fmt::Display for SortMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SortMode::Priority => write!(f, "priority"),
+            SortMode::Created => write!(f, "created"),
+            SortMode::Updated => write!(f, "updated"),
+            SortMode::Title => write!(f, "title"),
+        }
+    }
+}
+
+impl std::str::FromStr for SortMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "priority" => Ok(SortMode::Priority),
+            "created" => Ok(SortMode::Created),
+            "updated" => Ok(SortMode::Updated),
+            "title" => Ok(SortMode::Title),
+            _ => anyhow::bail!("Unknown sort order: {}", s),
+        }
+    }
+}
+
+/// How a label filter with several labels combines them
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LabelMatch {
+    /// Beads with at least one of the labels
+    #[default]
+    Any,
+    /// Beads with every one of the labels
+    All,
+}
+
+impl LabelMatch {
+    /// The other mode
+    pub fn toggle(self) -> Self {
+        match self {
+            LabelMatch::Any => LabelMatch::All,
+            LabelMatch::All => LabelMatch::Any,
+        }
+    }
+}
+
+impl fmt::Display for LabelMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LabelMatch::Any => write!(f, "any"),
+            LabelMatch::All => write!(f, "all"),
+        }
+    }
+}
+
+/// How the list is grouped, instead of arranged as a tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GroupBy {
+    /// The dependency tree
+    #[default]
+    None,
+    /// Flat, under In Progress / Open / Blocked / Deferred / Closed headers
+    Status,
+    /// Flat, under one header per bead type
+    Type,
+}
+
+impl GroupBy {
+    /// Next grouping in the cycle
+    pub fn next(self) -> Self {
+        match self {
+            GroupBy::None => GroupBy::Status,
+            GroupBy::Status => GroupBy::Type,
+            GroupBy::Type => GroupBy::None,
+        }
+    }
+
+    /// Rank and header of the group `bead` falls in (None when not grouping).
+    /// Status groups go by effective status, so open beads with open
+    /// blockers are listed as blocked.
+    pub fn group_of(
+        self,
+        bead: &Bead,
+        not_closed: &HashSet<&str>,
+    ) -> Option<(usize, &'static str)> {
+        match self {
+            GroupBy::None => None,
+            GroupBy::Status => Some(match bead.effective_status(not_closed) {
+                BeadStatus::InProgress => (0, "In Progress"),
+                BeadStatus::Open => (1, "Open"),
+                BeadStatus::Blocked => (2, "Blocked"),
+                BeadStatus::Deferred => (3, "Deferred"),
+                BeadStatus::Closed => (4, "Closed"),
+            }),
+            GroupBy::Type => Some(match bead.bead_type {
+                BeadType::Task => (0, "Tasks"),
+                BeadType::Bug => (1, "Bugs"),
+                BeadType::Feature => (2, "Features"),
+                BeadType::Epic => (3, "Epics"),
+                BeadType::Story => (4, "Stories"),
+            }),
+        }
+    }
+}
+
+impl fmt::Display for GroupBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GroupBy::None => write!(f, "none"),
+            GroupBy::Status => write!(f, "status"),
+            GroupBy::Type => write!(f, "type"),
+        }
+    }
+}
+
+/// Options controlling which beads appear in the tree-ordered list.
+///
+/// Filters apply per bead: a bead that matches is kept even when its parent
+/// (or blocker) is filtered out, in which case it is shown as a root.
+#[derive(Debug, Clone, Default)]
+pub struct TreeOptions {
+    /// Hide closed beads (ignored when `status` explicitly asks for closed)
+    pub hide_closed: bool,
+    /// Text filter (matches title or ID)
+    pub filter: Option<String>,
+    /// Only keep beads with this status
+    pub status: Option<BeadStatus>,
+    /// Only keep beads at this priority or more urgent (e.g. 1 keeps P0 and P1)
+    pub max_priority: Option<u8>,
+    /// Only keep beads that can be worked on now (see [`Bead::is_ready`])
+    pub ready_only: bool,
+    /// Only keep beads with these labels (empty keeps all)
+    pub labels: Vec<String>,
+    /// Whether beads need any or all of `labels`
+    pub label_match: LabelMatch,
+    /// Order for roots, children, and closed beads
+    pub sort: SortMode,
+    /// Flip the sort order
+    pub reverse: bool,
+    /// Show a flat list bucketed into groups instead of the tree
+    pub group_by: GroupBy,
+    /// Skip the tree: every bead at the top level, in sort order
+    pub flat: bool,
+    /// Beads whose children are hidden
+    pub collapsed: HashSet<String>,
+}
+
+impl TreeOptions {
+    /// Whether any filter beyond hiding closed beads is narrowing the list
+    pub fn is_filtered(&self) -> bool {
+        self.filter.as_ref().is_some_and(|f| !f.is_empty())
+            || self.status.is_some()
+            || self.max_priority.is_some()
+            || self.ready_only
+            || !self.labels.is_empty()
+    }
+
+    /// Compare two beads under the sort mode, honoring `reverse`
+    pub fn compare(&self, a: &Bead, b: &Bead) -> Ordering {
+        let ord = self.sort.compare(a, b);
+        if self.reverse { ord.reverse() } else { ord }
+    }
+}
+
+/// Check whether a bead matches a text filter (see [`filter_score`]).
+pub fn matches_filter(bead: &Bead, filter: &str) -> bool {
+    match filter_regex(filter) {
+        Some(regex) => regex.is_ok_and(|re| regex_matches(bead, &re)),
+        None => filter_score(bead, filter, &SkimMatcherV2::default()).is_some(),
+    }
+}
+
+/// Compile a `/pattern/` filter (the closing slash is optional) as a
+/// case-insensitive regex, or `None` if the filter isn't a regex query.
+pub fn filter_regex(filter: &str) -> Option<Result<Regex, regex::Error>> {
+    let pattern = filter.strip_prefix('/')?;
+    let pattern = pattern.strip_suffix('/').unwrap_or(pattern);
+    Some(RegexBuilder::new(pattern).case_insensitive(true).build())
+}
+
+/// Whether a regex filter matches the bead's title, ID or description
+fn regex_matches(bead: &Bead, re: &Regex) -> bool {
+    re.is_match(&bead.title)
+        || re.is_match(&bead.id)
+        || bead.description.as_deref().is_some_and(|d| re.is_match(d))
+}
+
+/// Score a bead against a text filter, or `None` if it doesn't match.
+///
+/// A leading `@` matches the assignee, `~` the creator, and a leading `'`
+/// forces a plain substring match. Otherwise the title and ID are fuzzy matched (so "btn"
+/// finds "button"), with a substring match on description or labels as a
+/// zero-score fallback. Matching is case-insensitive. `/pattern/` regex
+/// filters are handled by [`filter_regex`] instead.
+pub fn filter_score(bead: &Bead, filter: &str, matcher: &SkimMatcherV2) -> Option<i64> {
+    let f_lower = filter.to_lowercase();
+    if let Some(name) = f_lower.strip_prefix('@') {
+        return bead
+            .assignee
+            .as_deref()
+            .is_some_and(|a| a.to_lowercase().contains(name))
+            .then_some(0);
+    }
+    if let Some(name) = f_lower.strip_prefix('~') {
+        return bead
+            .created_by
+            .as_deref()
+            .is_some_and(|c| c.to_lowercase().contains(name))
+            .then_some(0);
+    }
+
+    let contains = |needle: &str| {
+        bead.title.to_lowercase().contains(needle)
+            || bead.id.to_lowercase().contains(needle)
+            || bead
+                .description
+                .as_deref()
+                .is_some_and(|d| d.to_lowercase().contains(needle))
+            || bead
+                .labels
+                .iter()
+                .any(|l| l.to_lowercase().contains(needle))
+    };
+
+    if let Some(exact) = f_lower.strip_prefix('\'') {
+        return contains(exact).then_some(0);
+    }
+
+    // The query is lowercased, so skim's smart case matches case-insensitively
+    let fuzzy = [bead.title.as_str(), bead.id.as_str()]
+        .iter()
+        .filter_map(|text| matcher.fuzzy_match(text, &f_lower))
+        .max();
+    fuzzy.or_else(|| contains(&f_lower).then_some(0))
+}
+
+/// Character indices of `text` matched by a text filter, for highlighting
+pub fn filter_match_indices(text: &str, filter: &str, matcher: &SkimMatcherV2) -> Vec<usize> {
+    if let Some(regex) = filter_regex(filter) {
+        let Some(found) = regex.ok().and_then(|re| re.find(text)) else {
+            return Vec::new();
+        };
+        // Byte offsets to char indices
+        return text
+            .char_indices()
+            .enumerate()
+            .filter(|(_, (byte, _))| found.range().contains(byte))
+            .map(|(i, _)| i)
+            .collect();
+    }
+
+    let f_lower = filter.to_lowercase();
+    if f_lower.is_empty() || f_lower.starts_with(['@', '~']) {
+        return Vec::new();
+    }
+
+    if let Some(exact) = f_lower.strip_prefix('\'') {
+        if exact.is_empty() {
+            return Vec::new();
+        }
+        // Compare char-by-char so indices line up with the original text
+        let haystack: Vec<char> = text.chars().collect();
+        let needle: Vec<char> = exact.chars().collect();
+        let lower = |c: &char| c.to_lowercase().next().unwrap_or(*c);
+        return haystack
+            .windows(needle.len())
+            .position(|w| w.iter().map(lower).eq(needle.iter().copied()))
+            .map(|start| (start..start + needle.len()).collect())
+            .unwrap_or_default();
+    }
+
+    matcher
+        .fuzzy_indices(text, &f_lower)
+        .map(|(_, indices)| indices)
+        .unwrap_or_default()
+}
+
+/// IDs of all non-closed descendants (children, grandchildren, ...) of a bead
+pub fn open_descendants(beads: &[Bead], id: &str) -> Vec<String> {
+    let mut found = Vec::new();
+    let mut stack = vec![id.to_string()];
+    while let Some(parent) = stack.pop() {
+        for child in beads.iter().filter(|b| b.parent_ids.contains(&parent)) {
+            if found.contains(&child.id) || child.id == id {
+                continue;
+            }
+            if child.status != BeadStatus::Closed {
+                found.push(child.id.clone());
+            }
+            stack.push(child.id.clone());
+        }
+    }
+    found
+}
+
+/// Groups of beads that depend on each other in a loop, following
+/// `blocked_by` and `parent_ids`. Each cycle lists its IDs sorted.
+///
+/// These are strongly connected components (Tarjan's algorithm); a bead
+/// that lists itself as a blocker or parent is a cycle of one.
+pub fn find_cycles(beads: &[Bead]) -> Vec<Vec<String>> {
+    struct Tarjan<'a> {
+        by_id: HashMap<&'a str, &'a Bead>,
+        index: HashMap<&'a str, usize>,
+        low: HashMap<&'a str, usize>,
+        stack: Vec<&'a str>,
+        on_stack: HashSet<&'a str>,
+        cycles: Vec<Vec<String>>,
+    }
+
+    impl<'a> Tarjan<'a> {
+        fn visit(&mut self, id: &'a str) {
+            let n = self.index.len();
+            self.index.insert(id, n);
+            self.low.insert(id, n);
+            self.stack.push(id);
+            self.on_stack.insert(id);
+
+            let bead = self.by_id[id];
+            for next in bead.blocked_by.iter().chain(&bead.parent_ids) {
+                let Some(next) = self.by_id.get(next.as_str()).map(|b| b.id.as_str()) else {
+                    continue;
+                };
+                if !self.index.contains_key(next) {
+                    self.visit(next);
+                    let low = self.low[id].min(self.low[next]);
+                    self.low.insert(id, low);
+                } else if self.on_stack.contains(next) {
+                    let low = self.low[id].min(self.index[next]);
+                    self.low.insert(id, low);
+                }
+            }
+
+            if self.low[id] == self.index[id] {
+                let mut component = Vec::new();
+                while let Some(member) = self.stack.pop() {
+                    self.on_stack.remove(member);
+                    component.push(member.to_string());
+                    if member == id {
+                        break;
+                    }
+                }
+                let self_loop = bead
+                    .blocked_by
+                    .iter()
+                    .chain(&bead.parent_ids)
+                    .any(|d| d == id);
+                if component.len() > 1 || self_loop {
+                    component.sort();
+                    self.cycles.push(component);
+                }
+            }
+        }
+    }
+
+    let mut tarjan = Tarjan {
+        by_id: beads.iter().map(|b| (b.id.as_str(), b)).collect(),
+        index: HashMap::new(),
+        low: HashMap::new(),
+        stack: Vec::new(),
+        on_stack: HashSet::new(),
+        cycles: Vec::new(),
+    };
+    for bead in beads {
+        if !tarjan.index.contains_key(bead.id.as_str()) {
+            tarjan.visit(&bead.id);
+        }
+    }
+    tarjan.cycles.sort();
+    tarjan.cycles
+}
+
+/// A row of the tree view
+#[derive(Debug, Clone, Copy)]
+pub struct TreeRow<'a> {
+    pub bead: &'a Bead,
+    pub depth: usize,
+    /// Number of children drawn directly under it, or that expanding it would
+    /// draw; a child of several rows belongs to the first that reaches it
+    pub child_count: usize,
+    /// No sibling follows it under the same parent, so its tree connector
+    /// closes the branch
+    pub last_child: bool,
+    /// How it hangs under the row it's nested in: `ParentChild` for a
+    /// sub-task, `Blocks` when that row blocks it. None at the top level.
+    pub edge: Option<DependencyType>,
+    /// Heading and number of beads of the group this row starts, in a
+    /// grouped list
+    pub group_start: Option<(&'static str, usize)>,
+}
+
+impl TreeRow<'_> {
+    pub fn has_children(&self) -> bool {
+        self.child_count > 0
+    }
+}
+
 /// Build a tree-ordered list of beads with their depths.
 /// Non-closed beads are arranged hierarchically, closed beads are flat at the end.
 /// Returns Vec of (bead reference, depth).
-pub fn build_tree_order<'a>(
-    beads: &'a [Bead],
-    hide_closed: bool,
-    filter: Option<&str>,
-) -> Vec<(&'a Bead, usize)> {
+pub fn build_tree_order<'a>(beads: &'a [Bead], opts: &TreeOptions) -> Vec<(&'a Bead, usize)> {
+    build_tree_rows(beads, opts)
+        .into_iter()
+        .map(|row| (row.bead, row.depth))
+        .collect()
+}
+
+/// Every label used on `beads` with how many beads carry it, most used first
+/// (ties alphabetical)
+pub fn label_counts(beads: &[Bead]) -> Vec<(String, usize)> {
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for label in beads.iter().flat_map(|b| &b.labels) {
+        *counts.entry(label).or_default() += 1;
+    }
+    let mut labels: Vec<(String, usize)> = counts
+        .into_iter()
+        .map(|(label, count)| (label.to_string(), count))
+        .collect();
+    labels.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    labels
+}
+
+/// Counts behind the stats overlay
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BeadStats {
+    pub total: usize,
+    /// Every status in workflow order, including ones with no beads
+    pub by_status: Vec<(BeadStatus, usize)>,
+    /// Beads at P0 to P4
+    pub by_priority: [usize; 5],
+    /// Types in use, most common first
+    pub by_type: Vec<(BeadType, usize)>,
+    /// Assignees, most beads first (None = unassigned)
+    pub by_assignee: Vec<(Option<String>, usize)>,
+    /// Closed beads with both timestamps, and their average cycle time
+    pub cycle_times: (usize, Option<chrono::Duration>),
+}
+
+/// Tally `beads` by status, priority, type and assignee
+pub fn bead_stats<'a>(beads: impl IntoIterator<Item = &'a Bead>) -> BeadStats {
+    let mut stats = BeadStats {
+        by_status: BeadStatus::all().iter().map(|s| (*s, 0)).collect(),
+        ..BeadStats::default()
+    };
+    let mut by_assignee: HashMap<Option<&str>, usize> = HashMap::new();
+    let mut cycle_total = chrono::Duration::zero();
+    for bead in beads {
+        stats.total += 1;
+        if let Some((_, count)) = stats.by_status.iter_mut().find(|(s, _)| *s == bead.status) {
+            *count += 1;
+        }
+        if let Some(count) = stats.by_priority.get_mut(bead.priority as usize) {
+            *count += 1;
+        }
+        match stats.by_type.iter_mut().find(|(t, _)| *t == bead.bead_type) {
+            Some((_, count)) => *count += 1,
+            None => stats.by_type.push((bead.bead_type, 1)),
+        }
+        *by_assignee.entry(bead.assignee.as_deref()).or_default() += 1;
+        if bead.status == BeadStatus::Closed
+            && let (Some(created), Some(closed)) = (bead.created_at, bead.closed_at)
+        {
+            stats.cycle_times.0 += 1;
+            cycle_total += closed - created;
+        }
+    }
+
+    stats
+        .by_type
+        .sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    stats.by_assignee = by_assignee
+        .into_iter()
+        .map(|(name, count)| (name.map(str::to_string), count))
+        .collect();
+    // Most beads first, then by name with unassigned last
+    stats.by_assignee.sort_by(|a, b| {
+        b.1.cmp(&a.1)
+            .then_with(|| a.0.is_none().cmp(&b.0.is_none()))
+            .then_with(|| a.0.cmp(&b.0))
+    });
+    let closed = stats.cycle_times.0;
+    if closed > 0 {
+        stats.cycle_times.1 = Some(cycle_total / closed as i32);
+    }
+    stats
+}
+
+/// IDs of the beads that aren't closed, i.e. the ones that still block others
+pub fn not_closed_ids(beads: &[Bead]) -> HashSet<&str> {
+    beads
+        .iter()
+        .filter(|b| b.status != BeadStatus::Closed)
+        .map(|b| b.id.as_str())
+        .collect()
+}
+
+/// Like [`build_tree_order`], but also reports which rows have children.
+/// Children of beads in `opts.collapsed` are left out.
+pub fn build_tree_rows<'a>(beads: &'a [Bead], opts: &TreeOptions) -> Vec<TreeRow<'a>> {
+    // An explicit closed status filter wins over hide_closed
+    let hide_closed = opts.hide_closed && opts.status != Some(BeadStatus::Closed);
+
+    // Text filter scores, used to rank matches best-first
+    let matcher = SkimMatcherV2::default();
+    let regex = opts.filter.as_deref().and_then(filter_regex);
+    let mut scores: HashMap<&str, i64> = HashMap::new();
+
+    // Blockers that still count, for what's ready and which beads are
+    // effectively blocked (as they're drawn)
+    let not_closed = if opts.ready_only
+        || opts.status.is_some()
+        || opts.flat
+        || opts.group_by != GroupBy::None
+    {
+        not_closed_ids(beads)
+    } else {
+        HashSet::new()
+    };
+
     // Filter beads first
     let filtered: Vec<&Bead> = beads
         .iter()
@@ -309,25 +978,91 @@ pub fn build_tree_order<'a>(
             if hide_closed && b.status == BeadStatus::Closed {
                 return false;
             }
-            // Apply text filter (matches title or ID)
-            filter
-                .map(|f| {
-                    let f_lower = f.to_lowercase();
-                    b.title.to_lowercase().contains(&f_lower)
-                        || b.id.to_lowercase().contains(&f_lower)
-                })
-                .unwrap_or(true)
+            // Apply status filter, to the status the bead is shown with
+            if opts
+                .status
+                .is_some_and(|s| s != b.effective_status(&not_closed))
+            {
+                return false;
+            }
+            // Apply priority filter
+            if opts.max_priority.is_some_and(|p| b.priority > p) {
+                return false;
+            }
+            // Apply ready filter
+            if opts.ready_only && !b.is_ready(&not_closed) {
+                return false;
+            }
+            // Apply label filter
+            if !opts.labels.is_empty() && !b.has_labels(&opts.labels, opts.label_match) {
+                return false;
+            }
+            // Apply text filter
+            match (opts.filter.as_deref(), &regex) {
+                // A bad regex matches nothing (the footer says why)
+                (Some(_), Some(re)) => re.as_ref().is_ok_and(|re| regex_matches(b, re)),
+                (Some(f), None) => match filter_score(b, f, &matcher) {
+                    Some(score) => {
+                        scores.insert(b.id.as_str(), score);
+                        true
+                    }
+                    None => false,
+                },
+                (None, _) => true,
+            }
         })
         .collect();
 
+    // Best filter match first (all scores are equal without a filter), then the sort mode
+    let score = |b: &Bead| scores.get(b.id.as_str()).copied().unwrap_or(0);
+    let compare = |a: &Bead, b: &Bead| score(b).cmp(&score(a)).then_with(|| opts.compare(a, b));
+
+    // Flat and grouped lists skip the tree: each group in turn (just one
+    // when not grouped), sorted within it
+    if opts.flat || opts.group_by != GroupBy::None {
+        let mut grouped: Vec<_> = filtered
+            .into_iter()
+            .map(|b| (opts.group_by.group_of(b, &not_closed), b))
+            .collect();
+        grouped.sort_by(|(a_group, a), (b_group, b)| {
+            let rank = |group: &Option<(usize, _)>| group.map(|(rank, _)| rank);
+            rank(a_group)
+                .cmp(&rank(b_group))
+                .then_with(|| compare(a, b))
+        });
+        let mut rows: Vec<TreeRow> = Vec::with_capacity(grouped.len());
+        // Index of the row starting the current group, to count its beads
+        let mut start = 0;
+        for (idx, (group, bead)) in grouped.iter().enumerate() {
+            let starts_group = idx == 0 || grouped[idx - 1].0 != *group;
+            if starts_group {
+                start = idx;
+            }
+            rows.push(TreeRow {
+                bead,
+                depth: 0,
+                child_count: 0,
+                last_child: false,
+                edge: None,
+                group_start: group
+                    .filter(|_| starts_group)
+                    .map(|(_, heading)| (heading, 0)),
+            });
+            if let Some((_, count)) = rows[start].group_start.as_mut() {
+                *count += 1;
+            }
+        }
+        return rows;
+    }
+
     // Separate closed and non-closed
-    let (closed, non_closed): (Vec<_>, Vec<_>) = filtered
+    let (mut closed, non_closed): (Vec<_>, Vec<_>) = filtered
         .into_iter()
         .partition(|b| b.status == BeadStatus::Closed);
 
     // Build parent -> children map for non-closed beads
     let non_closed_ids: HashSet<&str> = non_closed.iter().map(|b| b.id.as_str()).collect();
-    let mut children_map: HashMap<&str, Vec<&Bead>> = HashMap::new();
+    let mut children_map: HashMap<&str, Vec<(&Bead, DependencyType)>> = HashMap::new();
     let mut has_parent: HashSet<&str> = HashSet::new();
 
     for bead in &non_closed {
@@ -337,17 +1072,18 @@ pub fn build_tree_order<'a>(
                 children_map
                     .entry(parent_id.as_str())
                     .or_default()
-                    .push(bead);
+                    .push((bead, DependencyType::ParentChild));
                 has_parent.insert(bead.id.as_str());
             }
         }
-        // Blocked-by relationships: if A is blocked by B, show A under B
+        // Blocked-by relationships: if A is blocked by B, show A under B,
+        // unless A is already B's sub-task
         for blocker_id in &bead.blocked_by {
             if non_closed_ids.contains(blocker_id.as_str()) {
-                children_map
-                    .entry(blocker_id.as_str())
-                    .or_default()
-                    .push(bead);
+                let children = children_map.entry(blocker_id.as_str()).or_default();
+                if !children.iter().any(|(child, _)| child.id == bead.id) {
+                    children.push((bead, DependencyType::Blocks));
+                }
                 has_parent.insert(bead.id.as_str());
             }
         }
@@ -360,44 +1096,836 @@ pub fn build_tree_order<'a>(
         .copied()
         .collect();
 
-    // Sort roots: non-deferred first (by priority, then title), deferred last (by priority, then title)
+    // Sort roots: best match first, then non-deferred before deferred, then the sort mode
     roots.sort_by(|a, b| {
-        a.is_deferred()
-            .cmp(&b.is_deferred())
-            .then(a.priority.cmp(&b.priority))
-            .then(a.title.cmp(&b.title))
+        score(b)
+            .cmp(&score(a))
+            .then_with(|| a.is_deferred().cmp(&b.is_deferred()))
+            .then_with(|| opts.compare(a, b))
     });
 
-    // DFS to build ordered list with depths
-    let mut result: Vec<(&Bead, usize)> = Vec::new();
-    let mut stack: Vec<(&Bead, usize)> = roots.into_iter().map(|b| (b, 0)).rev().collect();
+    // Beads in a dependency cycle all have parents, so none of them is a
+    // root. Promote the first unreachable bead (in sort order) of each such
+    // group so cycles don't silently drop out of the list.
+    fn mark_reachable<'a>(
+        from: &'a Bead,
+        children_map: &HashMap<&str, Vec<(&'a Bead, DependencyType)>>,
+        reachable: &mut HashSet<&'a str>,
+    ) {
+        let mut stack = vec![from];
+        while let Some(bead) = stack.pop() {
+            if reachable.insert(bead.id.as_str()) {
+                stack.extend(
+                    children_map
+                        .get(bead.id.as_str())
+                        .into_iter()
+                        .flatten()
+                        .map(|(child, _)| *child),
+                );
+            }
+        }
+    }
+    let mut reachable: HashSet<&str> = HashSet::new();
+    for root in &roots {
+        mark_reachable(root, &children_map, &mut reachable);
+    }
+    let mut rest = non_closed.clone();
+    rest.sort_by(|a, b| compare(a, b));
+    for bead in rest {
+        if !reachable.contains(bead.id.as_str()) {
+            mark_reachable(bead, &children_map, &mut reachable);
+            roots.push(bead);
+        }
+    }
+
+    // Each bead's children in the order they're drawn
+    for children in children_map.values_mut() {
+        children.sort_by(|(a, _), (b, _)| compare(a, b));
+    }
+    let children_of = |bead: &Bead| -> &[(&'a Bead, DependencyType)] {
+        children_map
+            .get(bead.id.as_str())
+            .map_or(&[], Vec::as_slice)
+    };
+
+    // DFS to build ordered list with depths. A bead reachable from several
+    // rows is placed under the first one only.
+    let mut result: Vec<TreeRow> = Vec::new();
+    let mut stack: Vec<(&Bead, usize, Option<DependencyType>)> =
+        roots.into_iter().map(|b| (b, 0, None)).rev().collect();
     let mut visited: HashSet<&str> = HashSet::new();
 
-    while let Some((bead, depth)) = stack.pop() {
-        // Skip if already visited (can happen with multiple dependency types)
-        if visited.contains(bead.id.as_str()) {
+    while let Some((bead, depth, edge)) = stack.pop() {
+        if !visited.insert(bead.id.as_str()) {
+            continue;
+        }
+        let collapsed = opts.collapsed.contains(&bead.id);
+        // A collapsed row counts the children expanding it would show: the
+        // ones a walk from here places directly under it. The walk marks what
+        // it places as visited and unmarks it afterwards, so each hidden bead
+        // is walked once per collapsed row above it. Expanded rows are
+        // counted from the rows below them once the walk is done.
+        let child_count = if collapsed {
+            let mut below: Vec<(&Bead, usize)> = children_of(bead)
+                .iter()
+                .rev()
+                .map(|(child, _)| (*child, depth + 1))
+                .collect();
+            let mut placed = Vec::new();
+            let mut count = 0;
+            while let Some((bead, at)) = below.pop() {
+                if !visited.insert(bead.id.as_str()) {
+                    continue;
+                }
+                placed.push(bead.id.as_str());
+                count += usize::from(at == depth + 1);
+                if !opts.collapsed.contains(&bead.id) {
+                    below.extend(children_of(bead).iter().rev().map(|(c, _)| (*c, at + 1)));
+                }
+            }
+            for id in placed {
+                visited.remove(id);
+            }
+            count
+        } else {
+            0
+        };
+        result.push(TreeRow {
+            bead,
+            depth,
+            child_count,
+            last_child: false,
+            edge,
+            group_start: None,
+        });
+        if collapsed {
             continue;
         }
-        visited.insert(bead.id.as_str());
-        result.push((bead, depth));
 
         // Add children in reverse order (so they come out in correct order)
-        if let Some(children) = children_map.get(bead.id.as_str()) {
-            let mut sorted_children = children.clone();
-            sorted_children.sort_by(|a, b| {
-                b.priority.cmp(&a.priority).then(b.title.cmp(&a.title)) // Reverse for stack
-            });
-            for child in sorted_children {
-                stack.push((child, depth + 1));
-            }
+        for (child, edge) in children_of(bead).iter().rev() {
+            stack.push((child, depth + 1, Some(*edge)));
         }
     }
+    // Each row counts toward the nearest row above it one level up. Collapsed
+    // rows have none below them, so they keep their count from the walk.
+    let mut ancestors: Vec<usize> = Vec::new();
+    for idx in 0..result.len() {
+        let depth = result[idx].depth;
+        ancestors.truncate(depth);
+        if let Some(&parent) = ancestors.last() {
+            result[parent].child_count += 1;
+        }
+        ancestors.push(idx);
+    }
 
     // Add closed beads flat at the end (depth 0)
-    // They're already sorted by closed_at from sqlite.rs, so just append in order
+    closed.sort_by(|a, b| compare(a, b));
     for bead in closed {
-        result.push((bead, 0));
+        result.push(TreeRow {
+            bead,
+            depth: 0,
+            child_count: 0,
+            last_child: false,
+            edge: None,
+            group_start: None,
+        });
+    }
+
+    // Walking up from the bottom, a row is the last child when no row at its
+    // depth came after it before the tree climbed back above it. This counts
+    // only the children actually placed here, not ones shown under another
+    // parent.
+    let mut sibling_below: Vec<bool> = Vec::new();
+    for row in result.iter_mut().rev() {
+        sibling_below.resize(row.depth + 1, false);
+        row.last_child = !sibling_below[row.depth];
+        sibling_below[row.depth] = true;
     }
 
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bead(id: &str, status: BeadStatus) -> Bead {
+        Bead {
+            id: id.to_string(),
+            title: id.to_string(),
+            status,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn stats_tally_every_dimension() {
+        let day = |d: u32| format!("2026-01-{:02}T00:00:00Z", d).parse().ok();
+        let mut fix = bead("bd-1", BeadStatus::Closed);
+        fix.bead_type = BeadType::Bug;
+        fix.assignee = Some("alice".to_string());
+        (fix.created_at, fix.closed_at) = (day(1), day(3));
+        let mut feature = bead("bd-2", BeadStatus::Closed);
+        (feature.created_at, feature.closed_at) = (day(1), day(5));
+        let mut open = bead("bd-3", BeadStatus::Open);
+        open.priority = 0;
+        open.bead_type = BeadType::Bug;
+        let beads = [fix, feature, open, bead("bd-4", BeadStatus::Open)];
+
+        let stats = bead_stats(&beads);
+        assert_eq!(stats.total, 4);
+        assert_eq!(stats.by_status[0], (BeadStatus::Open, 2));
+        assert_eq!(stats.by_status.len(), BeadStatus::all().len());
+        assert_eq!(stats.by_priority[0], 1);
+        assert_eq!(stats.by_type, vec![(BeadType::Bug, 2), (BeadType::Task, 2)]);
+        assert_eq!(
+            stats.by_assignee,
+            vec![(None, 3), (Some("alice".to_string()), 1)]
+        );
+        assert_eq!(stats.cycle_times, (2, Some(chrono::Duration::days(3))));
+    }
+
+    fn with_parent(mut bead: Bead, parent: &str) -> Bead {
+        bead.parent_ids.push(parent.to_string());
+        bead
+    }
+
+    fn ids(order: &[(&Bead, usize)]) -> Vec<String> {
+        order.iter().map(|(b, _)| b.id.clone()).collect()
+    }
+
+    #[test]
+    fn collapsed_beads_hide_their_subtree() {
+        let beads = vec![
+            bead("bd-1", BeadStatus::Open),
+            with_parent(bead("bd-2", BeadStatus::Open), "bd-1"),
+            with_parent(bead("bd-3", BeadStatus::Open), "bd-2"),
+            bead("bd-4", BeadStatus::Open),
+        ];
+        let mut opts = TreeOptions::default();
+
+        let rows = build_tree_rows(&beads, &opts);
+        let parents: Vec<&str> = rows
+            .iter()
+            .filter(|r| r.has_children())
+            .map(|r| r.bead.id.as_str())
+            .collect();
+        assert_eq!(parents, vec!["bd-1", "bd-2"]);
+
+        opts.collapsed.insert("bd-2".to_string());
+        assert_eq!(
+            ids(&build_tree_order(&beads, &opts)),
+            vec!["bd-1", "bd-2", "bd-4"]
+        );
+
+        opts.collapsed.insert("bd-1".to_string());
+        let rows = build_tree_rows(&beads, &opts);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].child_count, 1);
+    }
+
+    #[test]
+    fn child_count_only_counts_visible_children_once() {
+        let mut both = with_parent(bead("bd-2", BeadStatus::Open), "bd-1");
+        both.blocked_by.push("bd-1".to_string());
+        let beads = vec![
+            bead("bd-1", BeadStatus::Open),
+            both,
+            with_parent(bead("bd-3", BeadStatus::Closed), "bd-1"),
+            with_parent(bead("bd-4", BeadStatus::Open), "bd-1"),
+        ];
+        let opts = TreeOptions {
+            hide_closed: true,
+            ..Default::default()
+        };
+
+        let rows = build_tree_rows(&beads, &opts);
+        assert_eq!(rows[0].bead.id, "bd-1");
+        assert_eq!(rows[0].child_count, 2);
+    }
+
+    #[test]
+    fn child_count_only_counts_children_placed_under_the_row() {
+        // bd-3 is a sub-task of both, but is drawn under bd-1 only
+        let mut shared = with_parent(bead("bd-3", BeadStatus::Open), "bd-1");
+        shared.parent_ids.push("bd-2".to_string());
+        let beads = vec![
+            bead("bd-1", BeadStatus::Open),
+            bead("bd-2", BeadStatus::Open),
+            shared,
+        ];
+        let mut opts = TreeOptions::default();
+        let counts = |opts: &TreeOptions| -> Vec<(String, usize)> {
+            build_tree_rows(&beads, opts)
+                .iter()
+                .map(|r| (r.bead.id.clone(), r.child_count))
+                .collect()
+        };
+        let row = |id: &str, count: usize| (id.to_string(), count);
+
+        assert_eq!(
+            counts(&opts),
+            vec![row("bd-1", 1), row("bd-3", 0), row("bd-2", 0)]
+        );
+
+        // Collapsing bd-1 hands bd-3 to bd-2; expanding bd-1 takes it back
+        opts.collapsed.insert("bd-1".to_string());
+        assert_eq!(
+            counts(&opts),
+            vec![row("bd-1", 1), row("bd-2", 1), row("bd-3", 0)]
+        );
+        opts.collapsed.insert("bd-2".to_string());
+        assert_eq!(counts(&opts), vec![row("bd-1", 1), row("bd-2", 1)]);
+    }
+
+    #[test]
+    fn nested_rows_say_whether_they_are_sub_tasks_or_blocked() {
+        let mut both = with_parent(bead("bd-2", BeadStatus::Open), "bd-1");
+        both.blocked_by.push("bd-1".to_string());
+        let mut blocked = bead("bd-3", BeadStatus::Open);
+        blocked.blocked_by.push("bd-1".to_string());
+        let beads = vec![bead("bd-1", BeadStatus::Open), both, blocked];
+
+        let rows = build_tree_rows(&beads, &TreeOptions::default());
+        let edges: Vec<(&str, Option<DependencyType>)> =
+            rows.iter().map(|r| (r.bead.id.as_str(), r.edge)).collect();
+        assert_eq!(
+            edges,
+            vec![
+                ("bd-1", None),
+                ("bd-2", Some(DependencyType::ParentChild)),
+                ("bd-3", Some(DependencyType::Blocks)),
+            ]
+        );
+    }
+
+    #[test]
+    fn last_child_marks_the_final_sibling_under_each_parent() {
+        let beads = vec![
+            bead("bd-1", BeadStatus::Open),
+            with_parent(bead("bd-2", BeadStatus::Open), "bd-1"),
+            with_parent(bead("bd-3", BeadStatus::Open), "bd-2"),
+            with_parent(bead("bd-4", BeadStatus::Open), "bd-1"),
+            bead("bd-5", BeadStatus::Open),
+        ];
+
+        let rows = build_tree_rows(&beads, &TreeOptions::default());
+        let flags: Vec<(&str, usize, bool)> = rows
+            .iter()
+            .map(|r| (r.bead.id.as_str(), r.depth, r.last_child))
+            .collect();
+        assert_eq!(
+            flags,
+            vec![
+                ("bd-1", 0, false),
+                ("bd-2", 1, false),
+                ("bd-3", 2, true),
+                ("bd-4", 1, true),
+                ("bd-5", 0, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn find_cycles_reports_loops_through_blockers_and_parents() {
+        let mut a = bead("bd-a", BeadStatus::Open);
+        a.blocked_by.push("bd-b".to_string());
+        let b = with_parent(bead("bd-b", BeadStatus::Open), "bd-c");
+        let mut c = bead("bd-c", BeadStatus::Open);
+        c.blocked_by.push("bd-a".to_string());
+        let mut own = bead("bd-self", BeadStatus::Open);
+        own.blocked_by.push("bd-self".to_string());
+        let fine = with_parent(bead("bd-x", BeadStatus::Open), "bd-a");
+
+        let cycles = find_cycles(&[a, b, c, own, fine]);
+
+        assert_eq!(
+            cycles,
+            vec![
+                vec!["bd-a".to_string(), "bd-b".to_string(), "bd-c".to_string()],
+                vec!["bd-self".to_string()],
+            ]
+        );
+    }
+
+    #[test]
+    fn beads_in_a_cycle_still_appear_in_the_tree() {
+        let mut a = bead("bd-a", BeadStatus::Open);
+        a.blocked_by.push("bd-b".to_string());
+        let mut b = bead("bd-b", BeadStatus::Open);
+        b.blocked_by.push("bd-a".to_string());
+        let beads = vec![a, b, bead("bd-c", BeadStatus::Open)];
+
+        let order = build_tree_order(&beads, &TreeOptions::default());
+
+        assert_eq!(ids(&order), vec!["bd-c", "bd-a", "bd-b"]);
+        assert_eq!(order[2].1, 1);
+    }
+
+    #[test]
+    fn status_icons_differ_by_shape_for_every_type() {
+        let types = [
+            BeadType::Task,
+            BeadType::Bug,
+            BeadType::Feature,
+            BeadType::Epic,
+            BeadType::Story,
+        ];
+        let mut active = HashSet::new();
+        for bead_type in types {
+            let icons: HashSet<_> = BeadStatus::all()
+                .iter()
+                .map(|status| bead_type.icon_for_status(status))
+                .collect();
+            assert_eq!(icons.len(), BeadStatus::all().len(), "{:?}", bead_type);
+            active.insert(bead_type.icon_outline());
+            active.insert(bead_type.icon_filled());
+        }
+        // Types keep their own shapes while open or in progress, none of
+        // them used for blocked, deferred or closed beads
+        assert_eq!(active.len(), 2 * types.len());
+        for status in [
+            BeadStatus::Blocked,
+            BeadStatus::Deferred,
+            BeadStatus::Closed,
+        ] {
+            assert!(!active.contains(status.icon()), "{:?}", status);
+        }
+    }
+
+    #[test]
+    fn grouping_flattens_the_tree_into_status_and_type_buckets() {
+        let epic = Bead {
+            bead_type: BeadType::Epic,
+            ..bead("bd-1", BeadStatus::Open)
+        };
+        let mut waiting = bead("bd-2", BeadStatus::Open);
+        waiting.blocked_by.push("bd-1".to_string());
+        let mut child = bead("bd-3", BeadStatus::InProgress);
+        child.parent_ids.push("bd-1".to_string());
+        let beads = vec![epic, waiting, child, bead("bd-4", BeadStatus::Closed)];
+        let mut opts = TreeOptions {
+            group_by: GroupBy::Status,
+            ..Default::default()
+        };
+
+        // Children come out of the tree, and bd-2 counts as blocked
+        let rows = build_tree_rows(&beads, &opts);
+        let seen: Vec<_> = rows.iter().map(|r| (r.bead.id.as_str(), r.depth)).collect();
+        assert_eq!(
+            seen,
+            vec![("bd-3", 0), ("bd-1", 0), ("bd-2", 0), ("bd-4", 0)]
+        );
+        assert!(rows.iter().all(|r| !r.has_children()));
+
+        opts.group_by = GroupBy::Type;
+        assert_eq!(
+            ids(&build_tree_order(&beads, &opts)),
+            vec!["bd-2", "bd-3", "bd-4", "bd-1"]
+        );
+        // Each group's first row carries its heading and size
+        let starts: Vec<_> = build_tree_rows(&beads, &opts)
+            .iter()
+            .map(|r| r.group_start)
+            .collect();
+        assert_eq!(
+            starts,
+            vec![Some(("Tasks", 3)), None, None, Some(("Epics", 1))]
+        );
+        assert_eq!(
+            GroupBy::Type.group_of(&beads[0], &HashSet::new()),
+            Some((3, "Epics"))
+        );
+        assert_eq!(GroupBy::None.group_of(&beads[0], &HashSet::new()), None);
+    }
+
+    #[test]
+    fn flat_lists_ignore_the_tree_and_collapsing() {
+        let beads = vec![
+            bead("bd-1", BeadStatus::Open),
+            with_parent(bead("bd-3", BeadStatus::Open), "bd-1"),
+            bead("bd-2", BeadStatus::Open),
+        ];
+        let mut opts = TreeOptions {
+            sort: SortMode::Title,
+            flat: true,
+            ..Default::default()
+        };
+        opts.collapsed.insert("bd-1".to_string());
+
+        let rows = build_tree_rows(&beads, &opts);
+        let seen: Vec<_> = rows.iter().map(|r| (r.bead.id.as_str(), r.depth)).collect();
+        assert_eq!(seen, vec![("bd-1", 0), ("bd-2", 0), ("bd-3", 0)]);
+        assert!(rows.iter().all(|r| !r.has_children()));
+    }
+
+    #[test]
+    fn status_filter_keeps_only_matching_beads() {
+        let beads = vec![
+            bead("bd-1", BeadStatus::Open),
+            bead("bd-2", BeadStatus::InProgress),
+            bead("bd-3", BeadStatus::Closed),
+        ];
+        let opts = TreeOptions {
+            status: Some(BeadStatus::InProgress),
+            ..Default::default()
+        };
+
+        assert_eq!(ids(&build_tree_order(&beads, &opts)), vec!["bd-2"]);
+    }
+
+    #[test]
+    fn status_filter_matches_the_status_beads_are_shown_with() {
+        let mut waiting = bead("bd-2", BeadStatus::Open);
+        waiting.blocked_by.push("bd-1".to_string());
+        let mut unblocked = bead("bd-4", BeadStatus::Open);
+        unblocked.blocked_by.push("bd-3".to_string());
+        let beads = vec![
+            bead("bd-1", BeadStatus::Open),
+            waiting,
+            bead("bd-3", BeadStatus::Closed),
+            unblocked,
+            bead("bd-5", BeadStatus::Blocked),
+        ];
+        let mut opts = TreeOptions {
+            status: Some(BeadStatus::Blocked),
+            ..Default::default()
+        };
+
+        // bd-2 is open but waits on bd-1; bd-4's blocker is closed
+        assert_eq!(ids(&build_tree_order(&beads, &opts)), vec!["bd-2", "bd-5"]);
+
+        opts.status = Some(BeadStatus::Open);
+        assert_eq!(ids(&build_tree_order(&beads, &opts)), vec!["bd-1", "bd-4"]);
+    }
+
+    #[test]
+    fn closed_status_filter_overrides_hide_closed() {
+        let beads = vec![
+            bead("bd-1", BeadStatus::Open),
+            bead("bd-2", BeadStatus::Closed),
+        ];
+        let opts = TreeOptions {
+            hide_closed: true,
+            status: Some(BeadStatus::Closed),
+            ..Default::default()
+        };
+
+        assert_eq!(ids(&build_tree_order(&beads, &opts)), vec!["bd-2"]);
+    }
+
+    #[test]
+    fn priority_filter_promotes_matching_children_to_roots() {
+        let mut parent = bead("bd-1", BeadStatus::Open);
+        parent.priority = 3;
+        let mut child = with_parent(bead("bd-2", BeadStatus::Open), "bd-1");
+        child.priority = 1;
+        let beads = vec![parent, child];
+        let opts = TreeOptions {
+            max_priority: Some(1),
+            ..Default::default()
+        };
+
+        let order = build_tree_order(&beads, &opts);
+
+        assert_eq!(ids(&order), vec!["bd-2"]);
+        assert_eq!(order[0].1, 0);
+    }
+
+    #[test]
+    fn at_filter_matches_assignee_only() {
+        let mut alice = bead("bd-1", BeadStatus::Open);
+        alice.assignee = Some("Alice".to_string());
+        let mut titled = bead("bd-2", BeadStatus::Open);
+        titled.title = "email @alice".to_string();
+
+        assert!(matches_filter(&alice, "@ali"));
+        assert!(!matches_filter(&titled, "@ali"));
+        assert!(!matches_filter(&bead("bd-3", BeadStatus::Open), "@"));
+        assert!(matches_filter(&alice, "@"));
+    }
+
+    #[test]
+    fn tilde_filter_matches_creator_only() {
+        let mut opened = bead("bd-1", BeadStatus::Open);
+        opened.created_by = Some("Alice".to_string());
+        opened.assignee = Some("bob".to_string());
+
+        assert!(matches_filter(&opened, "~ali"));
+        assert!(!matches_filter(&opened, "~bob"));
+        assert!(!matches_filter(&bead("bd-2", BeadStatus::Open), "~"));
+    }
+
+    #[test]
+    fn created_sort_orders_roots_and_closed_newest_first() {
+        use chrono::TimeZone;
+
+        let at = |day| Some(Utc.with_ymd_and_hms(2026, 1, day, 0, 0, 0).unwrap());
+        let mut old = bead("bd-old", BeadStatus::Open);
+        old.created_at = at(1);
+        let mut new = bead("bd-new", BeadStatus::Open);
+        new.created_at = at(5);
+        let mut closed_old = bead("bd-c-old", BeadStatus::Closed);
+        closed_old.created_at = at(2);
+        let mut closed_new = bead("bd-c-new", BeadStatus::Closed);
+        closed_new.created_at = at(6);
+        let beads = vec![old, closed_old, new, closed_new];
+        let opts = TreeOptions {
+            sort: SortMode::Created,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            ids(&build_tree_order(&beads, &opts)),
+            vec!["bd-new", "bd-old", "bd-c-new", "bd-c-old"]
+        );
+    }
+
+    #[test]
+    fn filter_matches_description_and_labels() {
+        let mut described = bead("bd-desc", BeadStatus::Open);
+        described.description = Some("Crashes when the Cache is cold".to_string());
+        let mut labeled = bead("bd-label", BeadStatus::Open);
+        labeled.labels = vec!["Frontend".to_string()];
+        let beads = vec![described, labeled, bead("bd-plain", BeadStatus::Open)];
+
+        let search = |filter: &str| {
+            ids(&build_tree_order(
+                &beads,
+                &TreeOptions {
+                    filter: Some(filter.to_string()),
+                    ..Default::default()
+                },
+            ))
+        };
+        assert_eq!(search("cache"), vec!["bd-desc"]);
+        assert_eq!(search("frontend"), vec!["bd-label"]);
+    }
+
+    #[test]
+    fn fuzzy_filter_matches_subsequences_best_first() {
+        let mut loose = bead("bd-1", BeadStatus::Open);
+        loose.title = "Subtle rendering glitch".to_string();
+        let mut tight = bead("bd-2", BeadStatus::Open);
+        tight.title = "Fix button color".to_string();
+        let mut miss = bead("bd-3", BeadStatus::Open);
+        miss.title = "Write docs".to_string();
+        let beads = vec![loose, tight, miss];
+        let opts = TreeOptions {
+            filter: Some("btn".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(ids(&build_tree_order(&beads, &opts)), vec!["bd-2", "bd-1"]);
+    }
+
+    #[test]
+    fn match_indices_cover_fuzzy_and_exact_hits() {
+        let matcher = SkimMatcherV2::default();
+        assert_eq!(
+            filter_match_indices("Fix Button", "btn", &matcher),
+            vec![4, 7, 9]
+        );
+        assert_eq!(
+            filter_match_indices("Fix Button", "'BUT", &matcher),
+            vec![4, 5, 6]
+        );
+        assert!(filter_match_indices("Fix Button", "@bob", &matcher).is_empty());
+    }
+
+    #[test]
+    fn quote_prefix_forces_substring_match() {
+        let mut button = bead("bd-1", BeadStatus::Open);
+        button.title = "Fix button color".to_string();
+        let mut btn = bead("bd-2", BeadStatus::Open);
+        btn.title = "Rename btn helper".to_string();
+
+        assert!(!matches_filter(&button, "'btn"));
+        assert!(matches_filter(&btn, "'btn"));
+        assert!(matches_filter(&button, "btn"));
+    }
+
+    #[test]
+    fn slashes_make_a_regex_filter() {
+        let mut ticket = bead("bd-1", BeadStatus::Open);
+        ticket.title = "PROJ-142 flaky login test".to_string();
+        let mut mention = bead("bd-2", BeadStatus::Open);
+        mention.title = "Follow up on PROJ-142".to_string();
+
+        assert!(matches_filter(&ticket, "/^proj-\\d+/"));
+        assert!(!matches_filter(&mention, "/^proj-\\d+/"));
+        // The closing slash is optional
+        assert!(matches_filter(&mention, "/follow|login"));
+
+        // A bad regex matches nothing, rather than falling back to fuzzy
+        assert!(filter_regex("/proj-(").is_some_and(|re| re.is_err()));
+        assert!(!matches_filter(&ticket, "/proj-("));
+        assert!(filter_regex("proj").is_none());
+
+        let matcher = SkimMatcherV2::default();
+        assert_eq!(
+            filter_match_indices("Fix böx 42", "/\\d+", &matcher),
+            vec![8, 9]
+        );
+    }
+
+    #[test]
+    fn open_descendants_walks_subtree_and_skips_closed() {
+        let beads = vec![
+            bead("bd-root", BeadStatus::Open),
+            with_parent(bead("bd-child", BeadStatus::Open), "bd-root"),
+            with_parent(bead("bd-done", BeadStatus::Closed), "bd-root"),
+            with_parent(bead("bd-grandchild", BeadStatus::Blocked), "bd-done"),
+            bead("bd-other", BeadStatus::Open),
+        ];
+
+        let mut found = open_descendants(&beads, "bd-root");
+        found.sort();
+        assert_eq!(found, vec!["bd-child", "bd-grandchild"]);
+        assert!(open_descendants(&beads, "bd-other").is_empty());
+    }
+
+    #[test]
+    fn reverse_flips_sort_but_keeps_deferred_last() {
+        let mut a = bead("bd-a", BeadStatus::Open);
+        a.title = "Alpha".to_string();
+        let mut b = bead("bd-b", BeadStatus::Open);
+        b.title = "Beta".to_string();
+        let mut deferred = bead("bd-d", BeadStatus::Deferred);
+        deferred.title = "Aardvark".to_string();
+        let beads = vec![a, b, deferred];
+        let opts = TreeOptions {
+            sort: SortMode::Title,
+            reverse: true,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            ids(&build_tree_order(&beads, &opts)),
+            vec!["bd-b", "bd-a", "bd-d"]
+        );
+    }
+
+    #[test]
+    fn stale_beads_are_open_and_not_updated_recently() {
+        let now = Utc::now();
+        let month = chrono::Duration::days(30);
+        let mut old = bead("bd-old", BeadStatus::Open);
+        old.updated_at = Some(now - chrono::Duration::days(45));
+        let mut fresh = bead("bd-fresh", BeadStatus::InProgress);
+        fresh.updated_at = Some(now - chrono::Duration::days(2));
+        let mut closed = old.clone();
+        closed.status = BeadStatus::Closed;
+
+        assert!(old.is_stale(month, now));
+        assert!(!fresh.is_stale(month, now));
+        assert!(!closed.is_stale(month, now));
+        assert!(!bead("bd-undated", BeadStatus::Open).is_stale(month, now));
+    }
+
+    #[test]
+    fn recency_buckets_today_this_week_and_older() {
+        let now = Utc::now();
+        let updated = |days| Bead {
+            updated_at: Some(now - chrono::Duration::days(days)),
+            ..bead("bd-1", BeadStatus::Open)
+        };
+
+        assert_eq!(updated(0).recency(now), Recency::Today);
+        assert_eq!(updated(3).recency(now), Recency::ThisWeek);
+        assert_eq!(updated(10).recency(now), Recency::Older);
+        assert_eq!(bead("bd-2", BeadStatus::Open).recency(now), Recency::Older);
+    }
+
+    #[test]
+    fn label_filter_matches_any_or_all_labels() {
+        let labeled = |id: &str, labels: &[&str]| {
+            let mut b = bead(id, BeadStatus::Open);
+            b.labels = labels.iter().map(|l| l.to_string()).collect();
+            b
+        };
+        let beads = vec![
+            labeled("bd-ui", &["ui"]),
+            labeled("bd-ui-bug", &["ui", "bug"]),
+            labeled("bd-bug", &["bug"]),
+            labeled("bd-none", &[]),
+        ];
+        assert_eq!(
+            label_counts(&beads),
+            vec![("bug".to_string(), 2), ("ui".to_string(), 2)]
+        );
+
+        let mut opts = TreeOptions {
+            labels: vec!["ui".to_string(), "bug".to_string()],
+            sort: SortMode::Title,
+            ..Default::default()
+        };
+        assert_eq!(
+            ids(&build_tree_order(&beads, &opts)),
+            vec!["bd-bug", "bd-ui", "bd-ui-bug"]
+        );
+        opts.label_match = LabelMatch::All;
+        assert_eq!(ids(&build_tree_order(&beads, &opts)), vec!["bd-ui-bug"]);
+    }
+
+    #[test]
+    fn ready_only_keeps_unblocked_open_work() {
+        let done = bead("bd-done", BeadStatus::Closed);
+        let mut unblocked = bead("bd-unblocked", BeadStatus::Open);
+        unblocked.blocked_by.push("bd-done".to_string());
+        let mut waiting = bead("bd-waiting", BeadStatus::Open);
+        waiting.blocked_by.push("bd-started".to_string());
+        let beads = vec![
+            done,
+            unblocked,
+            waiting,
+            bead("bd-started", BeadStatus::InProgress),
+            bead("bd-blocked", BeadStatus::Blocked),
+            bead("bd-later", BeadStatus::Deferred),
+        ];
+        let opts = TreeOptions {
+            ready_only: true,
+            ..Default::default()
+        };
+
+        let mut ready = ids(&build_tree_order(&beads, &opts));
+        ready.sort();
+        assert_eq!(ready, vec!["bd-started", "bd-unblocked"]);
+    }
+
+    #[test]
+    fn open_beads_with_open_blockers_show_as_blocked() {
+        let mut waiting = bead("bd-waiting", BeadStatus::Open);
+        waiting.blocked_by.push("bd-blocker".to_string());
+        let mut unblocked = bead("bd-unblocked", BeadStatus::Open);
+        unblocked.blocked_by.push("bd-done".to_string());
+        let beads = vec![
+            waiting,
+            unblocked,
+            bead("bd-blocker", BeadStatus::InProgress),
+            bead("bd-done", BeadStatus::Closed),
+        ];
+        let not_closed = not_closed_ids(&beads);
+
+        assert_eq!(beads[0].effective_status(&not_closed), BeadStatus::Blocked);
+        assert_eq!(beads[1].effective_status(&not_closed), BeadStatus::Open);
+        assert_eq!(
+            beads[2].effective_status(&not_closed),
+            BeadStatus::InProgress
+        );
+    }
+
+    #[test]
+    fn priority_levels_clamp_into_range() {
+        assert_eq!(Priority::clamped(-3), Priority::Critical);
+        assert_eq!(Priority::clamped(2), Priority::Medium);
+        assert_eq!(Priority::clamped(9), Priority::Backlog);
+        assert_eq!(Priority::from(200), Priority::Backlog);
+        assert_eq!(Priority::Backlog.level(), Priority::MAX_LEVEL);
+
+        let mut big = bead("bd-big", BeadStatus::Open);
+        big.priority = 7;
+        assert_eq!(big.priority_label(), "P4");
+    }
+}
@@ -3,23 +3,44 @@
 #![allow(dead_code)]
 
 use anyhow::{Context, Result};
-use std::process::Command;
+use std::path::PathBuf;
+use std::process::Command as StdCommand;
+use tokio::process::Command;
+use tokio::sync::mpsc;
 
 use super::BeadType;
 
 /// CLI wrapper for the br command
-pub struct BrCli;
+#[derive(Debug, Clone)]
+pub struct BrCli {
+    /// Project directory br runs in, the one holding `.beads`; br finds the
+    /// database from there
+    dir: PathBuf,
+}
 
 impl BrCli {
+    /// br for the project in `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// A `br` command run from the project directory
+    fn command(&self) -> Command {
+        let mut cmd = Command::new("br");
+        cmd.current_dir(&self.dir);
+        cmd
+    }
+
     /// Create a new bead
-    pub fn create(
+    pub async fn create(
+        &self,
         title: &str,
         bead_type: BeadType,
         priority: u8,
         description: Option<&str>,
         parent_id: Option<&str>,
     ) -> Result<String> {
-        let mut cmd = Command::new("br");
+        let mut cmd = self.command();
         cmd.arg("create")
             .arg(format!("--title={}", title))
             .arg("--type")
@@ -33,6 +54,7 @@ impl BrCli {
 
         let output = cmd
             .output()
+            .await
             .context("Failed to execute br create command")?;
 
         if !output.status.success() {
@@ -61,20 +83,22 @@ impl BrCli {
         if let Some(pid) = parent_id
             && !id.is_empty()
         {
-            Self::add_dependency(&id, pid, "parent-child")?;
+            self.add_dependency(&id, pid, "parent-child").await?;
         }
 
         Ok(id)
     }
 
     /// Update a bead's status
-    pub fn update_status(id: &str, status: &str) -> Result<()> {
-        let output = Command::new("br")
+    pub async fn update_status(&self, id: &str, status: &str) -> Result<()> {
+        let output = self
+            .command()
             .arg("update")
             .arg(id)
             .arg("--status")
             .arg(status)
             .output()
+            .await
             .context("Failed to execute br update command")?;
 
         if !output.status.success() {
@@ -86,15 +110,18 @@ impl BrCli {
     }
 
     /// Close a bead
-    pub fn close(id: &str, reason: Option<&str>) -> Result<()> {
-        let mut cmd = Command::new("br");
+    pub async fn close(&self, id: &str, reason: Option<&str>) -> Result<()> {
+        let mut cmd = self.command();
         cmd.arg("close").arg(id);
 
         if let Some(r) = reason {
             cmd.arg(format!("--reason={}", r));
         }
 
-        let output = cmd.output().context("Failed to execute br close command")?;
+        let output = cmd
+            .output()
+            .await
+            .context("Failed to execute br close command")?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -105,8 +132,9 @@ impl BrCli {
     }
 
     /// Add a dependency between beads
-    pub fn add_dependency(from_id: &str, to_id: &str, dep_type: &str) -> Result<()> {
-        let output = Command::new("br")
+    pub async fn add_dependency(&self, from_id: &str, to_id: &str, dep_type: &str) -> Result<()> {
+        let output = self
+            .command()
             .arg("dep")
             .arg("add")
             .arg(from_id)
@@ -114,6 +142,7 @@ impl BrCli {
             .arg("--type")
             .arg(dep_type)
             .output()
+            .await
             .context("Failed to execute br dep add command")?;
 
         if !output.status.success() {
@@ -124,16 +153,38 @@ impl BrCli {
         Ok(())
     }
 
-    /// Update a generic field on a bead (title, description, type, priority)
-    pub fn update_field(id: &str, field: &str, value: &str) -> Result<()> {
+    /// Remove a dependency between beads
+    pub async fn remove_dependency(&self, from_id: &str, to_id: &str) -> Result<()> {
+        let output = self
+            .command()
+            .arg("dep")
+            .arg("remove")
+            .arg(from_id)
+            .arg(to_id)
+            .output()
+            .await
+            .context("Failed to execute br dep remove command")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("br dep remove failed: {}", stderr);
+        }
+
+        Ok(())
+    }
+
+    /// Update a generic field on a bead (title, description, type, priority, assignee)
+    pub async fn update_field(&self, id: &str, field: &str, value: &str) -> Result<()> {
         let flag = format!("--{}", field);
         let arg = format!("--{}={}", field, value);
 
-        let output = Command::new("br")
+        let output = self
+            .command()
             .arg("update")
             .arg(id)
             .arg(arg)
             .output()
+            .await
             .context(format!("Failed to execute br update {} command", flag))?;
 
         if !output.status.success() {
@@ -144,13 +195,48 @@ impl BrCli {
         Ok(())
     }
 
+    /// Update several fields and labels of a bead in one `br update` call
+    pub async fn update(
+        &self,
+        id: &str,
+        fields: &[(&str, String)],
+        add_labels: &[String],
+        remove_labels: &[String],
+    ) -> Result<()> {
+        let mut cmd = self.command();
+        cmd.arg("update").arg(id);
+        for (field, value) in fields {
+            cmd.arg(format!("--{}={}", field, value));
+        }
+        for label in add_labels {
+            cmd.arg(format!("--add-label={}", label));
+        }
+        for label in remove_labels {
+            cmd.arg(format!("--remove-label={}", label));
+        }
+
+        let output = cmd
+            .output()
+            .await
+            .context("Failed to execute br update command")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("br update failed: {}", stderr);
+        }
+
+        Ok(())
+    }
+
     /// Add a label to a bead
-    pub fn add_label(id: &str, label: &str) -> Result<()> {
-        let output = Command::new("br")
+    pub async fn add_label(&self, id: &str, label: &str) -> Result<()> {
+        let output = self
+            .command()
             .arg("update")
             .arg(id)
             .arg(format!("--add-label={}", label))
             .output()
+            .await
             .context("Failed to execute br update --add-label command")?;
 
         if !output.status.success() {
@@ -162,12 +248,14 @@ impl BrCli {
     }
 
     /// Remove a label from a bead
-    pub fn remove_label(id: &str, label: &str) -> Result<()> {
-        let output = Command::new("br")
+    pub async fn remove_label(&self, id: &str, label: &str) -> Result<()> {
+        let output = self
+            .command()
             .arg("update")
             .arg(id)
             .arg(format!("--remove-label={}", label))
             .output()
+            .await
             .context("Failed to execute br update --remove-label command")?;
 
         if !output.status.success() {
@@ -179,14 +267,16 @@ impl BrCli {
     }
 
     /// Add a comment to a bead
-    pub fn add_comment(id: &str, comment: &str) -> Result<()> {
-        let output = Command::new("br")
+    pub async fn add_comment(&self, id: &str, comment: &str) -> Result<()> {
+        let output = self
+            .command()
             .arg("comments")
             .arg("add")
             .arg(id)
             .arg("--")
             .arg(comment)
             .output()
+            .await
             .context("Failed to execute br comments add command")?;
 
         if !output.status.success() {
@@ -198,10 +288,12 @@ impl BrCli {
     }
 
     /// Run `br sync` to rebuild/export state (including SQLite DB)
-    pub fn sync() -> Result<()> {
-        let output = Command::new("br")
+    pub async fn sync(&self) -> Result<()> {
+        let output = self
+            .command()
             .arg("sync")
             .output()
+            .await
             .context("Failed to execute br sync command")?;
 
         if !output.status.success() {
@@ -214,10 +306,206 @@ impl BrCli {
 
     /// Check if br CLI is available
     pub fn is_available() -> bool {
-        Command::new("br")
+        StdCommand::new("br")
             .arg("--version")
             .output()
             .map(|o| o.status.success())
             .unwrap_or(false)
     }
 }
+
+/// A single br command, queued to run in the background
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BrOp {
+    /// Create a bead, then add its labels (best-effort, like the form always has)
+    Create {
+        title: String,
+        bead_type: BeadType,
+        priority: u8,
+        description: Option<String>,
+        parent: Option<String>,
+        labels: Vec<String>,
+    },
+    UpdateStatus {
+        id: String,
+        status: String,
+    },
+    Close {
+        id: String,
+        reason: Option<String>,
+    },
+    UpdateField {
+        id: String,
+        field: &'static str,
+        value: String,
+    },
+    /// Several fields and labels at once
+    Update {
+        id: String,
+        fields: Vec<(&'static str, String)>,
+        add_labels: Vec<String>,
+        remove_labels: Vec<String>,
+    },
+    AddLabel {
+        id: String,
+        label: String,
+    },
+    RemoveLabel {
+        id: String,
+        label: String,
+    },
+    AddDependency {
+        from: String,
+        to: String,
+        dep_type: String,
+    },
+    RemoveDependency {
+        from: String,
+        to: String,
+    },
+    AddComment {
+        id: String,
+        text: String,
+    },
+}
+
+impl BrOp {
+    /// Run the command. Returns the new bead's ID for `Create`, otherwise empty.
+    pub async fn run(&self, br: &BrCli) -> Result<String> {
+        match self {
+            BrOp::Create {
+                title,
+                bead_type,
+                priority,
+                description,
+                parent,
+                labels,
+            } => {
+                let id = br
+                    .create(
+                        title,
+                        *bead_type,
+                        *priority,
+                        description.as_deref(),
+                        parent.as_deref(),
+                    )
+                    .await?;
+                // All the labels in one update
+                if !id.is_empty() && !labels.is_empty() {
+                    br.update(&id, &[], labels, &[])
+                        .await
+                        .with_context(|| format!("Created {}, but couldn't add its labels", id))?;
+                }
+                return Ok(id);
+            }
+            BrOp::UpdateStatus { id, status } => br.update_status(id, status).await?,
+            BrOp::Close { id, reason } => br.close(id, reason.as_deref()).await?,
+            BrOp::UpdateField { id, field, value } => br.update_field(id, field, value).await?,
+            BrOp::Update {
+                id,
+                fields,
+                add_labels,
+                remove_labels,
+            } => br.update(id, fields, add_labels, remove_labels).await?,
+            BrOp::AddLabel { id, label } => br.add_label(id, label).await?,
+            BrOp::RemoveLabel { id, label } => br.remove_label(id, label).await?,
+            BrOp::AddDependency { from, to, dep_type } => {
+                br.add_dependency(from, to, dep_type).await?
+            }
+            BrOp::RemoveDependency { from, to } => br.remove_dependency(from, to).await?,
+            BrOp::AddComment { id, text } => br.add_comment(id, text).await?,
+        }
+        Ok(String::new())
+    }
+
+    /// Bead the command changes (None for `Create`, whose bead doesn't exist yet)
+    pub fn bead_id(&self) -> Option<&str> {
+        match self {
+            BrOp::Create { .. } => None,
+            BrOp::UpdateStatus { id, .. }
+            | BrOp::Close { id, .. }
+            | BrOp::UpdateField { id, .. }
+            | BrOp::Update { id, .. }
+            | BrOp::AddLabel { id, .. }
+            | BrOp::RemoveLabel { id, .. }
+            | BrOp::AddComment { id, .. } => Some(id),
+            BrOp::AddDependency { from, .. } | BrOp::RemoveDependency { from, .. } => Some(from),
+        }
+    }
+}
+
+/// What a batch does when one of its commands fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnFailure {
+    /// Skip the rest, since later commands depend on earlier ones (e.g. the
+    /// steps of one edit)
+    Stop,
+    /// Run the rest anyway, since each stands alone (e.g. the same change to
+    /// every bead in a selection)
+    Continue,
+}
+
+/// Runs batches of br commands in the background, one batch after another in
+/// the order they were sent, so the UI keeps responding while they run
+pub struct BrQueue {
+    batches: mpsc::UnboundedSender<(Vec<BrOp>, OnFailure)>,
+    finished: mpsc::UnboundedReceiver<Vec<Result<String>>>,
+    worker: tokio::task::JoinHandle<()>,
+    in_flight: usize,
+}
+
+impl BrQueue {
+    /// Start the worker task, running br from `br`'s project. Must be called
+    /// inside the tokio runtime.
+    pub fn start(br: BrCli) -> Self {
+        let (batches, mut todo) = mpsc::unbounded_channel::<(Vec<BrOp>, OnFailure)>();
+        let (done, finished) = mpsc::unbounded_channel();
+        let worker = tokio::spawn(async move {
+            while let Some((batch, on_failure)) = todo.recv().await {
+                let mut results = Vec::new();
+                for op in &batch {
+                    let result = op.run(&br).await;
+                    let failed = result.is_err();
+                    results.push(result);
+                    if failed && on_failure == OnFailure::Stop {
+                        break;
+                    }
+                }
+                let _ = done.send(results);
+            }
+        });
+        Self {
+            batches,
+            finished,
+            worker,
+            in_flight: 0,
+        }
+    }
+
+    /// Queue a batch of commands
+    pub fn send(&mut self, batch: Vec<BrOp>, on_failure: OnFailure) {
+        if self.batches.send((batch, on_failure)).is_ok() {
+            self.in_flight += 1;
+        }
+    }
+
+    /// Results of the next finished batch, if one is done: one per command
+    /// that ran. An `OnFailure::Stop` batch ends at its first failure; a
+    /// `Continue` one has a result for every command.
+    pub fn try_finished(&mut self) -> Option<Vec<Result<String>>> {
+        let results = self.finished.try_recv().ok()?;
+        self.in_flight -= 1;
+        Some(results)
+    }
+
+    /// Batches sent but not finished yet
+    pub fn in_flight(&self) -> usize {
+        self.in_flight
+    }
+
+    /// Wait for everything queued to finish, e.g. before quitting
+    pub async fn drain(self) {
+        drop(self.batches);
+        let _ = self.worker.await;
+    }
+}
@@ -6,7 +6,14 @@
 mod bead;
 mod br;
 mod sqlite;
+mod watch;
 
-pub use bead::{Bead, BeadStatus, BeadType, Comment, DependencyType, build_tree_order};
-pub use br::BrCli;
-pub use sqlite::BeadStore;
+pub use bead::{
+    Bead, BeadStats, BeadStatus, BeadType, Comment, DependencyType, GroupBy, LabelMatch, Priority,
+    Recency, SortMode, TreeOptions, TreeRow, bead_stats, build_tree_order, build_tree_rows,
+    filter_match_indices, filter_regex, find_cycles, label_counts, not_closed_ids,
+    open_descendants,
+};
+pub use br::{BrCli, BrOp, BrQueue, OnFailure};
+pub use sqlite::{BeadStore, is_busy};
+pub use watch::DbWatcher;
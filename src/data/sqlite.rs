@@ -3,14 +3,31 @@
 #![allow(dead_code)]
 
 use anyhow::{Context, Result};
-use rusqlite::{Connection, OpenFlags};
-use std::path::Path;
+use rusqlite::{Connection, ErrorCode, OpenFlags};
+use std::collections::HashMap;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
 
-use super::{Bead, BeadStatus, BeadType, Comment, DependencyType};
+use super::{Bead, BeadStatus, BeadType, Comment, DependencyType, Priority};
+
+/// How long SQLite waits on a lock held by a writer (e.g. `br`) before a
+/// query gives up. Loads run on the UI's thread, so this is also the longest
+/// a locked database can hold up a frame; the next refresh tries again.
+const BUSY_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Further attempts [`BeadStore::load_all_retrying`] makes while the database
+/// stays locked, with the wait before the first one (doubling after that)
+const BUSY_RETRIES: u32 = 3;
+const BUSY_BACKOFF: Duration = Duration::from_millis(100);
 
 /// A store that reads beads from SQLite
 pub struct BeadStore {
     conn: Connection,
+    path: PathBuf,
+    /// (device, inode) of the database file when it was opened
+    file_id: Option<(u64, u64)>,
 }
 
 impl BeadStore {
@@ -21,50 +38,74 @@ impl BeadStore {
             OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
         )
         .with_context(|| format!("Failed to open database: {:?}", path.as_ref()))?;
+        conn.busy_timeout(BUSY_TIMEOUT)
+            .context("Failed to set the database busy timeout")?;
+
+        Ok(Self {
+            conn,
+            path: path.as_ref().to_path_buf(),
+            file_id: file_id(path.as_ref()),
+        })
+    }
 
-        Ok(Self { conn })
+    /// Counter that changes whenever another connection commits to the database
+    pub fn data_version(&self) -> Result<i64> {
+        self.conn
+            .query_row("PRAGMA data_version", [], |row| row.get(0))
+            .context("Failed to read database data_version")
+    }
+
+    /// Whether the database file was replaced (e.g. rebuilt by `br sync`) since
+    /// it was opened, in which case this connection still sees the old file
+    pub fn file_replaced(&self) -> bool {
+        file_id(&self.path) != self.file_id
     }
 
     /// Load all beads from the database
     pub fn load_all(&self) -> Result<Vec<Bead>> {
-        let mut beads = self.load_beads()?;
-        let deps = self.load_dependencies()?;
-        let labels = self.load_labels()?;
-        let comments = self.load_comments()?;
+        let mut beads = explain_busy(self.load_beads())?;
+        let deps = explain_busy(self.load_dependencies())?;
+        let labels = explain_busy(self.load_labels())?;
+        let comments = explain_busy(self.load_comments())?;
+
+        // Index beads by ID so attaching related rows stays linear
+        let index: HashMap<String, usize> = beads
+            .iter()
+            .enumerate()
+            .map(|(idx, bead)| (bead.id.clone(), idx))
+            .collect();
 
         // Apply labels to beads
-        for bead in &mut beads {
-            for (issue_id, label) in &labels {
-                if issue_id == &bead.id {
-                    bead.labels.push(label.clone());
-                }
+        for (issue_id, label) in labels {
+            if let Some(&idx) = index.get(&issue_id) {
+                beads[idx].labels.push(label);
             }
         }
 
         // Apply comments to beads
-        for bead in &mut beads {
-            for (issue_id, comment) in &comments {
-                if issue_id == &bead.id {
-                    bead.comments.push(comment.clone());
-                }
+        for (issue_id, comment) in comments {
+            if let Some(&idx) = index.get(&issue_id) {
+                beads[idx].comments.push(comment);
             }
         }
 
         // Apply dependencies to beads
-        for bead in &mut beads {
-            for (from_id, to_id, dep_type) in &deps {
-                match dep_type {
-                    DependencyType::ParentChild if from_id == &bead.id => {
-                        bead.parent_ids.push(to_id.clone());
+        for (from_id, to_id, dep_type) in deps {
+            match dep_type {
+                DependencyType::ParentChild => {
+                    if let Some(&idx) = index.get(&from_id) {
+                        beads[idx].parent_ids.push(to_id);
                     }
-                    DependencyType::Blocks if from_id == &bead.id => {
-                        bead.blocked_by.push(to_id.clone());
+                }
+                DependencyType::Blocks => {
+                    if let Some(&idx) = index.get(&to_id) {
+                        beads[idx].blocks.push(from_id.clone());
                     }
-                    DependencyType::Blocks if to_id == &bead.id => {
-                        bead.blocks.push(from_id.clone());
+                    if let Some(&idx) = index.get(&from_id) {
+                        beads[idx].blocked_by.push(to_id);
                     }
-                    _ => {}
                 }
+                DependencyType::Related => {}
             }
         }
 
@@ -127,7 +168,7 @@ impl BeadStore {
                     id: row.get(0)?,
                     title: row.get(1)?,
                     status: row.get::<_, String>(2)?.parse().unwrap_or(BeadStatus::Open),
-                    priority: row.get::<_, i64>(3)? as u8,
+                    priority: Priority::clamped(row.get(3)?).level(),
                     bead_type: row.get::<_, String>(4)?.parse().unwrap_or(BeadType::Task),
                     description: row.get(5)?,
                     labels: Vec::new(), // Loaded separately from labels table
@@ -233,6 +274,23 @@ impl BeadStore {
         Ok(comments)
     }
 
+    /// Load all beads, trying again with a growing backoff while a writer
+    /// keeps the database locked. This sleeps, so it's for startup, before
+    /// the UI is drawn; refreshes use `load_all` and try again next time.
+    pub fn load_all_retrying(&self) -> Result<Vec<Bead>> {
+        let mut wait = BUSY_BACKOFF;
+        for _ in 0..BUSY_RETRIES {
+            match self.load_all() {
+                Err(e) if is_busy(&e) => {
+                    thread::sleep(wait);
+                    wait *= 2;
+                }
+                result => return result,
+            }
+        }
+        self.load_all()
+    }
+
     /// Get a single bead by ID
     pub fn get(&self, id: &str) -> Result<Option<Bead>> {
         let beads = self.load_all()?;
@@ -240,9 +298,68 @@ impl BeadStore {
     }
 }
 
+/// Say plainly that a query gave up because a writer kept the database
+/// locked for longer than [`BUSY_TIMEOUT`]
+fn explain_busy<T>(result: Result<T>) -> Result<T> {
+    result.map_err(|e| {
+        if is_busy(&e) {
+            e.context("The database stayed locked by another process")
+        } else {
+            e
+        }
+    })
+}
+
+/// Whether an error is SQLite reporting the database as busy or locked
+pub fn is_busy(error: &anyhow::Error) -> bool {
+    error.chain().any(|cause| {
+        cause
+            .downcast_ref::<rusqlite::Error>()
+            .and_then(rusqlite::Error::sqlite_error_code)
+            .is_some_and(|code| matches!(code, ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked))
+    })
+}
+
+/// Identity of a file on disk, used to notice when it is replaced
+fn file_id(path: &Path) -> Option<(u64, u64)> {
+    std::fs::metadata(path).ok().map(|m| (m.dev(), m.ino()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tempfile::TempDir;
+
+    /// Create a minimal beads database with the tables `BeadStore` reads.
+    fn test_db() -> (TempDir, std::path::PathBuf) {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("beads.db");
+        let conn = Connection::open(&path).unwrap();
+        conn.execute_batch(
+            r#"
+            CREATE TABLE issues (
+                id TEXT PRIMARY KEY,
+                title TEXT NOT NULL,
+                status TEXT NOT NULL,
+                priority INTEGER NOT NULL,
+                issue_type TEXT NOT NULL,
+                description TEXT,
+                created_by TEXT,
+                assignee TEXT,
+                created_at TEXT,
+                updated_at TEXT,
+                closed_at TEXT,
+                close_reason TEXT,
+                deleted_at TEXT
+            );
+            CREATE TABLE dependencies (issue_id TEXT, depends_on_id TEXT, type TEXT);
+            CREATE TABLE labels (issue_id TEXT, label TEXT);
+            CREATE TABLE comments (issue_id TEXT, author TEXT, text TEXT, created_at TEXT);
+            "#,
+        )
+        .unwrap();
+        (dir, path)
+    }
 
     #[test]
     fn test_status_parsing() {
@@ -261,4 +378,116 @@ mod tests {
         );
         assert_eq!("closed".parse::<BeadStatus>().unwrap(), BeadStatus::Closed);
     }
+
+    #[test]
+    fn load_all_attaches_comments_in_order() {
+        let (_dir, path) = test_db();
+        let conn = Connection::open(&path).unwrap();
+        conn.execute_batch(
+            r#"
+            INSERT INTO issues (id, title, status, priority, issue_type)
+                VALUES ('bd-1', 'First', 'open', 2, 'task'),
+                       ('bd-2', 'Second', 'open', 2, 'task');
+            INSERT INTO comments (issue_id, author, text, created_at) VALUES
+                ('bd-1', 'bob', 'later', '2026-01-02T10:00:00Z'),
+                ('bd-1', 'alice', 'earlier', '2026-01-01 09:30:00');
+            "#,
+        )
+        .unwrap();
+
+        let beads = BeadStore::open(&path).unwrap().load_all().unwrap();
+        let first = beads.iter().find(|b| b.id == "bd-1").unwrap();
+        let second = beads.iter().find(|b| b.id == "bd-2").unwrap();
+
+        let texts: Vec<_> = first.comments.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(texts, vec!["earlier", "later"]);
+        assert_eq!(first.comments[0].author, "alice");
+        assert!(first.comments.iter().all(|c| c.created_at.is_some()));
+        assert!(second.comments.is_empty());
+    }
+
+    #[test]
+    fn data_version_changes_after_external_write() {
+        let (_dir, path) = test_db();
+        let store = BeadStore::open(&path).unwrap();
+        let before = store.data_version().unwrap();
+        assert_eq!(store.data_version().unwrap(), before);
+
+        let writer = Connection::open(&path).unwrap();
+        writer
+            .execute(
+                "INSERT INTO issues (id, title, status, priority, issue_type)
+                     VALUES ('bd-1', 'New', 'open', 2, 'task')",
+                [],
+            )
+            .unwrap();
+
+        assert_ne!(store.data_version().unwrap(), before);
+        assert!(!store.file_replaced());
+    }
+
+    #[test]
+    fn load_all_waits_out_a_writer_only_for_the_busy_timeout() {
+        let (_dir, path) = test_db();
+        let store = BeadStore::open(&path).unwrap();
+
+        // A writer with an exclusive lock blocks readers until it commits
+        let writer = Connection::open(&path).unwrap();
+        writer
+            .execute_batch(
+                "BEGIN EXCLUSIVE;
+                 INSERT INTO issues (id, title, status, priority, issue_type)
+                     VALUES ('bd-1', 'Locked in', 'open', 2, 'task');",
+            )
+            .unwrap();
+
+        // Held for longer than the busy timeout, the load gives up
+        let err = store.load_all().unwrap_err();
+        assert!(err.to_string().contains("stayed locked"), "{:#}", err);
+
+        // Committed within it, the load sees the new row
+        let commit = std::thread::spawn(move || {
+            std::thread::sleep(BUSY_TIMEOUT / 2);
+            writer.execute_batch("COMMIT").unwrap();
+        });
+        let beads = store.load_all().unwrap();
+        commit.join().unwrap();
+        assert_eq!(beads.len(), 1);
+    }
+
+    #[test]
+    fn load_all_retrying_outlasts_a_longer_lock() {
+        let (_dir, path) = test_db();
+        let store = BeadStore::open(&path).unwrap();
+
+        let writer = Connection::open(&path).unwrap();
+        writer
+            .execute_batch(
+                "BEGIN EXCLUSIVE;
+                 INSERT INTO issues (id, title, status, priority, issue_type)
+                     VALUES ('bd-1', 'Locked in', 'open', 2, 'task');",
+            )
+            .unwrap();
+        // Longer than one busy timeout, well within the retries
+        let commit = thread::spawn(move || {
+            thread::sleep(BUSY_TIMEOUT + Duration::from_millis(200));
+            writer.execute_batch("COMMIT").unwrap();
+        });
+
+        let beads = store.load_all_retrying().unwrap();
+        commit.join().unwrap();
+        assert_eq!(beads.len(), 1);
+    }
+
+    #[test]
+    fn file_replaced_detects_rebuilt_database() {
+        let (dir, path) = test_db();
+        let store = BeadStore::open(&path).unwrap();
+
+        let rebuilt = dir.path().join("rebuilt.db");
+        std::fs::copy(&path, &rebuilt).unwrap();
+        std::fs::rename(&rebuilt, &path).unwrap();
+
+        assert!(store.file_replaced());
+    }
 }
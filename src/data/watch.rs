@@ -0,0 +1,118 @@
+//! Filesystem watcher for the beads database
+
+use anyhow::{Context, Result};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{Receiver, channel};
+use std::time::{Duration, Instant};
+
+/// Quiet period after the last write before reporting a change, so bursts
+/// (a transaction plus its WAL checkpoint) trigger a single reload
+const DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Watches the database file (and its `-wal`/`-journal` siblings) for writes
+pub struct DbWatcher {
+    _watcher: RecommendedWatcher,
+    events: Receiver<()>,
+    last_event: Option<Instant>,
+}
+
+impl DbWatcher {
+    /// Start watching the directory holding `db_path`.
+    ///
+    /// The directory is watched rather than the file so replacing the
+    /// database (e.g. `br sync` rebuilding it) is still noticed.
+    pub fn start(db_path: &Path) -> Result<Self> {
+        let dir = match db_path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        let db_name = db_path
+            .file_name()
+            .context("Database path has no file name")?
+            .to_string_lossy()
+            .into_owned();
+
+        let (tx, events) = channel();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            let Ok(event) = res else { return };
+            if event.kind.is_access() {
+                return;
+            }
+            let touches_db = event.paths.iter().any(|p| {
+                p.file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with(&db_name))
+            });
+            if touches_db {
+                let _ = tx.send(());
+            }
+        })
+        .context("Failed to create database watcher")?;
+        watcher
+            .watch(&dir, RecursiveMode::NonRecursive)
+            .with_context(|| format!("Failed to watch {:?}", dir))?;
+
+        Ok(Self {
+            _watcher: watcher,
+            events,
+            last_event: None,
+        })
+    }
+
+    /// Returns true once the database has changed and writes have settled
+    pub fn poll_changed(&mut self) -> bool {
+        while self.events.try_recv().is_ok() {
+            self.last_event = Some(Instant::now());
+        }
+        match self.last_event {
+            Some(at) if at.elapsed() >= DEBOUNCE => {
+                self.last_event = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn wait_for_change(watcher: &mut DbWatcher) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Instant::now() < deadline {
+            if watcher.poll_changed() {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        false
+    }
+
+    #[test]
+    fn reports_database_writes_once_settled() {
+        let dir = TempDir::new().unwrap();
+        let db = dir.path().join("beads.db");
+        std::fs::write(&db, "v1").unwrap();
+        let mut watcher = DbWatcher::start(&db).unwrap();
+
+        // A burst of writes to the db and its WAL collapses into one change
+        std::fs::write(&db, "v2").unwrap();
+        std::fs::write(dir.path().join("beads.db-wal"), "wal").unwrap();
+        assert!(wait_for_change(&mut watcher));
+        assert!(!watcher.poll_changed());
+    }
+
+    #[test]
+    fn ignores_unrelated_files() {
+        let dir = TempDir::new().unwrap();
+        let db = dir.path().join("beads.db");
+        std::fs::write(&db, "v1").unwrap();
+        let mut watcher = DbWatcher::start(&db).unwrap();
+
+        std::fs::write(dir.path().join("issues.jsonl"), "{}").unwrap();
+        std::thread::sleep(DEBOUNCE * 2);
+        assert!(!watcher.poll_changed());
+    }
+}
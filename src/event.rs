@@ -32,6 +32,16 @@ pub fn is_quit(key: &KeyEvent) -> bool {
     )
 }
 
+/// Check if a key is Shift+Tab. Most terminals (and tmux) send `BackTab`,
+/// some with Shift set as well; others report `Tab` with Shift.
+pub fn is_back_tab(key: &KeyEvent) -> bool {
+    match key.code {
+        KeyCode::BackTab => true,
+        KeyCode::Tab => key.modifiers.contains(KeyModifiers::SHIFT),
+        _ => false,
+    }
+}
+
 /// Check if a key is navigation up
 pub fn is_up(key: &KeyEvent) -> bool {
     matches!(key.code, KeyCode::Up | KeyCode::Char('k'))
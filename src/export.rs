@@ -0,0 +1,178 @@
+//! Export beads as Markdown or plain text
+
+use crate::data::{Bead, BeadStatus};
+
+/// Timestamp format for plain-text exports (matches the detail pane)
+const TIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// Default file name for exports, relative to the working directory
+pub const DEFAULT_EXPORT_PATH: &str = "beads-export.md";
+
+/// Render tree-ordered beads as a nested Markdown checklist.
+///
+/// Closed beads are checked; other non-open statuses are noted after the title.
+pub fn to_markdown(rows: &[(&Bead, usize)]) -> String {
+    let mut out = String::new();
+    for (bead, depth) in rows {
+        let check = if bead.status == BeadStatus::Closed {
+            "x"
+        } else {
+            " "
+        };
+        out.push_str(&format!(
+            "{}- [{}] **{}** {} ({})",
+            "  ".repeat(*depth),
+            check,
+            bead.id,
+            bead.title,
+            bead.priority_label()
+        ));
+        if !matches!(bead.status, BeadStatus::Open | BeadStatus::Closed) {
+            out.push_str(&format!(" _{}_", bead.status.to_string().replace('_', " ")));
+        }
+        for label in &bead.labels {
+            out.push_str(&format!(" `{}`", label));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Format one bead's full detail, comments included, as plain text that
+/// reads well pasted into chat or a ticket
+pub fn bead_to_text(bead: &Bead) -> String {
+    let mut out = format!("{}: {}\n", bead.id, bead.title);
+    out.push_str(&format!(
+        "Status: {} | Type: {} | Priority: {}\n",
+        bead.status.to_string().replace('_', " "),
+        bead.bead_type,
+        bead.priority_label()
+    ));
+    if !bead.labels.is_empty() {
+        out.push_str(&format!("Labels: {}\n", bead.labels.join(", ")));
+    }
+    if let Some(ref assignee) = bead.assignee {
+        out.push_str(&format!("Assignee: {}\n", assignee));
+    }
+    if let Some(ref creator) = bead.created_by {
+        out.push_str(&format!("Created by: {}\n", creator));
+    }
+    for (heading, ids) in [
+        ("Blocked by", &bead.blocked_by),
+        ("Blocks", &bead.blocks),
+        ("Part of", &bead.parent_ids),
+    ] {
+        if !ids.is_empty() {
+            out.push_str(&format!("{}: {}\n", heading, ids.join(", ")));
+        }
+    }
+    if let Some(created) = bead.created_at {
+        out.push_str(&format!("Created: {}\n", created.format(TIME_FORMAT)));
+    }
+    if let Some(updated) = bead.updated_at {
+        out.push_str(&format!("Updated: {}\n", updated.format(TIME_FORMAT)));
+    }
+    if bead.status == BeadStatus::Closed {
+        let when = bead
+            .closed_at
+            .map(|c| c.format(TIME_FORMAT).to_string())
+            .unwrap_or_default();
+        out.push_str(format!("Closed: {}", when).trim_end());
+        out.push('\n');
+        if let Some(ref reason) = bead.close_reason {
+            for line in reason.lines() {
+                out.push_str(&format!("  {}\n", line));
+            }
+        }
+    }
+
+    if let Some(ref desc) = bead.description {
+        out.push_str("\nDescription:\n");
+        out.push_str(desc.trim_end());
+        out.push('\n');
+    }
+
+    if !bead.comments.is_empty() {
+        out.push_str(&format!("\nComments ({}):\n", bead.comments.len()));
+        for comment in &bead.comments {
+            match comment.created_at {
+                Some(ts) => out.push_str(&format!(
+                    "\n{}, {}:\n",
+                    comment.author,
+                    ts.format(TIME_FORMAT)
+                )),
+                None => out.push_str(&format!("\n{}:\n", comment.author)),
+            }
+            for line in comment.text.lines() {
+                out.push_str(&format!("  {}\n", line));
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bead(id: &str, title: &str, status: BeadStatus, priority: u8) -> Bead {
+        Bead {
+            id: id.to_string(),
+            title: title.to_string(),
+            status,
+            priority,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn text_export_includes_fields_description_and_comments() {
+        let mut b = bead("bd-7", "Fix login", BeadStatus::Blocked, 1);
+        b.labels = vec!["auth".to_string(), "web".to_string()];
+        b.assignee = Some("sam".to_string());
+        b.created_by = Some("ana".to_string());
+        b.blocked_by = vec!["bd-3".to_string()];
+        b.description = Some("Users get logged out.\n".to_string());
+        b.comments = vec![crate::data::Comment {
+            author: "kim".to_string(),
+            text: "Repro on Safari\nonly".to_string(),
+            created_at: None,
+        }];
+
+        assert_eq!(
+            bead_to_text(&b),
+            "bd-7: Fix login\n\
+             Status: blocked | Type: task | Priority: P1\n\
+             Labels: auth, web\n\
+             Assignee: sam\n\
+             Created by: ana\n\
+             Blocked by: bd-3\n\
+             \n\
+             Description:\n\
+             Users get logged out.\n\
+             \n\
+             Comments (1):\n\
+             \n\
+             kim:\n  \
+             Repro on Safari\n  \
+             only\n"
+        );
+    }
+
+    #[test]
+    fn markdown_nests_by_depth_and_checks_closed_beads() {
+        let mut parent = bead("bd-1", "Login flow", BeadStatus::InProgress, 1);
+        parent.labels = vec!["auth".to_string()];
+        let child = bead("bd-2", "Write tests", BeadStatus::Open, 2);
+        let done = bead("bd-3", "Old bug", BeadStatus::Closed, 0);
+
+        let md = to_markdown(&[(&parent, 0), (&child, 1), (&done, 0)]);
+
+        assert_eq!(
+            md,
+            "- [ ] **bd-1** Login flow (P1) _in progress_ `auth`\n  \
+             - [ ] **bd-2** Write tests (P2)\n\
+             - [x] **bd-3** Old bug (P0)\n"
+        );
+    }
+}
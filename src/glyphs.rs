@@ -0,0 +1,317 @@
+//! Glyphs for statuses, bead types and list markers
+//!
+//! The defaults are unicode shapes. `[glyphs]` in config.toml can switch to
+//! an ASCII-only profile for fonts that draw those as boxes, and override
+//! single glyphs on top of either profile. Without a configured profile,
+//! ASCII is picked when the locale isn't UTF-8. The set is picked once at
+//! startup.
+
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+use crate::data::{BeadStatus, BeadType};
+
+/// Base glyph set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Profile {
+    #[default]
+    Unicode,
+    /// Letters and punctuation only
+    Ascii,
+}
+
+impl Profile {
+    /// Profile the terminal can draw, judging by the locale and `TERM`
+    pub fn detect() -> Self {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let locale = var("LC_ALL")
+            .or_else(|| var("LC_CTYPE"))
+            .or_else(|| var("LANG"));
+        if supports_unicode(locale.as_deref(), var("TERM").as_deref()) {
+            Self::Unicode
+        } else {
+            Self::Ascii
+        }
+    }
+}
+
+/// Whether a terminal with this locale (the first of `LC_ALL`, `LC_CTYPE`
+/// and `LANG` that's set) and `TERM` can draw unicode. An unset locale says
+/// nothing either way, so only one naming another charset counts.
+fn supports_unicode(locale: Option<&str>, term: Option<&str>) -> bool {
+    // The Linux console and old hardware terminals have no shapes beyond ASCII
+    if matches!(term, Some("linux" | "dumb" | "vt100" | "vt220")) {
+        return false;
+    }
+    locale.is_none_or(|locale| {
+        let locale = locale.to_ascii_lowercase();
+        locale.contains("utf-8") || locale.contains("utf8")
+    })
+}
+
+/// `[glyphs]` in config.toml: a profile plus overrides. Types take a pair:
+/// the glyph while open, then while in progress.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GlyphConfig {
+    /// Unset picks one with [`Profile::detect`]
+    pub profile: Option<Profile>,
+    pub open: Option<String>,
+    pub in_progress: Option<String>,
+    pub blocked: Option<String>,
+    pub deferred: Option<String>,
+    pub closed: Option<String>,
+    pub task: Option<[String; 2]>,
+    pub bug: Option<[String; 2]>,
+    pub feature: Option<[String; 2]>,
+    pub epic: Option<[String; 2]>,
+    pub story: Option<[String; 2]>,
+    pub expanded: Option<String>,
+    pub collapsed: Option<String>,
+    pub cycle: Option<String>,
+    pub stale: Option<String>,
+    pub blocked_by: Option<String>,
+    pub recency: Option<[String; 3]>,
+    pub branch: Option<String>,
+    pub last_branch: Option<String>,
+    pub trunk: Option<String>,
+    pub rule: Option<String>,
+}
+
+/// The glyphs the UI is drawn with
+#[derive(Debug, Clone, PartialEq)]
+pub struct Glyphs {
+    /// Base set the overrides were applied to
+    pub profile: Profile,
+    pub open: String,
+    pub in_progress: String,
+    pub blocked: String,
+    pub deferred: String,
+    pub closed: String,
+    /// Each type's glyph while open, then while in progress
+    pub task: [String; 2],
+    pub bug: [String; 2],
+    pub feature: [String; 2],
+    pub epic: [String; 2],
+    pub story: [String; 2],
+    /// Fold markers for beads with children
+    pub expanded: String,
+    pub collapsed: String,
+    /// Bead in a dependency cycle
+    pub cycle: String,
+    /// Open bead with no updates for a while
+    pub stale: String,
+    /// Before a bead nested under its blocker rather than its parent
+    pub blocked_by: String,
+    /// Dots for a bead updated today, this week, or before that
+    pub recency: [String; 3],
+    /// Dependency tree connectors: a child with siblings below it, the last
+    /// child, and the line continuing past a child's own children
+    pub branch: String,
+    pub last_branch: String,
+    pub trunk: String,
+    /// Horizontal rule between comments and after group headers
+    pub rule: String,
+}
+
+fn pair(open: &str, in_progress: &str) -> [String; 2] {
+    [open.to_string(), in_progress.to_string()]
+}
+
+impl Glyphs {
+    /// Simple unicode shapes (no emojis)
+    pub fn unicode() -> Self {
+        Self {
+            profile: Profile::Unicode,
+            open: "\u{25cb}".to_string(),          // ○ open circle
+            in_progress: "\u{25cf}".to_string(),   // ● filled circle
+            blocked: "\u{25a0}".to_string(),       // ■ filled square
+            deferred: "\u{2744}".to_string(),      // ❄ snowflake
+            closed: "\u{2713}".to_string(),        // ✓ check mark
+            task: pair("\u{25b7}", "\u{25b6}"),    // ▷ ▶ right triangle
+            bug: pair("\u{2298}", "\u{25cf}"),     // ⊘ circled slash, ● filled circle
+            feature: pair("\u{2606}", "\u{2605}"), // ☆ ★ star
+            epic: pair("\u{25c7}", "\u{25c6}"),    // ◇ ◆ diamond
+            story: pair("\u{2630}", "\u{25e4}"),   // ☰ trigram, ◤ filled corner
+            expanded: "\u{25be}".to_string(),      // ▾
+            collapsed: "\u{25b8}".to_string(),     // ▸
+            cycle: "\u{21bb}".to_string(),         // ↻
+            stale: "\u{29d6}".to_string(),         // ⧖ hourglass
+            blocked_by: "\u{22a3}".to_string(),    // ⊣ left tack
+            recency: ["\u{25cf}", "\u{2022}", "\u{00b7}"].map(String::from), // ● • ·
+            branch: "\u{251c}\u{2500} ".to_string(), // ├─
+            last_branch: "\u{2514}\u{2500} ".to_string(), // └─
+            trunk: "\u{2502}  ".to_string(),       // │
+            rule: "\u{2500}".to_string(),          // ─
+        }
+    }
+
+    /// ASCII only: types are letters, lowercase while open and uppercase
+    /// once in progress
+    pub fn ascii() -> Self {
+        Self {
+            profile: Profile::Ascii,
+            open: "o".to_string(),
+            in_progress: "*".to_string(),
+            blocked: "#".to_string(),
+            deferred: "z".to_string(),
+            closed: "x".to_string(),
+            task: pair("t", "T"),
+            bug: pair("b", "B"),
+            feature: pair("f", "F"),
+            epic: pair("e", "E"),
+            story: pair("s", "S"),
+            expanded: "v".to_string(),
+            collapsed: ">".to_string(),
+            cycle: "@".to_string(),
+            stale: "~".to_string(),
+            blocked_by: "!".to_string(),
+            recency: ["*", "+", "."].map(String::from),
+            branch: "|- ".to_string(),
+            last_branch: "`- ".to_string(),
+            trunk: "|  ".to_string(),
+            rule: "-".to_string(),
+        }
+    }
+
+    /// The profile's glyphs with the configured overrides applied
+    pub fn from_config(config: &GlyphConfig) -> Self {
+        let mut glyphs = match config.profile.unwrap_or_else(Profile::detect) {
+            Profile::Unicode => Self::unicode(),
+            Profile::Ascii => Self::ascii(),
+        };
+        fn set<T: Clone>(slot: &mut T, value: &Option<T>) {
+            if let Some(value) = value {
+                *slot = value.clone();
+            }
+        }
+        set(&mut glyphs.open, &config.open);
+        set(&mut glyphs.in_progress, &config.in_progress);
+        set(&mut glyphs.blocked, &config.blocked);
+        set(&mut glyphs.deferred, &config.deferred);
+        set(&mut glyphs.closed, &config.closed);
+        set(&mut glyphs.task, &config.task);
+        set(&mut glyphs.bug, &config.bug);
+        set(&mut glyphs.feature, &config.feature);
+        set(&mut glyphs.epic, &config.epic);
+        set(&mut glyphs.story, &config.story);
+        set(&mut glyphs.expanded, &config.expanded);
+        set(&mut glyphs.collapsed, &config.collapsed);
+        set(&mut glyphs.cycle, &config.cycle);
+        set(&mut glyphs.stale, &config.stale);
+        set(&mut glyphs.blocked_by, &config.blocked_by);
+        set(&mut glyphs.recency, &config.recency);
+        set(&mut glyphs.branch, &config.branch);
+        set(&mut glyphs.last_branch, &config.last_branch);
+        set(&mut glyphs.trunk, &config.trunk);
+        set(&mut glyphs.rule, &config.rule);
+        glyphs
+    }
+
+    /// Glyph for a status
+    pub fn status(&self, status: BeadStatus) -> &str {
+        match status {
+            BeadStatus::Open => &self.open,
+            BeadStatus::InProgress => &self.in_progress,
+            BeadStatus::Blocked => &self.blocked,
+            BeadStatus::Deferred => &self.deferred,
+            BeadStatus::Closed => &self.closed,
+        }
+    }
+
+    /// A type's glyphs while open and while in progress
+    pub fn bead_type(&self, bead_type: BeadType) -> &[String; 2] {
+        match bead_type {
+            BeadType::Task => &self.task,
+            BeadType::Bug => &self.bug,
+            BeadType::Feature => &self.feature,
+            BeadType::Epic => &self.epic,
+            BeadType::Story => &self.story,
+        }
+    }
+}
+
+static GLYPHS: OnceLock<Glyphs> = OnceLock::new();
+
+/// Draw with `glyphs` from now on. Only the first call counts, so call it
+/// before anything is drawn.
+pub fn init(glyphs: Glyphs) {
+    let _ = GLYPHS.set(glyphs);
+}
+
+/// The glyphs in use: the configured set, or the unicode defaults
+pub fn get() -> &'static Glyphs {
+    GLYPHS.get_or_init(Glyphs::unicode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overrides_apply_on_top_of_the_profile() {
+        let config: GlyphConfig = toml::from_str(
+            r#"
+            profile = "ascii"
+            blocked = "!"
+            epic = ["E", "E*"]
+            "#,
+        )
+        .expect("parse glyphs");
+        let glyphs = Glyphs::from_config(&config);
+
+        assert_eq!(glyphs.status(BeadStatus::Blocked), "!");
+        assert_eq!(glyphs.status(BeadStatus::Closed), "x");
+        assert_eq!(glyphs.bead_type(BeadType::Epic), &pair("E", "E*"));
+        let unicode = GlyphConfig {
+            profile: Some(Profile::Unicode),
+            ..Default::default()
+        };
+        assert_eq!(Glyphs::from_config(&unicode), Glyphs::unicode());
+
+        // Nothing in the ASCII profile is outside ASCII
+        let ascii = Glyphs::ascii();
+        let mut all: Vec<&str> = BeadStatus::all().iter().map(|s| ascii.status(*s)).collect();
+        all.extend(
+            [
+                &ascii.expanded,
+                &ascii.collapsed,
+                &ascii.cycle,
+                &ascii.stale,
+                &ascii.blocked_by,
+                &ascii.branch,
+                &ascii.last_branch,
+                &ascii.trunk,
+                &ascii.rule,
+            ]
+            .map(String::as_str),
+        );
+        let types = [
+            &ascii.task,
+            &ascii.bug,
+            &ascii.feature,
+            &ascii.epic,
+            &ascii.story,
+        ];
+        all.extend(types.into_iter().flatten().map(String::as_str));
+        all.extend(ascii.recency.iter().map(String::as_str));
+        assert!(all.iter().all(|glyph| glyph.is_ascii()), "{:?}", all);
+
+        assert!(toml::from_str::<GlyphConfig>("profile = \"emoji\"").is_err());
+        assert!(toml::from_str::<GlyphConfig>("blockd = \"!\"").is_err());
+    }
+
+    #[test]
+    fn only_a_non_utf8_locale_or_a_bare_console_falls_back_to_ascii() {
+        assert!(supports_unicode(
+            Some("en_US.UTF-8"),
+            Some("xterm-256color")
+        ));
+        assert!(supports_unicode(Some("C.utf8"), None));
+        assert!(supports_unicode(None, Some("screen")));
+        assert!(!supports_unicode(Some("C"), Some("xterm")));
+        assert!(!supports_unicode(Some("en_US.ISO-8859-1"), None));
+        assert!(!supports_unicode(Some("en_US.UTF-8"), Some("linux")));
+    }
+}
@@ -0,0 +1,90 @@
+//! Recent search queries, recalled with Up/Down like shell history
+
+use std::collections::VecDeque;
+
+/// Queries kept; the oldest are dropped first
+const CAPACITY: usize = 50;
+
+/// Ring buffer of recent entries with a browsing cursor
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    /// Oldest first
+    entries: VecDeque<String>,
+    /// Entry being shown while browsing (None = back at the typed text)
+    pos: Option<usize>,
+    /// What was typed before browsing started, restored past the newest entry
+    stash: String,
+}
+
+impl History {
+    /// Remember `entry` as the newest, moving it up if it's already there
+    pub fn push(&mut self, entry: &str) {
+        self.pos = None;
+        let entry = entry.trim();
+        if entry.is_empty() {
+            return;
+        }
+        self.entries.retain(|e| e != entry);
+        if self.entries.len() == CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry.to_string());
+    }
+
+    /// Step to the next older entry. `current` is the typed text, kept for
+    /// when browsing comes back past the newest entry.
+    pub fn older(&mut self, current: &str) -> Option<&str> {
+        let pos = match self.pos {
+            None => {
+                self.stash = current.to_string();
+                self.entries.len().checked_sub(1)?
+            }
+            Some(pos) => pos.saturating_sub(1),
+        };
+        self.pos = Some(pos);
+        self.entries.get(pos).map(String::as_str)
+    }
+
+    /// Step to the next newer entry, or back to the typed text after the newest
+    pub fn newer(&mut self) -> Option<&str> {
+        let pos = self.pos?;
+        if pos + 1 < self.entries.len() {
+            self.pos = Some(pos + 1);
+            self.entries.get(pos + 1).map(String::as_str)
+        } else {
+            self.pos = None;
+            Some(&self.stash)
+        }
+    }
+
+    /// Stop browsing, e.g. once the recalled entry is edited
+    pub fn reset(&mut self) {
+        self.pos = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::History;
+
+    #[test]
+    fn browses_like_shell_history() {
+        let mut history = History::default();
+        assert_eq!(history.older("typed"), None);
+
+        history.push("bug");
+        history.push("  ");
+        history.push("@alice");
+        history.push("bug");
+
+        // Newest first, stopping at the oldest
+        assert_eq!(history.older("typed"), Some("bug"));
+        assert_eq!(history.older("bug"), Some("@alice"));
+        assert_eq!(history.older("@alice"), Some("@alice"));
+
+        // Back down to what was typed, then nothing further
+        assert_eq!(history.newer(), Some("bug"));
+        assert_eq!(history.newer(), Some("typed"));
+        assert_eq!(history.newer(), None);
+    }
+}
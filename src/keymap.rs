@@ -0,0 +1,466 @@
+//! Normal-mode key bindings
+//!
+//! Keys map to [`Action`]s, seeded with the built-in defaults and optionally
+//! rebound from `~/.config/bu/keys.toml`:
+//!
+//! ```toml
+//! down = ["j", "ctrl+n"]
+//! close = "X"
+//! ```
+//!
+//! Each entry replaces that action's default keys. A key can trigger several
+//! actions; the first one that applies in the current context wins (e.g. `c`
+//! toggles closed beads in the list but adds a comment in the detail pane).
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config;
+
+/// Something a normal-mode key can do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    ForceQuit,
+    Suspend,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    HalfPageUp,
+    HalfPageDown,
+    First,
+    Last,
+    VisualSelect,
+    ClearSelection,
+    ToggleCollapse,
+    OpenDetail,
+    CloseDetail,
+    SwitchFocus,
+    ShrinkList,
+    GrowList,
+    Search,
+    JumpToId,
+    ClearFilter,
+    ResetFilters,
+    AddBead,
+    Edit,
+    CycleTheme,
+    CycleSort,
+    ReverseSort,
+    CycleGroup,
+    ToggleFlat,
+    ToggleBoard,
+    ColumnLeft,
+    ColumnRight,
+    MoveLeft,
+    MoveRight,
+    PriorityFilter,
+    LabelFilter,
+    ToggleLabels,
+    ToggleAssignee,
+    ToggleRecency,
+    ToggleGraph,
+    Export,
+    WriteBead,
+    RunOpenCommand,
+    CopyId,
+    CopyIdTitle,
+    ToggleWrap,
+    CycleDensity,
+    SaveView,
+    PickView,
+    PickWorkspace,
+    ToggleRelativeTimes,
+    Undo,
+    Refresh,
+    RefreshFaster,
+    RefreshSlower,
+    ToggleAutoRefresh,
+    Stats,
+    Help,
+    Close,
+    AddLabel,
+    Assign,
+    AddDependency,
+    RemoveDependency,
+    ToggleInProgress,
+    ToggleDeferred,
+    SetPriority,
+    ToggleClosed,
+    CycleStatusFilter,
+    ToggleReady,
+    AddComment,
+}
+
+/// Every action with its config name and default keys, in the order actions
+/// sharing a key are tried
+const DEFAULTS: &[(Action, &str, &[&str])] = &[
+    (Action::Quit, "quit", &["q"]),
+    (Action::ForceQuit, "force-quit", &["Q", "ctrl+c"]),
+    (Action::Suspend, "suspend", &["ctrl+z"]),
+    (Action::Up, "up", &["up", "k"]),
+    (Action::Down, "down", &["down", "j"]),
+    (Action::PageUp, "page-up", &["u", "b", "ctrl+k", "pageup"]),
+    (
+        Action::PageDown,
+        "page-down",
+        &["d", "f", "ctrl+j", "pagedown"],
+    ),
+    (Action::HalfPageUp, "half-page-up", &["ctrl+u"]),
+    (Action::HalfPageDown, "half-page-down", &["ctrl+d"]),
+    (Action::First, "first", &["home", "g"]),
+    (Action::Last, "last", &["end", "G"]),
+    (Action::VisualSelect, "visual-select", &["V"]),
+    (Action::ClearSelection, "clear-selection", &["esc"]),
+    (Action::Close, "close", &["x"]),
+    (Action::AddLabel, "add-label", &["+"]),
+    (Action::ToggleCollapse, "toggle-collapse", &["space"]),
+    (Action::ColumnLeft, "column-left", &["h", "left"]),
+    (Action::ColumnRight, "column-right", &["l", "right"]),
+    (Action::MoveLeft, "move-left", &["H"]),
+    (Action::MoveRight, "move-right", &["L"]),
+    (Action::OpenDetail, "open-detail", &["enter", "l", "right"]),
+    (Action::CloseDetail, "close-detail", &["esc", "h", "left"]),
+    (Action::SwitchFocus, "switch-focus", &["tab", "shift+tab"]),
+    (Action::ShrinkList, "shrink-list", &["<"]),
+    (Action::GrowList, "grow-list", &[">"]),
+    (Action::Search, "search", &["/"]),
+    (Action::JumpToId, "jump-to-id", &[":"]),
+    (Action::ClearFilter, "clear-filter", &["esc"]),
+    (Action::ResetFilters, "reset-filters", &["ctrl+l"]),
+    (Action::AddBead, "add-bead", &["a"]),
+    (Action::Edit, "edit", &["e"]),
+    (Action::CycleTheme, "cycle-theme", &["t"]),
+    (Action::CycleSort, "cycle-sort", &["o"]),
+    (Action::ReverseSort, "reverse-sort", &["O"]),
+    (Action::CycleGroup, "cycle-group", &["="]),
+    (Action::ToggleFlat, "toggle-flat", &["F"]),
+    (Action::ToggleBoard, "toggle-board", &["K"]),
+    (Action::PriorityFilter, "priority-filter", &["P"]),
+    (Action::LabelFilter, "label-filter", &["#"]),
+    (Action::ToggleLabels, "toggle-labels", &["L"]),
+    (Action::ToggleAssignee, "toggle-assignee", &["A"]),
+    (Action::ToggleRecency, "toggle-recency", &["."]),
+    (Action::ToggleGraph, "toggle-graph", &["B"]),
+    (Action::Export, "export", &["E"]),
+    (Action::WriteBead, "write-bead", &["W"]),
+    (Action::RunOpenCommand, "run-open-command", &["!"]),
+    (Action::CopyId, "copy-id", &["y"]),
+    (Action::CopyIdTitle, "copy-id-title", &["Y"]),
+    (Action::ToggleWrap, "toggle-wrap", &["w"]),
+    (Action::CycleDensity, "cycle-density", &["z"]),
+    (Action::SaveView, "save-view", &["m"]),
+    (Action::PickView, "pick-view", &["'"]),
+    (Action::PickWorkspace, "pick-workspace", &["ctrl+w"]),
+    (Action::ToggleRelativeTimes, "toggle-relative-times", &["T"]),
+    (Action::Undo, "undo", &["U"]),
+    (Action::Refresh, "refresh", &["r"]),
+    (Action::RefreshFaster, "refresh-faster", &["["]),
+    (Action::RefreshSlower, "refresh-slower", &["]"]),
+    (
+        Action::ToggleAutoRefresh,
+        "toggle-auto-refresh",
+        &["ctrl+r"],
+    ),
+    (Action::Stats, "stats", &["S"]),
+    (Action::Help, "help", &["?"]),
+    (Action::Assign, "assign", &["@"]),
+    (Action::AddDependency, "add-dependency", &["+"]),
+    (Action::RemoveDependency, "remove-dependency", &["-"]),
+    (Action::ToggleInProgress, "toggle-in-progress", &["i"]),
+    (Action::SetPriority, "set-priority", &["p"]),
+    (Action::ToggleDeferred, "toggle-deferred", &["D"]),
+    (Action::ToggleClosed, "toggle-closed", &["c"]),
+    (Action::CycleStatusFilter, "cycle-status-filter", &["s"]),
+    (Action::ToggleReady, "toggle-ready", &["R"]),
+    (Action::AddComment, "add-comment", &["c"]),
+];
+
+impl Action {
+    /// Whether this action changes beads via `br` (refused in read-only mode)
+    pub fn changes_beads(self) -> bool {
+        matches!(
+            self,
+            Action::AddBead
+                | Action::Edit
+                | Action::Undo
+                | Action::Close
+                | Action::AddLabel
+                | Action::Assign
+                | Action::AddDependency
+                | Action::RemoveDependency
+                | Action::ToggleInProgress
+                | Action::ToggleDeferred
+                | Action::SetPriority
+                | Action::AddComment
+                | Action::MoveLeft
+                | Action::MoveRight
+        )
+    }
+}
+
+/// A key press with the modifiers that matter for bindings
+type Binding = (KeyCode, KeyModifiers);
+
+/// Normalize a key event so lookups don't depend on how the terminal reports
+/// Shift (it's already part of `'G'` or `BackTab`, and some terminals send
+/// Shift+Tab as `Tab` with Shift)
+fn binding(code: KeyCode, modifiers: KeyModifiers) -> Binding {
+    let mut modifiers =
+        modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT);
+    let code = match code {
+        KeyCode::Tab if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+        code => code,
+    };
+    if matches!(code, KeyCode::Char(_) | KeyCode::BackTab) {
+        modifiers.remove(KeyModifiers::SHIFT);
+    }
+    (code, modifiers)
+}
+
+/// Parse a key like `"j"`, `"G"`, `"ctrl+n"`, `"esc"` or `"shift+tab"`
+pub fn parse_key(text: &str) -> Option<Binding> {
+    let mut rest = text;
+    let mut modifiers = KeyModifiers::NONE;
+    loop {
+        let lower = rest.to_ascii_lowercase();
+        let (modifier, len) = if lower.starts_with("ctrl+") && rest.len() > 5 {
+            (KeyModifiers::CONTROL, 5)
+        } else if lower.starts_with("alt+") && rest.len() > 4 {
+            (KeyModifiers::ALT, 4)
+        } else if lower.starts_with("shift+") && rest.len() > 6 {
+            (KeyModifiers::SHIFT, 6)
+        } else {
+            break;
+        };
+        modifiers |= modifier;
+        rest = &rest[len..];
+    }
+
+    let mut chars = rest.chars();
+    let code = match (chars.next(), chars.next()) {
+        (Some(c), None) if modifiers.contains(KeyModifiers::SHIFT) => {
+            KeyCode::Char(c.to_ascii_uppercase())
+        }
+        (Some(c), None) => KeyCode::Char(c),
+        _ => match rest.to_ascii_lowercase().as_str() {
+            "esc" | "escape" => KeyCode::Esc,
+            "enter" | "return" => KeyCode::Enter,
+            "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "space" => KeyCode::Char(' '),
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            name => match name.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                Some(n @ 1..=12) => KeyCode::F(n),
+                _ => return None,
+            },
+        },
+    };
+    Some(binding(code, modifiers))
+}
+
+/// Lookup from key presses to actions
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: HashMap<Binding, Vec<Action>>,
+    /// Problems with keys.toml, reported once the UI is up
+    warnings: Vec<String>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::with_overrides(&toml::Table::new())
+    }
+}
+
+impl Keymap {
+    /// Default location of the key bindings file
+    pub fn path() -> Option<PathBuf> {
+        config::config_dir().map(|dir| dir.join("keys.toml"))
+    }
+
+    /// Load bindings from the default location. Anything that can't be used
+    /// is skipped with a warning instead of failing.
+    pub fn load() -> Self {
+        match Self::path() {
+            Some(path) if path.exists() => Self::load_from(&path),
+            _ => Self::default(),
+        }
+    }
+
+    /// Load bindings from a file
+    pub fn load_from(path: &Path) -> Self {
+        let table = fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| {
+                text.parse::<toml::Table>()
+                    .map_err(|e| e.message().to_string())
+            });
+        match table {
+            Ok(table) => Self::with_overrides(&table),
+            Err(e) => Self {
+                warnings: vec![format!("{}: {}", path.display(), e)],
+                ..Self::default()
+            },
+        }
+    }
+
+    /// Build the keymap from the defaults with `action = "key"` or
+    /// `action = ["key", ...]` overrides
+    fn with_overrides(overrides: &toml::Table) -> Self {
+        let mut warnings = Vec::new();
+        for name in overrides.keys() {
+            if !DEFAULTS.iter().any(|(_, known, _)| known == name) {
+                warnings.push(format!("unknown action {:?}", name));
+            }
+        }
+
+        let mut bindings: HashMap<Binding, Vec<Action>> = HashMap::new();
+        for &(action, name, defaults) in DEFAULTS {
+            let keys: Vec<&str> = match overrides.get(name) {
+                None => defaults.to_vec(),
+                Some(toml::Value::String(key)) => vec![key.as_str()],
+                Some(toml::Value::Array(keys)) if keys.iter().all(|k| k.is_str()) => {
+                    keys.iter().filter_map(|k| k.as_str()).collect()
+                }
+                Some(_) => {
+                    warnings.push(format!("{}: expected a key or list of keys", name));
+                    defaults.to_vec()
+                }
+            };
+            for key in keys {
+                match parse_key(key) {
+                    Some(binding) => bindings.entry(binding).or_default().push(action),
+                    None => warnings.push(format!("{}: unknown key {:?}", name, key)),
+                }
+            }
+        }
+        Self { bindings, warnings }
+    }
+
+    /// Problems found while loading keys.toml
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// The actions bound to a key press, in the order to try them
+    pub fn actions(&self, key: KeyEvent) -> &[Action] {
+        self.bindings
+            .get(&binding(key.code, key.modifiers))
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn parses_keys_with_modifiers() {
+        assert_eq!(
+            parse_key("j"),
+            Some((KeyCode::Char('j'), KeyModifiers::NONE))
+        );
+        assert_eq!(
+            parse_key("G"),
+            Some((KeyCode::Char('G'), KeyModifiers::NONE))
+        );
+        assert_eq!(parse_key("shift+g"), parse_key("G"));
+        assert_eq!(
+            parse_key("Ctrl+n"),
+            Some((KeyCode::Char('n'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(
+            parse_key("ctrl++"),
+            Some((KeyCode::Char('+'), KeyModifiers::CONTROL))
+        );
+        assert_eq!(
+            parse_key("shift+tab"),
+            Some((KeyCode::BackTab, KeyModifiers::NONE))
+        );
+        assert_eq!(parse_key("f5"), Some((KeyCode::F(5), KeyModifiers::NONE)));
+        assert_eq!(parse_key("hyper+x"), None);
+        assert_eq!(parse_key(""), None);
+    }
+
+    #[test]
+    fn defaults_try_context_actions_in_order() {
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap.actions(key(KeyCode::Char('c'), KeyModifiers::NONE)),
+            &[Action::ToggleClosed, Action::AddComment]
+        );
+        assert_eq!(
+            keymap.actions(key(KeyCode::Esc, KeyModifiers::NONE)),
+            &[
+                Action::ClearSelection,
+                Action::CloseDetail,
+                Action::ClearFilter
+            ]
+        );
+        // Terminals may or may not report Shift for capital letters
+        assert_eq!(
+            keymap.actions(key(KeyCode::Char('G'), KeyModifiers::SHIFT)),
+            &[Action::Last]
+        );
+        assert!(
+            keymap.actions(key(KeyCode::Char('j'), KeyModifiers::CONTROL)) == [Action::PageDown]
+        );
+        // Shift+Tab arrives as BackTab (with or without Shift) or as Tab with Shift
+        for (code, modifiers) in [
+            (KeyCode::BackTab, KeyModifiers::NONE),
+            (KeyCode::BackTab, KeyModifiers::SHIFT),
+            (KeyCode::Tab, KeyModifiers::SHIFT),
+        ] {
+            assert_eq!(keymap.actions(key(code, modifiers)), &[Action::SwitchFocus]);
+        }
+    }
+
+    #[test]
+    fn overrides_replace_defaults_and_warn_on_unknowns() {
+        let table: toml::Table = r#"
+            down = ["ctrl+n", "J"]
+            close = "X"
+            launch-rockets = "!"
+            up = "hyper+k"
+        "#
+        .parse()
+        .expect("valid toml");
+        let keymap = Keymap::with_overrides(&table);
+
+        let actions = |code| keymap.actions(key(code, KeyModifiers::NONE));
+        assert_eq!(
+            keymap.actions(key(KeyCode::Char('n'), KeyModifiers::CONTROL)),
+            &[Action::Down]
+        );
+        assert_eq!(actions(KeyCode::Char('J')), &[Action::Down]);
+        assert!(actions(KeyCode::Char('j')).is_empty());
+        assert_eq!(actions(KeyCode::Char('X')), &[Action::Close]);
+        assert!(actions(KeyCode::Char('x')).is_empty());
+        assert_eq!(
+            keymap.warnings(),
+            [
+                "unknown action \"launch-rockets\"".to_string(),
+                "up: unknown key \"hyper+k\"".to_string(),
+            ]
+        );
+    }
+}
@@ -4,11 +4,17 @@
 //! beads (issues) stored in a local SQLite database.
 
 mod app;
+mod config;
 mod data;
 mod event;
+mod export;
+mod glyphs;
+mod history;
+mod keymap;
 mod ui;
+mod views;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Parser;
 use std::path::{Path, PathBuf};
 
@@ -22,12 +28,31 @@ struct Args {
     #[arg(short, long)]
     db: Option<PathBuf>,
 
-    /// Refresh interval in seconds (0 to disable auto-refresh)
+    /// Polling interval in seconds, used when the database can't be watched
+    /// for changes (0 disables auto-refresh entirely)
     #[arg(short, long, default_value = "3")]
     refresh: u64,
+
+    /// Color theme to start with (lazygit, tokyo-night, dracula, nord, colorblind,
+    /// monochrome)
+    #[arg(long, env = "BU_THEME")]
+    theme: Option<String>,
+
+    /// Browse only: disable creating, editing, closing and other changes
+    #[arg(long)]
+    read_only: bool,
+
+    /// Draw without colors (also when NO_COLOR is set)
+    #[arg(long)]
+    no_color: bool,
+
+    /// Draw icons and tree lines with ASCII only (picked automatically when
+    /// the locale isn't UTF-8 and no `[glyphs]` profile is set)
+    #[arg(long)]
+    ascii: bool,
 }
 
-fn ensure_database_exists(db_path: &Path) -> Result<()> {
+async fn ensure_database_exists(db_path: &Path) -> Result<()> {
     if db_path.exists() {
         return Ok(());
     }
@@ -42,7 +67,9 @@ fn ensure_database_exists(db_path: &Path) -> Result<()> {
             "Database missing at {:?}. Detected .beads workspace; running 'br sync'...",
             db_path
         );
-        BrCli::sync()?;
+        BrCli::new(config::workspace_root(db_path).unwrap_or(Path::new(".")))
+            .sync()
+            .await?;
 
         if db_path.exists() {
             return Ok(());
@@ -59,11 +86,53 @@ fn ensure_database_exists(db_path: &Path) -> Result<()> {
 async fn main() -> Result<()> {
     let args = Args::parse();
 
-    // Find the database path
-    let db_path = args.db.unwrap_or_else(|| PathBuf::from(".beads/beads.db"));
+    // Find the database path: --db, or the nearest .beads up from here (br
+    // then runs from that project's directory)
+    let db_path = match args.db {
+        Some(path) => path,
+        None => {
+            let cwd = std::env::current_dir().context("Failed to read the current directory")?;
+            config::find_database(&cwd).context(
+                "No .beads directory here or in any parent. Run 'br init' to initialize a beads workspace.",
+            )?
+        }
+    };
+
+    ensure_database_exists(&db_path).await?;
+
+    let mut config = config::Config::load()?;
+    if args.ascii {
+        config.glyphs.profile = Some(glyphs::Profile::Ascii);
+    }
+    glyphs::init(glyphs::Glyphs::from_config(&config.glyphs));
+    // NO_COLOR counts when set to anything but the empty string (no-color.org)
+    config.no_color |= args.no_color || std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
 
-    ensure_database_exists(&db_path)?;
+    // --theme/BU_THEME wins over the last theme used; unknown names fall back.
+    // Without colors there's only the monochrome theme.
+    let (themes, theme_idx) = if config.no_color {
+        (vec![ui::MONOCHROME], 0)
+    } else {
+        let themes = ui::load_themes()?;
+        let find = |name: &str| ui::theme_index(&themes, name);
+        let theme_idx = args
+            .theme
+            .as_deref()
+            .and_then(find)
+            .or_else(|| config::State::load().theme.as_deref().and_then(find))
+            .unwrap_or(0);
+        (themes, theme_idx)
+    };
 
     // Run the application
-    app::run(db_path, args.refresh).await
+    app::run(
+        db_path,
+        args.refresh,
+        themes,
+        theme_idx,
+        args.read_only,
+        config,
+        keymap::Keymap::load(),
+    )
+    .await
 }
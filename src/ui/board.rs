@@ -0,0 +1,266 @@
+//! Kanban board: the visible beads as cards in status columns
+
+use std::collections::HashSet;
+
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Paragraph, StatefulWidget, Widget},
+};
+
+use crate::data::{Bead, BeadStatus, not_closed_ids};
+use crate::ui::Theme;
+use crate::ui::list::truncate_to_width;
+
+/// Status each column stands for, left to right; moving a bead into a column
+/// gives it that status
+pub const COLUMNS: [BeadStatus; 4] = [
+    BeadStatus::Open,
+    BeadStatus::InProgress,
+    BeadStatus::Blocked,
+    BeadStatus::Closed,
+];
+
+/// Column headings, in the same order as [`COLUMNS`]
+pub const HEADINGS: [&str; 4] = ["Open", "In Progress", "Blocked", "Closed"];
+
+/// Rows per card: borders, then the icon/priority/ID line and the title
+const CARD_HEIGHT: u16 = 4;
+
+/// Column a bead sits in, by effective status (open beads waiting on open
+/// blockers are blocked). Deferred beads haven't started, so they stay in Open.
+pub fn column_of(bead: &Bead, not_closed: &HashSet<&str>) -> usize {
+    match bead.effective_status(not_closed) {
+        BeadStatus::Open | BeadStatus::Deferred => 0,
+        BeadStatus::InProgress => 1,
+        BeadStatus::Blocked => 2,
+        BeadStatus::Closed => 3,
+    }
+}
+
+/// Split `beads` into the board's columns, keeping their order within each
+pub fn columns<'a>(
+    beads: impl IntoIterator<Item = &'a Bead>,
+    not_closed: &HashSet<&str>,
+) -> [Vec<&'a Bead>; 4] {
+    let mut columns: [Vec<&Bead>; 4] = Default::default();
+    for bead in beads {
+        columns[column_of(bead, not_closed)].push(bead);
+    }
+    columns
+}
+
+/// Scroll position of each column, kept between frames
+#[derive(Debug, Clone, Default)]
+pub struct BoardState {
+    offsets: [usize; 4],
+}
+
+/// The board widget
+pub struct Board<'a> {
+    /// Beads to show, in list order
+    beads: Vec<&'a Bead>,
+    /// Every loaded bead that isn't closed, for effective statuses
+    not_closed: HashSet<&'a str>,
+    theme: &'a Theme,
+    selected: Option<&'a str>,
+    closed_hidden: bool,
+}
+
+impl<'a> Board<'a> {
+    /// Board of `beads`; `all` is every loaded bead, so blockers that are
+    /// filtered out still count
+    pub fn new(beads: Vec<&'a Bead>, all: &'a [Bead], theme: &'a Theme) -> Self {
+        Self {
+            beads,
+            not_closed: not_closed_ids(all),
+            theme,
+            selected: None,
+            closed_hidden: false,
+        }
+    }
+
+    /// ID of the bead whose card is highlighted
+    pub fn selected(mut self, id: Option<&'a str>) -> Self {
+        self.selected = id;
+        self
+    }
+
+    /// Closed beads are filtered out, so the Closed column says how to show them
+    pub fn closed_hidden(mut self, hidden: bool) -> Self {
+        self.closed_hidden = hidden;
+        self
+    }
+
+    fn render_card(&self, bead: &Bead, selected: bool, area: Rect, buf: &mut Buffer) {
+        let status = bead.effective_status(&self.not_closed);
+        let (border_color, style) = if selected {
+            (self.theme.focused_border, self.theme.selection_style())
+        } else {
+            (self.theme.border, Style::default())
+        };
+        let block = Block::bordered()
+            .border_set(border::ROUNDED)
+            .border_style(Style::default().fg(border_color))
+            .style(style);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        // Deferred beads are dimmed, as in the list
+        let deferred = if bead.is_deferred() {
+            Modifier::DIM | Modifier::ITALIC
+        } else {
+            Modifier::empty()
+        };
+        let header = Line::from(vec![
+            Span::styled(
+                format!("{} ", bead.bead_type.icon_for_status(&status)),
+                Style::default().fg(self.theme.status_color(status)),
+            ),
+            Span::styled(
+                format!("{} ", bead.priority_label()),
+                Style::default()
+                    .fg(self.theme.priority_color(bead.priority))
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(bead.id.clone(), Style::default().fg(self.theme.muted)),
+        ]);
+        let title = Line::from(Span::styled(
+            truncate_to_width(&bead.title, inner.width as usize),
+            Style::default().fg(self.theme.fg).add_modifier(deferred),
+        ));
+        Paragraph::new(vec![header, title]).render(inner, buf);
+    }
+}
+
+impl StatefulWidget for Board<'_> {
+    type State = BoardState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let columns = columns(self.beads.iter().copied(), &self.not_closed);
+        let areas = Layout::horizontal([Constraint::Ratio(1, 4); 4]).split(area);
+
+        for (col, beads) in columns.iter().enumerate() {
+            let area = areas[col];
+            let selected = self
+                .selected
+                .and_then(|id| beads.iter().position(|b| b.id == id));
+            let border_color = if selected.is_some() {
+                self.theme.focused_border
+            } else {
+                self.theme.border
+            };
+            let mut block = Block::bordered()
+                .border_set(border::ROUNDED)
+                .border_style(Style::default().fg(border_color))
+                .title(Span::styled(
+                    format!(" {} ({}) ", HEADINGS[col], beads.len()),
+                    Style::default()
+                        .fg(self.theme.status_color(COLUMNS[col]))
+                        .add_modifier(Modifier::BOLD),
+                ));
+            let inner = block.inner(area);
+
+            // Scroll just enough to keep the selected card in view
+            let fits = ((inner.height / CARD_HEIGHT) as usize).max(1);
+            let offset = &mut state.offsets[col];
+            if let Some(selected) = selected {
+                if selected < *offset {
+                    *offset = selected;
+                } else if selected >= *offset + fits {
+                    *offset = selected + 1 - fits;
+                }
+            }
+            *offset = (*offset).min(beads.len().saturating_sub(fits));
+            let offset = *offset;
+            let muted = Style::default().fg(self.theme.muted);
+            if offset > 0 {
+                block = block
+                    .title(Line::styled(format!(" \u{2191}{} ", offset), muted).right_aligned());
+            }
+            let below = beads.len().saturating_sub(offset + fits);
+            if below > 0 {
+                block = block.title_bottom(
+                    Line::styled(format!(" \u{2193}{} more ", below), muted).right_aligned(),
+                );
+            }
+            block.render(area, buf);
+
+            if beads.is_empty() {
+                let hint = if col == 3 && self.closed_hidden {
+                    "c shows closed beads"
+                } else {
+                    "No beads"
+                };
+                Paragraph::new(Span::styled(hint, muted)).render(inner, buf);
+                continue;
+            }
+            for (row, bead) in beads.iter().skip(offset).take(fits).enumerate() {
+                let card = Rect {
+                    y: inner.y + row as u16 * CARD_HEIGHT,
+                    height: CARD_HEIGHT.min(inner.height),
+                    ..inner
+                };
+                self.render_card(bead, selected == Some(offset + row), card, buf);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Board, BoardState, columns};
+    use crate::data::{Bead, BeadStatus, not_closed_ids};
+    use crate::ui::theme::LAZYGIT;
+    use ratatui::{buffer::Buffer, layout::Rect, widgets::StatefulWidget};
+
+    fn bead(id: &str, status: BeadStatus) -> Bead {
+        Bead {
+            id: id.to_string(),
+            title: format!("Title {}", id),
+            status,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn beads_land_in_columns_and_scroll_to_the_selected_card() {
+        let mut waiting = bead("bd-2", BeadStatus::Open);
+        waiting.blocked_by.push("bd-1".to_string());
+        let mut beads = vec![
+            bead("bd-1", BeadStatus::Open),
+            waiting,
+            bead("bd-3", BeadStatus::Deferred),
+        ];
+        beads.extend((4..10).map(|n| bead(&format!("bd-{}", n), BeadStatus::InProgress)));
+
+        let columns = columns(&beads, &not_closed_ids(&beads));
+        let ids =
+            |col: usize| -> Vec<&str> { columns[col].iter().map(|b| b.id.as_str()).collect() };
+        assert_eq!(ids(0), vec!["bd-1", "bd-3"]);
+        assert_eq!(ids(1).len(), 6);
+        assert_eq!(ids(2), vec!["bd-2"]);
+        assert!(ids(3).is_empty());
+
+        // Two cards fit per column, so selecting the last in-progress bead scrolls
+        let area = Rect::new(0, 0, 80, 10);
+        let mut buf = Buffer::empty(area);
+        let mut state = BoardState::default();
+        Board::new(beads.iter().collect(), &beads, &LAZYGIT)
+            .selected(Some("bd-9"))
+            .closed_hidden(true)
+            .render(area, &mut buf, &mut state);
+        assert_eq!(state.offsets, [0, 4, 0, 0]);
+
+        let text: String = (0..area.height)
+            .flat_map(|y| (0..area.width).map(move |x| (x, y)))
+            .map(|pos| buf[pos].symbol().to_string())
+            .collect();
+        assert!(text.contains("In Progress (6)"), "{}", text);
+        assert!(text.contains("bd-9") && !text.contains("bd-4"), "{}", text);
+        assert!(text.contains("c shows closed"), "{}", text);
+    }
+}
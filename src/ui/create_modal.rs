@@ -10,9 +10,14 @@
 //! ╰────────────────────────────────────────────────────────────╯
 //! ╭─Options────────────────────────────────────────────────────╮
 //! │ Type: task ▾   Priority: P2 ▾   Labels: +add               │
+//! │ Parent: (none)        (editing: Status: open  Assignee: …) │
 //! ╰────────────────────────────Press <ctrl+s> to create────────╯
 
+use std::collections::HashSet;
+
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
@@ -21,10 +26,13 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph},
 };
-use tui_textarea::TextArea;
+use tui_textarea::{CursorMove, TextArea};
 
-use crate::data::BeadType;
+use crate::config::Draft;
+use crate::data::{Bead, BeadStatus, BeadType, Priority, label_counts};
+use crate::event::is_back_tab;
 use crate::ui::Theme;
+use crate::ui::layout::centered_rect;
 
 /// Which field is focused in the create modal
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -35,26 +43,42 @@ pub enum CreateField {
     Type,
     Priority,
     Labels,
+    /// Only when creating a new bead
+    Parent,
+    /// Only when editing an existing bead
+    Status,
+    /// Only when editing an existing bead
+    Assignee,
 }
 
 impl CreateField {
-    fn next(self) -> Self {
+    /// Next field; Status and Assignee only exist when `editing`, Parent only when not
+    fn next(self, editing: bool) -> Self {
         match self {
             Self::Title => Self::Description,
             Self::Description => Self::Type,
             Self::Type => Self::Priority,
             Self::Priority => Self::Labels,
-            Self::Labels => Self::Title,
+            Self::Labels if editing => Self::Status,
+            Self::Labels => Self::Parent,
+            Self::Parent => Self::Title,
+            Self::Status => Self::Assignee,
+            Self::Assignee => Self::Title,
         }
     }
 
-    fn prev(self) -> Self {
+    /// Previous field; Status and Assignee only exist when `editing`, Parent only when not
+    fn prev(self, editing: bool) -> Self {
         match self {
-            Self::Title => Self::Labels,
+            Self::Title if editing => Self::Assignee,
+            Self::Title => Self::Parent,
             Self::Description => Self::Title,
             Self::Type => Self::Description,
             Self::Priority => Self::Type,
             Self::Labels => Self::Priority,
+            Self::Parent => Self::Labels,
+            Self::Status => Self::Labels,
+            Self::Assignee => Self::Status,
         }
     }
 }
@@ -74,10 +98,40 @@ pub struct CreateModal {
     pub priority: u8,
     /// Labels (comma-separated in input, parsed to vec)
     pub labels: TextArea<'static>,
+    /// Selected status (editing only)
+    pub status: BeadStatus,
+    /// Assignee input (editing only; empty clears)
+    pub assignee: TextArea<'static>,
+    /// Whether an existing bead is being edited rather than a new one created
+    pub editing: bool,
+    /// Search text for the parent picker (creating only)
+    pub parent_input: TextArea<'static>,
+    /// Parent chosen from the picker
+    pub parent: Option<String>,
+    /// Highlighted row in the parent picker
+    pub parent_pick: usize,
+    /// Beads that can be picked as the parent: (id, title)
+    parent_candidates: Vec<(String, String)>,
+    /// Highlighted row in the label completions
+    pub label_pick: usize,
+    /// Labels already used on some bead, most used first: (label, bead count)
+    known_labels: Vec<(String, usize)>,
     /// Whether the modal is open
     pub open: bool,
+    /// Esc was pressed with unsaved changes; waiting for y/n
+    pub confirm_discard: bool,
+    /// A draft left from last time, waiting for y (restore) or n (start fresh)
+    pub offered_draft: Option<Draft>,
+    /// Field contents when the modal was opened, to tell if anything changed
+    opened_with: Snapshot,
 }
 
+/// Most rows shown in the parent and label dropdowns at once
+const PICKER_ROWS: usize = 5;
+
+/// Everything a draft holds, plus assignee and status
+type Snapshot = (Draft, String, BeadStatus);
+
 impl Default for CreateModal {
     fn default() -> Self {
         Self {
@@ -87,7 +141,19 @@ impl Default for CreateModal {
             bead_type: BeadType::default(),
             priority: 2,
             labels: TextArea::default(),
+            status: BeadStatus::default(),
+            assignee: TextArea::default(),
+            editing: false,
+            parent_input: TextArea::default(),
+            parent: None,
+            parent_pick: 0,
+            parent_candidates: Vec::new(),
+            label_pick: 0,
+            known_labels: Vec::new(),
             open: false,
+            confirm_discard: false,
+            offered_draft: None,
+            opened_with: Snapshot::default(),
         }
     }
 }
@@ -100,8 +166,9 @@ impl CreateModal {
         }
     }
 
-    /// Open the modal and reset state
-    pub fn open(&mut self) {
+    /// Open the modal and reset state; `beads` are offered in the parent picker
+    /// and their labels as completions
+    pub fn open(&mut self, beads: &[Bead]) {
         self.open = true;
         self.focus = CreateField::Title;
         self.title = TextArea::default();
@@ -109,10 +176,26 @@ impl CreateModal {
         self.labels = TextArea::default();
         self.bead_type = BeadType::Task;
         self.priority = 2;
+        self.status = BeadStatus::Open;
+        self.assignee = TextArea::default();
+        self.editing = false;
+        self.parent_input = TextArea::default();
+        self.parent = None;
+        self.parent_pick = 0;
+        self.parent_candidates = beads
+            .iter()
+            .filter(|b| b.status != BeadStatus::Closed)
+            .map(|b| (b.id.clone(), b.title.clone()))
+            .collect();
+        self.set_known_labels(beads);
+        self.confirm_discard = false;
+        self.offered_draft = None;
+        self.opened_with = self.snapshot();
     }
 
-    /// Open the modal pre-filled with bead data for editing
-    pub fn open_with_bead(&mut self, bead: &crate::data::Bead) {
+    /// Open the modal pre-filled with bead data for editing; labels on `beads`
+    /// are offered as completions
+    pub fn open_with_bead(&mut self, bead: &Bead, beads: &[Bead]) {
         self.open = true;
         self.focus = CreateField::Title;
 
@@ -137,7 +220,136 @@ impl CreateModal {
 
         // Set type and priority
         self.bead_type = bead.bead_type;
-        self.priority = bead.priority;
+        self.priority = bead.priority().level();
+        self.status = bead.status;
+        self.assignee = TextArea::from(bead.assignee.iter().cloned());
+        self.editing = true;
+        self.set_known_labels(beads);
+        self.confirm_discard = false;
+        self.offered_draft = None;
+        self.opened_with = self.snapshot();
+    }
+
+    /// The fields as typed so far, for saving as a draft
+    pub fn draft(&self) -> Draft {
+        Draft {
+            title: self.get_title(),
+            description: self.description.lines().join("\n"),
+            labels: self.labels.lines().join("\n"),
+            bead_type: self.bead_type,
+            priority: self.priority.min(Priority::MAX_LEVEL),
+            parent: self.parent.clone(),
+        }
+    }
+
+    /// Fill the fields from a saved draft
+    fn restore(&mut self, draft: Draft) {
+        let lines = |text: &str| text.lines().map(str::to_string).collect::<Vec<_>>();
+        self.title = TextArea::from(lines(&draft.title));
+        self.description = TextArea::from(lines(&draft.description));
+        self.labels = TextArea::from(lines(&draft.labels));
+        self.bead_type = draft.bead_type;
+        self.priority = draft.priority.min(Priority::MAX_LEVEL);
+        // The parent may have been closed or deleted since
+        self.parent = draft
+            .parent
+            .filter(|id| self.parent_candidates.iter().any(|(c, _)| c == id));
+        self.parent_input = TextArea::from(self.parent.iter().cloned());
+        self.parent_input.move_cursor(CursorMove::End);
+    }
+
+    /// Beads matching the parent search, best first (at most `PICKER_ROWS`)
+    pub fn parent_matches(&self) -> Vec<&(String, String)> {
+        let query = self.parent_input.lines().join("");
+        let query = query.trim();
+        if query.is_empty() {
+            return self.parent_candidates.iter().take(PICKER_ROWS).collect();
+        }
+        let matcher = SkimMatcherV2::default();
+        let mut scored: Vec<_> = self
+            .parent_candidates
+            .iter()
+            .filter_map(|candidate| {
+                let text = format!("{} {}", candidate.0, candidate.1);
+                matcher
+                    .fuzzy_match(&text, query)
+                    .map(|score| (score, candidate))
+            })
+            .collect();
+        // Stable sort keeps list order among equal scores
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored
+            .into_iter()
+            .take(PICKER_ROWS)
+            .map(|(_, candidate)| candidate)
+            .collect()
+    }
+
+    fn set_known_labels(&mut self, beads: &[Bead]) {
+        self.known_labels = label_counts(beads);
+        self.label_pick = 0;
+    }
+
+    /// Existing labels matching the label being typed (the text after the last comma),
+    /// best first; empty when nothing is typed or it already names a label
+    pub fn label_matches(&self) -> Vec<&(String, usize)> {
+        let text = self.labels.lines().join("");
+        let (entered, current) = match text.rsplit_once(',') {
+            Some((entered, current)) => (entered, current.trim()),
+            None => ("", text.trim()),
+        };
+        if current.is_empty() || self.known_labels.iter().any(|(l, _)| l == current) {
+            return Vec::new();
+        }
+        let entered: HashSet<&str> = entered.split(',').map(str::trim).collect();
+        let matcher = SkimMatcherV2::default();
+        let mut scored: Vec<_> = self
+            .known_labels
+            .iter()
+            .filter(|(label, _)| !entered.contains(label.as_str()))
+            .filter_map(|known| {
+                matcher
+                    .fuzzy_match(&known.0, current)
+                    .map(|score| (score, known))
+            })
+            .collect();
+        // Stable sort keeps the most used first among equal scores
+        scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        scored
+            .into_iter()
+            .take(PICKER_ROWS)
+            .map(|(_, known)| known)
+            .collect()
+    }
+
+    /// Replace the label being typed with the highlighted completion
+    fn complete_label(&mut self) -> bool {
+        let Some((label, _)) = self.label_matches().get(self.label_pick).copied() else {
+            return false;
+        };
+        let text = self.labels.lines().join("");
+        let completed = match text.rsplit_once(',') {
+            Some((entered, _)) => format!("{}, {}", entered, label),
+            None => label.clone(),
+        };
+        self.labels = TextArea::from([completed]);
+        self.labels.move_cursor(CursorMove::End);
+        self.label_pick = 0;
+        true
+    }
+
+    /// Whether the parent picker dropdown should be shown
+    pub fn picking_parent(&self) -> bool {
+        self.focus == CreateField::Parent && self.parent.is_none()
+    }
+
+    fn snapshot(&self) -> Snapshot {
+        (self.draft(), self.get_assignee(), self.status)
+    }
+
+    /// Whether anything was changed since the modal was opened
+    pub fn is_dirty(&self) -> bool {
+        self.snapshot() != self.opened_with
     }
 
     /// Close the modal
@@ -173,6 +385,11 @@ impl CreateModal {
             .collect()
     }
 
+    /// Get the assignee (empty means unassigned)
+    pub fn get_assignee(&self) -> String {
+        self.assignee.lines().join("").trim().to_string()
+    }
+
     /// Handle pasted text for the currently focused field.
     pub fn handle_paste(&mut self, text: &str) {
         match self.focus {
@@ -196,18 +413,51 @@ impl CreateModal {
                     .join(", ");
                 let _ = self.labels.insert_str(labels_line);
             }
-            CreateField::Type | CreateField::Priority => {}
+            CreateField::Assignee => {
+                let single_line = text.lines().map(str::trim).collect::<Vec<_>>().join(" ");
+                let _ = self.assignee.insert_str(single_line);
+            }
+            CreateField::Parent => {
+                let single_line = text.lines().map(str::trim).collect::<Vec<_>>().join(" ");
+                let _ = self.parent_input.insert_str(single_line);
+                self.parent = None;
+                self.parent_pick = 0;
+            }
+            CreateField::Type | CreateField::Priority | CreateField::Status => {}
         }
     }
 
     /// Handle a key event, returns true if modal should close and submit
     pub fn handle_key(&mut self, key: KeyEvent) -> ModalAction {
         let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
-        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+
+        // Pending "restore draft?": y restores it, n or Esc starts from an empty form
+        if let Some(draft) = self.offered_draft.take() {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => self.restore(draft),
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {}
+                _ => self.offered_draft = Some(draft),
+            }
+            return ModalAction::None;
+        }
+
+        // Pending "discard unsaved changes?": y discards, anything else keeps editing
+        if self.confirm_discard {
+            self.confirm_discard = false;
+            if matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+                self.close();
+                return ModalAction::Cancelled;
+            }
+            return ModalAction::None;
+        }
 
         match key.code {
-            // Cancel
+            // Cancel, asking first if that would throw away changes
             KeyCode::Esc => {
+                if self.is_dirty() {
+                    self.confirm_discard = true;
+                    return ModalAction::None;
+                }
                 self.close();
                 return ModalAction::Cancelled;
             }
@@ -249,19 +499,20 @@ impl CreateModal {
                 return ModalAction::None;
             }
 
-            // Tab to switch fields
-            KeyCode::Tab if shift => {
-                // Some terminals send Tab with shift modifier
-                self.focus = self.focus.prev();
+            // Shift+Tab to the previous field, however the terminal reports it
+            _ if is_back_tab(&key) => {
+                self.focus = self.focus.prev(self.editing);
                 return ModalAction::None;
             }
-            KeyCode::BackTab => {
-                // Most terminals send BackTab for Shift+Tab
-                self.focus = self.focus.prev();
+
+            // Tab accepts a label completion when one is offered
+            KeyCode::Tab if self.focus == CreateField::Labels && self.complete_label() => {
                 return ModalAction::None;
             }
+
+            // Tab to the next field
             KeyCode::Tab => {
-                self.focus = self.focus.next();
+                self.focus = self.focus.next(self.editing);
                 return ModalAction::None;
             }
 
@@ -309,23 +560,78 @@ impl CreateModal {
                         self.priority = self.priority.saturating_sub(1);
                     }
                     KeyCode::Right | KeyCode::Char('l') | KeyCode::Char('j') | KeyCode::Down => {
-                        self.priority = (self.priority + 1).min(4);
+                        self.priority = (self.priority + 1).min(Priority::MAX_LEVEL);
                     }
                     KeyCode::Char(c) if c.is_ascii_digit() => {
                         let p = c.to_digit(10).unwrap() as u8;
-                        if p <= 4 {
+                        if p <= Priority::MAX_LEVEL {
                             self.priority = p;
                         }
                     }
                     _ => {}
                 }
             }
-            CreateField::Labels => {
+            CreateField::Labels => match key.code {
+                // Move through the completions; Enter (like Tab) accepts one
+                KeyCode::Up => self.label_pick = self.label_pick.saturating_sub(1),
+                KeyCode::Down => {
+                    let last = self.label_matches().len().saturating_sub(1);
+                    self.label_pick = (self.label_pick + 1).min(last);
+                }
                 // Don't allow newlines in labels field
+                KeyCode::Enter => {
+                    self.complete_label();
+                }
+                _ => {
+                    if self.labels.input(key) {
+                        self.label_pick = 0;
+                    }
+                }
+            },
+            CreateField::Status => {
+                // Cycle through statuses with left/right or j/k
+                let statuses = BeadStatus::all();
+                let idx = statuses.iter().position(|s| *s == self.status).unwrap_or(0);
+                match key.code {
+                    KeyCode::Left | KeyCode::Char('h') | KeyCode::Char('k') | KeyCode::Up => {
+                        self.status = statuses[(idx + statuses.len() - 1) % statuses.len()];
+                    }
+                    KeyCode::Right | KeyCode::Char('l') | KeyCode::Char('j') | KeyCode::Down => {
+                        self.status = statuses[(idx + 1) % statuses.len()];
+                    }
+                    _ => {}
+                }
+            }
+            CreateField::Assignee => {
                 if key.code != KeyCode::Enter {
-                    self.labels.input(key);
+                    self.assignee.input(key);
                 }
             }
+            CreateField::Parent => match key.code {
+                // Move through the matches, or choose the highlighted one
+                KeyCode::Up => self.parent_pick = self.parent_pick.saturating_sub(1),
+                KeyCode::Down => {
+                    let last = self.parent_matches().len().saturating_sub(1);
+                    self.parent_pick = (self.parent_pick + 1).min(last);
+                }
+                KeyCode::Enter => {
+                    if self.parent.is_none()
+                        && let Some((id, _)) = self.parent_matches().get(self.parent_pick)
+                    {
+                        let id = id.clone();
+                        self.parent_input = TextArea::from([id.clone()]);
+                        self.parent_input.move_cursor(CursorMove::End);
+                        self.parent = Some(id);
+                    }
+                }
+                // Editing the text goes back to searching
+                _ => {
+                    if self.parent_input.input(key) {
+                        self.parent = None;
+                        self.parent_pick = 0;
+                    }
+                }
+            },
         }
     }
 
@@ -352,7 +658,185 @@ impl CreateModal {
 
 #[cfg(test)]
 mod tests {
-    use super::{CreateField, CreateModal};
+    use super::{CreateField, CreateModal, ModalAction};
+    use crate::data::{Bead, BeadStatus};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn press(modal: &mut CreateModal, code: KeyCode) -> ModalAction {
+        modal.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn esc_asks_before_discarding_changes() {
+        let mut modal = CreateModal::new();
+        modal.open(&[]);
+        assert_eq!(press(&mut modal, KeyCode::Esc), ModalAction::Cancelled);
+
+        modal.open(&[]);
+        modal.handle_paste("Half-typed bead");
+        assert_eq!(press(&mut modal, KeyCode::Esc), ModalAction::None);
+        assert!(modal.confirm_discard);
+        // Anything but y goes back to editing, text intact
+        assert_eq!(press(&mut modal, KeyCode::Char('n')), ModalAction::None);
+        assert_eq!(modal.get_title(), "Half-typed bead");
+
+        press(&mut modal, KeyCode::Esc);
+        assert_eq!(
+            press(&mut modal, KeyCode::Char('y')),
+            ModalAction::Cancelled
+        );
+        assert!(!modal.open);
+    }
+
+    #[test]
+    fn offered_draft_is_restored_with_y() {
+        let mut modal = CreateModal::new();
+        modal.open(&[]);
+        modal.handle_paste("Saved title");
+        modal.focus = CreateField::Labels;
+        modal.handle_paste("ui");
+        let draft = modal.draft();
+
+        modal.open(&[]);
+        modal.offered_draft = Some(draft.clone());
+        // Other keys don't answer the question or reach the fields
+        press(&mut modal, KeyCode::Char('x'));
+        assert_eq!(modal.get_title(), "");
+        assert!(modal.offered_draft.is_some());
+
+        press(&mut modal, KeyCode::Char('y'));
+        assert_eq!(modal.draft(), draft);
+        assert_eq!(modal.get_labels(), vec!["ui"]);
+        assert!(modal.is_dirty());
+
+        modal.open(&[]);
+        modal.offered_draft = Some(draft);
+        press(&mut modal, KeyCode::Char('n'));
+        assert!(modal.offered_draft.is_none());
+        assert!(modal.draft().is_empty());
+    }
+
+    #[test]
+    fn editing_is_only_dirty_after_a_change() {
+        let bead = Bead {
+            title: "Existing".to_string(),
+            ..Default::default()
+        };
+        let mut modal = CreateModal::new();
+        modal.open_with_bead(&bead, &[]);
+        assert!(!modal.is_dirty());
+        modal.priority = 0;
+        assert!(modal.is_dirty());
+    }
+
+    #[test]
+    fn status_and_assignee_are_only_tabbed_to_when_editing() {
+        let bead = Bead {
+            title: "Existing".to_string(),
+            assignee: Some("alice".to_string()),
+            status: BeadStatus::InProgress,
+            ..Default::default()
+        };
+        let mut modal = CreateModal::new();
+        modal.open_with_bead(&bead, &[]);
+        assert_eq!(modal.get_assignee(), "alice");
+        modal.focus = CreateField::Labels;
+        press(&mut modal, KeyCode::Tab);
+        assert_eq!(modal.focus, CreateField::Status);
+        press(&mut modal, KeyCode::Right);
+        assert_eq!(modal.status, BeadStatus::Blocked);
+        press(&mut modal, KeyCode::Tab);
+        assert_eq!(modal.focus, CreateField::Assignee);
+        press(&mut modal, KeyCode::Tab);
+        assert_eq!(modal.focus, CreateField::Title);
+
+        modal.open(&[]);
+        modal.focus = CreateField::Labels;
+        press(&mut modal, KeyCode::Tab);
+        assert_eq!(modal.focus, CreateField::Parent);
+        press(&mut modal, KeyCode::Tab);
+        assert_eq!(modal.focus, CreateField::Title);
+        press(&mut modal, KeyCode::BackTab);
+        assert_eq!(modal.focus, CreateField::Parent);
+    }
+
+    #[test]
+    fn labels_complete_from_existing_ones() {
+        let labeled = |labels: &[&str]| Bead {
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            ..Default::default()
+        };
+        let beads = [
+            labeled(&["backend", "ui"]),
+            labeled(&["backend"]),
+            labeled(&["bug"]),
+        ];
+        let mut modal = CreateModal::new();
+        modal.open(&beads);
+        modal.focus = CreateField::Labels;
+
+        modal.handle_paste("b");
+        let matches: Vec<&str> = modal
+            .label_matches()
+            .iter()
+            .map(|(l, _)| l.as_str())
+            .collect();
+        assert_eq!(matches, ["backend", "bug"]);
+        press(&mut modal, KeyCode::Down);
+        press(&mut modal, KeyCode::Tab);
+        assert_eq!(modal.get_labels(), vec!["bug"]);
+        assert_eq!(modal.focus, CreateField::Labels);
+
+        // A complete label offers nothing, so Tab moves on as usual
+        modal.handle_paste(", ui");
+        assert!(modal.label_matches().is_empty());
+        press(&mut modal, KeyCode::Tab);
+        assert_eq!(modal.focus, CreateField::Parent);
+
+        modal.focus = CreateField::Labels;
+        modal.handle_paste(", bak");
+        press(&mut modal, KeyCode::Enter);
+        assert_eq!(modal.get_labels(), vec!["bug", "ui", "backend"]);
+
+        // Labels already entered aren't offered again
+        modal.handle_paste(", b");
+        assert!(modal.label_matches().is_empty());
+    }
+
+    #[test]
+    fn parent_is_picked_from_matching_open_beads() {
+        let bead = |id: &str, title: &str, status| Bead {
+            id: id.to_string(),
+            title: title.to_string(),
+            status,
+            ..Default::default()
+        };
+        let beads = [
+            bead("bd-a1", "Login epic", BeadStatus::Open),
+            bead("bd-b2", "Billing epic", BeadStatus::Open),
+            bead("bd-c3", "Old login work", BeadStatus::Closed),
+        ];
+        let mut modal = CreateModal::new();
+        modal.open(&beads);
+        assert_eq!(modal.parent_matches().len(), 2);
+
+        modal.focus = CreateField::Parent;
+        modal.handle_paste("bill");
+        let ids: Vec<&str> = modal
+            .parent_matches()
+            .iter()
+            .map(|(id, _)| id.as_str())
+            .collect();
+        assert_eq!(ids, ["bd-b2"]);
+        press(&mut modal, KeyCode::Enter);
+        assert_eq!(modal.parent.as_deref(), Some("bd-b2"));
+        assert!(!modal.picking_parent());
+
+        // Typing again searches afresh
+        press(&mut modal, KeyCode::Backspace);
+        assert_eq!(modal.parent, None);
+        assert!(modal.picking_parent());
+    }
 
     #[test]
     fn paste_title_flattens_newlines() {
@@ -383,6 +867,57 @@ mod tests {
 
         assert_eq!(modal.get_labels(), vec!["ui", "bug"]);
     }
+
+    #[test]
+    fn every_shift_tab_variant_goes_back_a_field() {
+        let variants = [
+            KeyEvent::new(KeyCode::BackTab, KeyModifiers::NONE), // iTerm2, tmux
+            KeyEvent::new(KeyCode::BackTab, KeyModifiers::SHIFT), // Alacritty, kitty protocol
+            KeyEvent::new(KeyCode::Tab, KeyModifiers::SHIFT),
+        ];
+        for back in variants {
+            let mut modal = CreateModal::new();
+            modal.open(&[]);
+            press(&mut modal, KeyCode::Tab);
+            assert_eq!(modal.focus, CreateField::Description);
+            modal.handle_key(back);
+            assert_eq!(modal.focus, CreateField::Title, "{:?}", back);
+        }
+
+        // Shift+Tab in Labels doesn't accept a completion on the way back
+        let mut modal = CreateModal::new();
+        modal.open(&[Bead {
+            labels: vec!["frontend".to_string()],
+            ..Default::default()
+        }]);
+        modal.focus = CreateField::Labels;
+        modal.handle_paste("fr");
+        modal.handle_key(KeyEvent::new(KeyCode::Tab, KeyModifiers::SHIFT));
+        assert_eq!(modal.focus, CreateField::Priority);
+        assert_eq!(modal.get_labels(), vec!["fr"]);
+    }
+
+    #[test]
+    fn renders_in_terminals_smaller_than_the_modal() {
+        use ratatui::{Terminal, backend::TestBackend};
+
+        let mut modal = CreateModal::new();
+        modal.open(&[Bead::default()]);
+        modal.focus = CreateField::Parent;
+        for (width, height) in [(80, 24), (20, 6), (3, 2), (1, 1), (0, 0)] {
+            let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+            terminal
+                .draw(|frame| {
+                    super::render_create_modal(
+                        frame,
+                        frame.area(),
+                        &crate::ui::theme::LAZYGIT,
+                        &modal,
+                    )
+                })
+                .unwrap();
+        }
+    }
 }
 
 /// Action to take after handling a key
@@ -396,11 +931,9 @@ pub enum ModalAction {
 /// Render the create modal
 pub fn render_create_modal(frame: &mut Frame, area: Rect, theme: &Theme, modal: &CreateModal) {
     // Calculate modal size - take up most of the screen
-    let modal_width = (area.width - 4).min(80);
-    let modal_height = (area.height - 4).min(20);
-    let x = (area.width - modal_width) / 2;
-    let y = (area.height - modal_height) / 2;
-    let modal_area = Rect::new(x, y, modal_width, modal_height);
+    let modal_width = area.width.saturating_sub(4).min(80);
+    let modal_height = area.height.saturating_sub(4).min(20);
+    let modal_area = centered_rect(area, modal_width, modal_height);
 
     // Clear the area
     frame.render_widget(Clear, modal_area);
@@ -411,7 +944,7 @@ pub fn render_create_modal(frame: &mut Frame, area: Rect, theme: &Theme, modal:
         .constraints([
             Constraint::Length(3), // Title
             Constraint::Min(5),    // Description
-            Constraint::Length(3), // Options
+            Constraint::Length(4), // Options
         ])
         .split(modal_area);
 
@@ -423,6 +956,74 @@ pub fn render_create_modal(frame: &mut Frame, area: Rect, theme: &Theme, modal:
 
     // Render options field
     render_options_field(frame, chunks[2], theme, modal);
+
+    // Dropdowns for the focused field, drawn over the bottom of the description
+    if modal.picking_parent() {
+        let rows: Vec<(String, String)> = modal
+            .parent_matches()
+            .into_iter()
+            .map(|(id, title)| (format!("{} ", id), title.clone()))
+            .collect();
+        let title = " Parent ─── ↑/↓ to pick, <enter> to choose ";
+        render_picker(frame, chunks[1], theme, title, &rows, modal.parent_pick);
+    } else if modal.focus == CreateField::Labels {
+        let rows: Vec<(String, String)> = modal
+            .label_matches()
+            .into_iter()
+            .map(|(label, count)| {
+                let beads = if *count == 1 { "bead" } else { "beads" };
+                (label.clone(), format!("  {} {}", count, beads))
+            })
+            .collect();
+        if !rows.is_empty() {
+            let title = " Existing labels ─── ↑/↓ to pick, <tab> to complete ";
+            render_picker(frame, chunks[1], theme, title, &rows, modal.label_pick);
+        }
+    }
+}
+
+/// Dropdown of (bold, plain) rows with `pick` highlighted, drawn at the bottom of `area`
+fn render_picker(
+    frame: &mut Frame,
+    area: Rect,
+    theme: &Theme,
+    title: &str,
+    rows: &[(String, String)],
+    pick: usize,
+) {
+    let height = (rows.len().max(1) as u16 + 2).min(area.height);
+    let picker_area = Rect::new(area.x, area.bottom() - height, area.width, height);
+    frame.render_widget(Clear, picker_area);
+
+    let lines: Vec<Line> = if rows.is_empty() {
+        vec![Line::styled(
+            "No matching beads",
+            Style::default().fg(theme.muted),
+        )]
+    } else {
+        rows.iter()
+            .enumerate()
+            .map(|(i, (key, rest))| {
+                let style = if i == pick {
+                    theme.selection_style().fg(theme.selection_fg)
+                } else {
+                    Style::default().fg(theme.fg)
+                };
+                Line::from(vec![
+                    Span::styled(key.clone(), style.add_modifier(Modifier::BOLD)),
+                    Span::styled(rest.clone(), style),
+                ])
+            })
+            .collect()
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_set(border::ROUNDED)
+        .border_style(Style::default().fg(theme.focused_border))
+        .title(title)
+        .title_style(Style::default().fg(theme.fg).add_modifier(Modifier::BOLD));
+    frame.render_widget(Paragraph::new(lines).block(block), picker_area);
 }
 
 fn render_title_field(frame: &mut Frame, area: Rect, theme: &Theme, modal: &CreateModal) {
@@ -487,7 +1088,15 @@ fn render_options_field(frame: &mut Frame, area: Rect, theme: &Theme, modal: &Cr
     let type_focused = modal.focus == CreateField::Type;
     let priority_focused = modal.focus == CreateField::Priority;
     let labels_focused = modal.focus == CreateField::Labels;
-    let any_focused = type_focused || priority_focused || labels_focused;
+    let status_focused = modal.focus == CreateField::Status;
+    let assignee_focused = modal.focus == CreateField::Assignee;
+    let parent_focused = modal.focus == CreateField::Parent;
+    let any_focused = type_focused
+        || priority_focused
+        || labels_focused
+        || status_focused
+        || assignee_focused
+        || parent_focused;
 
     let border_color = if any_focused {
         theme.focused_border
@@ -495,15 +1104,33 @@ fn render_options_field(frame: &mut Frame, area: Rect, theme: &Theme, modal: &Cr
         theme.border
     };
 
-    // Title with submit hint
-    let title = " Options ─── Press <ctrl+s> to create ";
+    // Title with submit hint, or the pending restore/discard question
+    let (title, title_color) = if modal.offered_draft.is_some() {
+        (
+            " Restore the unsaved draft from last time? y restores, n starts fresh ",
+            theme.accent,
+        )
+    } else if modal.confirm_discard {
+        (
+            " Discard unsaved changes? y discards, any other key keeps editing ",
+            theme.status_blocked,
+        )
+    } else if modal.editing {
+        (" Options ─── Press <ctrl+s> to save ", theme.fg)
+    } else {
+        (" Options ─── Press <ctrl+s> to create ", theme.fg)
+    };
 
     let block = Block::default()
         .borders(Borders::ALL)
         .border_set(border::ROUNDED)
         .border_style(Style::default().fg(border_color))
         .title(title)
-        .title_style(Style::default().fg(theme.fg).add_modifier(Modifier::BOLD));
+        .title_style(
+            Style::default()
+                .fg(title_color)
+                .add_modifier(Modifier::BOLD),
+        );
 
     let inner = block.inner(area);
     frame.render_widget(block, area);
@@ -556,7 +1183,71 @@ fn render_options_field(frame: &mut Frame, area: Rect, theme: &Theme, modal: &Cr
     };
     spans.push(Span::styled(display_text, label_style));
 
-    let line = Line::from(spans);
-    let para = Paragraph::new(line);
+    let mut lines = vec![Line::from(spans)];
+
+    // Status and assignee, only for existing beads
+    if modal.editing {
+        let mut spans = vec![Span::styled("Status: ", Style::default().fg(theme.muted))];
+        let status_style = if status_focused {
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+        } else {
+            Style::default().fg(theme.status_color(modal.status))
+        };
+        spans.push(Span::styled(
+            format!(" {} {} ", modal.status.icon(), modal.status),
+            status_style,
+        ));
+
+        spans.push(Span::raw("   "));
+
+        spans.push(Span::styled("Assignee: ", Style::default().fg(theme.muted)));
+        let assignee = modal.get_assignee();
+        let assignee_style = if assignee_focused {
+            Style::default().fg(theme.accent)
+        } else {
+            Style::default().fg(theme.fg)
+        };
+        spans.push(Span::styled(
+            if assignee.is_empty() {
+                "(none)".to_string()
+            } else {
+                assignee
+            },
+            assignee_style,
+        ));
+        lines.push(Line::from(spans));
+    } else {
+        // Parent, chosen with the picker
+        let mut spans = vec![Span::styled("Parent: ", Style::default().fg(theme.muted))];
+        let parent_style = if parent_focused {
+            Style::default().fg(theme.accent)
+        } else {
+            Style::default().fg(theme.fg)
+        };
+        match &modal.parent {
+            Some(id) => {
+                spans.push(Span::styled(id.clone(), parent_style));
+                if let Some((_, title)) = modal.parent_candidates.iter().find(|(c, _)| c == id) {
+                    spans.push(Span::styled(
+                        format!(" {}", title),
+                        Style::default().fg(theme.muted),
+                    ));
+                }
+            }
+            None => {
+                let query = modal.parent_input.lines().join("");
+                spans.push(if query.is_empty() {
+                    Span::styled("(none)", parent_style)
+                } else {
+                    Span::styled(query, parent_style)
+                });
+            }
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let para = Paragraph::new(lines);
     frame.render_widget(para, inner);
 }
@@ -9,9 +9,133 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, StatefulWidget, Widget, Wrap},
 };
 
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use unicode_width::UnicodeWidthStr;
+
 use crate::data::{Bead, BeadStatus, Comment};
+use crate::glyphs;
 use crate::ui::Theme;
 
+/// How far the dependency graph follows blockers before cutting off
+const MAX_GRAPH_DEPTH: usize = 8;
+
+/// Which links to follow: `blocked_by` or `blocks`
+type Edges = fn(&Bead) -> &[String];
+
+/// One line of a dependency tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct GraphNode {
+    /// Box-drawing connector, e.g. "│  └─ "
+    prefix: String,
+    id: String,
+    /// Already on the path above (a dependency cycle), so not expanded
+    cycle: bool,
+}
+
+/// Walk `edges` (blocked_by or blocks) from `root`, returning tree lines
+/// for everything reachable, root excluded
+fn graph_lines(by_id: &HashMap<&str, &Bead>, root: &Bead, edges: Edges) -> Vec<GraphNode> {
+    fn walk(
+        by_id: &HashMap<&str, &Bead>,
+        ids: &[String],
+        edges: Edges,
+        indent: &str,
+        path: &mut Vec<String>,
+        out: &mut Vec<GraphNode>,
+    ) {
+        let glyphs = glyphs::get();
+        for (idx, id) in ids.iter().enumerate() {
+            let last = idx + 1 == ids.len();
+            let cycle = path.contains(id);
+            out.push(GraphNode {
+                prefix: format!(
+                    "{}{}",
+                    indent,
+                    if last {
+                        &glyphs.last_branch
+                    } else {
+                        &glyphs.branch
+                    }
+                ),
+                id: id.clone(),
+                cycle,
+            });
+            let Some(bead) = by_id.get(id.as_str()) else {
+                continue;
+            };
+            if cycle || path.len() >= MAX_GRAPH_DEPTH {
+                continue;
+            }
+            let trunk = if last {
+                " ".repeat(glyphs.trunk.width())
+            } else {
+                glyphs.trunk.clone()
+            };
+            let child_indent = format!("{}{}", indent, trunk);
+            path.push(id.clone());
+            walk(by_id, edges(bead), edges, &child_indent, path, out);
+            path.pop();
+        }
+    }
+
+    let mut out = Vec::new();
+    let mut path = vec![root.id.clone()];
+    walk(by_id, edges(root), edges, "", &mut path, &mut out);
+    out
+}
+
+/// How long ago `time` was, e.g. "just now", "5m ago", "2h ago", "3 days ago"
+pub fn relative_time(time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let age = now.signed_duration_since(time);
+    let plural =
+        |n: i64, unit: &str| format!("{} {}{} ago", n, unit, if n == 1 { "" } else { "s" });
+    let secs = age.num_seconds();
+    let days = age.num_days();
+    if secs < 60 {
+        "just now".to_string()
+    } else if secs < 3_600 {
+        format!("{}m ago", age.num_minutes())
+    } else if secs < 86_400 {
+        format!("{}h ago", age.num_hours())
+    } else if days < 30 {
+        plural(days, "day")
+    } else if days < 365 {
+        plural(days / 30, "month")
+    } else {
+        plural(days / 365, "year")
+    }
+}
+
+/// Length of a span of time in its two largest units, e.g. "4d 3h", "2h 15m", "40m"
+pub fn format_duration(duration: chrono::Duration) -> String {
+    let minutes = duration.num_minutes().max(0);
+    let (days, hours, minutes) = (minutes / 1_440, minutes / 60 % 24, minutes % 60);
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// What a bead looked like when it was shown: a remembered scroll offset
+/// only applies while this stays the same
+type ContentVersion = (Option<DateTime<Utc>>, usize);
+
+fn content_version(bead: &Bead) -> ContentVersion {
+    (bead.updated_at, bead.comments.len())
+}
+
+/// The view the current scroll offset belongs to
+#[derive(Debug, Clone, PartialEq)]
+struct Shown {
+    id: String,
+    graph: bool,
+    version: ContentVersion,
+}
+
 /// State for the detail panel (scroll position)
 #[derive(Debug, Default, Clone)]
 pub struct DetailState {
@@ -21,6 +145,12 @@ pub struct DetailState {
     content_height: u16,
     /// Viewport height
     viewport_height: u16,
+    /// Content rows (before scrolling) that name another bead, for clicks
+    links: Vec<(u16, String)>,
+    /// Bead currently shown
+    shown: Option<Shown>,
+    /// Where each bead's details were left, restored when it's shown again
+    saved_scroll: HashMap<String, (u16, ContentVersion)>,
 }
 
 impl DetailState {
@@ -40,6 +170,50 @@ impl DetailState {
         self.scroll = self.scroll.saturating_add(n);
     }
 
+    /// Lines in half the visible panel (at least one), for Ctrl+D/Ctrl+U
+    pub fn half_page(&self) -> u16 {
+        (self.viewport_height / 2).max(1)
+    }
+
+    /// The bead named on a row of the panel (counted from the top of its
+    /// inner area), if that row is a dependency line
+    pub fn link_at(&self, row: u16) -> Option<&str> {
+        let row = self.scroll.saturating_add(row);
+        self.links
+            .iter()
+            .find(|(link_row, _)| *link_row == row)
+            .map(|(_, id)| id.as_str())
+    }
+
+    /// Switch to the scroll offset of the bead being shown, remembering
+    /// where the previous one was left. The graph view always starts at the
+    /// top, as does a bead whose content changed since it was last read.
+    fn follow(&mut self, bead: Option<&Bead>, graph: bool) {
+        let next = bead.map(|b| Shown {
+            id: b.id.clone(),
+            graph,
+            version: content_version(b),
+        });
+        if next == self.shown {
+            return;
+        }
+        if let Some(prev) = self.shown.take()
+            && !prev.graph
+        {
+            self.saved_scroll
+                .insert(prev.id, (self.scroll, prev.version));
+        }
+        self.scroll = match &next {
+            Some(shown) if !shown.graph => self
+                .saved_scroll
+                .get(&shown.id)
+                .filter(|(_, version)| *version == shown.version)
+                .map_or(0, |(scroll, _)| *scroll),
+            _ => 0,
+        };
+        self.shown = next;
+    }
+
     /// Reset scroll to top
     pub fn reset(&mut self) {
         self.scroll = 0;
@@ -57,6 +231,12 @@ pub struct DetailPanel<'a> {
     bead: Option<&'a Bead>,
     theme: &'a Theme,
     focused: bool,
+    /// All beads, when showing the dependency graph instead of the details
+    graph: Option<&'a [Bead]>,
+    /// The dependency cycle the bead is part of, if any
+    cycle: Option<&'a [String]>,
+    /// Show timestamps as "3 days ago" instead of dates
+    relative_times: bool,
 }
 
 impl<'a> DetailPanel<'a> {
@@ -65,23 +245,44 @@ impl<'a> DetailPanel<'a> {
             bead,
             theme,
             focused: false,
+            graph: None,
+            cycle: None,
+            relative_times: false,
         }
     }
 
+    pub fn relative_times(mut self, relative: bool) -> Self {
+        self.relative_times = relative;
+        self
+    }
+
+    fn timestamp(&self, time: DateTime<Utc>) -> String {
+        if self.relative_times {
+            relative_time(time, Utc::now())
+        } else {
+            time.format("%Y-%m-%d %H:%M").to_string()
+        }
+    }
+
+    pub fn cycle(mut self, cycle: Option<&'a [String]>) -> Self {
+        self.cycle = cycle;
+        self
+    }
+
+    /// Show the selected bead's transitive blockers and dependents
+    /// (looked up in `beads`) instead of its details
+    pub fn dependency_graph(mut self, beads: Option<&'a [Bead]>) -> Self {
+        self.graph = beads;
+        self
+    }
+
     pub fn focused(mut self, focused: bool) -> Self {
         self.focused = focused;
         self
     }
 
     fn status_style(&self, status: &BeadStatus) -> Style {
-        let color = match status {
-            BeadStatus::Open => self.theme.status_open,
-            BeadStatus::InProgress => self.theme.status_in_progress,
-            BeadStatus::Blocked => self.theme.status_blocked,
-            BeadStatus::Deferred => self.theme.status_deferred,
-            BeadStatus::Closed => self.theme.status_closed,
-        };
-        Style::default().fg(color)
+        Style::default().fg(self.theme.status_color(*status))
     }
 
     fn render_comment(&self, lines: &mut Vec<Line<'static>>, comment: &Comment) {
@@ -96,7 +297,7 @@ impl<'a> DetailPanel<'a> {
         ];
         if let Some(ts) = comment.created_at {
             header.push(Span::styled(
-                format!("  {}", ts.format("%Y-%m-%d %H:%M")),
+                format!("  {}", self.timestamp(ts)),
                 Style::default().fg(self.theme.muted),
             ));
         }
@@ -109,7 +310,96 @@ impl<'a> DetailPanel<'a> {
         }
     }
 
-    fn render_metadata(&self, bead: &Bead) -> Text<'static> {
+    fn render_graph(
+        &self,
+        bead: &Bead,
+        beads: &[Bead],
+        links: &mut Vec<(usize, String)>,
+    ) -> Text<'static> {
+        let by_id: HashMap<&str, &Bead> = beads.iter().map(|b| (b.id.as_str(), b)).collect();
+        let mut lines = vec![
+            Line::from(vec![
+                Span::styled(
+                    format!("{} ", bead.status.icon()),
+                    self.status_style(&bead.status),
+                ),
+                Span::styled(bead.id.clone(), Style::default().fg(self.theme.accent)),
+                Span::raw(": "),
+                Span::styled(
+                    bead.title.clone(),
+                    Style::default()
+                        .fg(self.theme.fg)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]),
+            Line::raw(""),
+        ];
+
+        let sections: [(&str, Edges, _); 2] = [
+            ("Blocked by", |b| &b.blocked_by, self.theme.status_blocked),
+            ("Blocks", |b| &b.blocks, self.theme.accent),
+        ];
+        for (heading, edges, color) in sections {
+            let nodes = graph_lines(&by_id, bead, edges);
+            lines.push(Line::from(Span::styled(
+                format!("{}:", heading),
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            )));
+            if nodes.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    "  (none)",
+                    Style::default().fg(self.theme.muted),
+                )));
+            }
+            for node in nodes {
+                let mut spans = vec![Span::styled(
+                    format!("  {}", node.prefix),
+                    Style::default().fg(self.theme.border),
+                )];
+                match by_id.get(node.id.as_str()) {
+                    Some(dep) => {
+                        // Closed blockers no longer hold anything up, so fade them
+                        let text_style = if dep.status == BeadStatus::Closed {
+                            Style::default().fg(self.theme.muted)
+                        } else {
+                            Style::default().fg(self.theme.fg)
+                        };
+                        spans.push(Span::styled(
+                            format!("{} ", dep.status.icon()),
+                            self.status_style(&dep.status),
+                        ));
+                        spans.push(Span::styled(
+                            dep.id.clone(),
+                            Style::default().fg(self.theme.accent),
+                        ));
+                        spans.push(Span::styled(format!(": {}", dep.title), text_style));
+                    }
+                    None => spans.push(Span::styled(
+                        format!("{} (not found)", node.id),
+                        Style::default().fg(self.theme.muted),
+                    )),
+                }
+                if node.cycle {
+                    spans.push(Span::styled(
+                        " (cycle)",
+                        Style::default().fg(self.theme.status_blocked),
+                    ));
+                }
+                links.push((lines.len(), node.id.clone()));
+                lines.push(Line::from(spans));
+            }
+            lines.push(Line::raw(""));
+        }
+
+        Text::from(lines)
+    }
+
+    fn render_metadata(
+        &self,
+        bead: &Bead,
+        width: u16,
+        links: &mut Vec<(usize, String)>,
+    ) -> Text<'static> {
         let mut lines = Vec::new();
 
         // Title
@@ -134,6 +424,29 @@ impl<'a> DetailPanel<'a> {
             ),
         ]));
 
+        if let Some(cycle) = self.cycle {
+            let others: Vec<&str> = cycle
+                .iter()
+                .filter(|id| **id != bead.id)
+                .map(String::as_str)
+                .collect();
+            let text = if others.is_empty() {
+                format!("{} Depends on itself", glyphs::get().cycle)
+            } else {
+                format!(
+                    "{} Dependency cycle with {}",
+                    glyphs::get().cycle,
+                    others.join(", ")
+                )
+            };
+            lines.push(Line::from(Span::styled(
+                text,
+                Style::default()
+                    .fg(self.theme.status_blocked)
+                    .add_modifier(Modifier::BOLD),
+            )));
+        }
+
         // Type and Priority
         lines.push(Line::from(vec![
             Span::styled("Type: ", Style::default().fg(self.theme.muted)),
@@ -153,13 +466,20 @@ impl<'a> DetailPanel<'a> {
 
         // Labels
         if !bead.labels.is_empty() {
-            lines.push(Line::from(vec![
-                Span::styled("Labels: ", Style::default().fg(self.theme.muted)),
-                Span::styled(
-                    bead.labels.join(", "),
-                    Style::default().fg(self.theme.accent),
-                ),
-            ]));
+            let mut spans = vec![Span::styled(
+                "Labels: ",
+                Style::default().fg(self.theme.muted),
+            )];
+            for (idx, label) in bead.labels.iter().enumerate() {
+                if idx > 0 {
+                    spans.push(Span::styled(", ", Style::default().fg(self.theme.muted)));
+                }
+                spans.push(Span::styled(
+                    label.clone(),
+                    Style::default().fg(self.theme.label_color(label)),
+                ));
+            }
+            lines.push(Line::from(spans));
         }
 
         // Assignee
@@ -169,6 +489,12 @@ impl<'a> DetailPanel<'a> {
                 Span::styled(assignee.clone(), Style::default().fg(self.theme.fg)),
             ]));
         }
+        if let Some(ref creator) = bead.created_by {
+            lines.push(Line::from(vec![
+                Span::styled("Created by: ", Style::default().fg(self.theme.muted)),
+                Span::styled(creator.clone(), Style::default().fg(self.theme.fg)),
+            ]));
+        }
 
         lines.push(Line::raw(""));
 
@@ -186,21 +512,6 @@ impl<'a> DetailPanel<'a> {
             }
         }
 
-        // Comments section
-        if !bead.comments.is_empty() {
-            lines.push(Line::raw(""));
-            lines.push(Line::from(vec![Span::styled(
-                format!("Comments ({}):", bead.comments.len()),
-                Style::default()
-                    .fg(self.theme.fg)
-                    .add_modifier(Modifier::BOLD),
-            )]));
-            for comment in &bead.comments {
-                lines.push(Line::raw(""));
-                self.render_comment(&mut lines, comment);
-            }
-        }
-
         // Dependencies section
         if !bead.blocked_by.is_empty() {
             lines.push(Line::raw(""));
@@ -211,10 +522,11 @@ impl<'a> DetailPanel<'a> {
                     .add_modifier(Modifier::BOLD),
             )]));
             for id in &bead.blocked_by {
+                links.push((lines.len(), id.clone()));
                 lines.push(Line::from(vec![
                     Span::raw("  "),
                     Span::styled(
-                        format!("\u{2514}\u{2500} {}", id),
+                        format!("{}{}", glyphs::get().last_branch, id),
                         Style::default().fg(self.theme.status_blocked),
                     ),
                 ]));
@@ -230,10 +542,11 @@ impl<'a> DetailPanel<'a> {
                     .add_modifier(Modifier::BOLD),
             )]));
             for id in &bead.blocks {
+                links.push((lines.len(), id.clone()));
                 lines.push(Line::from(vec![
                     Span::raw("  "),
                     Span::styled(
-                        format!("\u{2514}\u{2500} {}", id),
+                        format!("{}{}", glyphs::get().last_branch, id),
                         Style::default().fg(self.theme.accent),
                     ),
                 ]));
@@ -249,10 +562,11 @@ impl<'a> DetailPanel<'a> {
                     .add_modifier(Modifier::BOLD),
             )]));
             for id in &bead.parent_ids {
+                links.push((lines.len(), id.clone()));
                 lines.push(Line::from(vec![
                     Span::raw("  "),
                     Span::styled(
-                        format!("\u{2514}\u{2500} {}", id),
+                        format!("{}{}", glyphs::get().last_branch, id),
                         Style::default().fg(self.theme.muted),
                     ),
                 ]));
@@ -264,22 +578,80 @@ impl<'a> DetailPanel<'a> {
         if let Some(created) = bead.created_at {
             lines.push(Line::from(vec![
                 Span::styled("Created: ", Style::default().fg(self.theme.muted)),
-                Span::styled(
-                    created.format("%Y-%m-%d %H:%M").to_string(),
-                    Style::default().fg(self.theme.fg),
-                ),
+                Span::styled(self.timestamp(created), Style::default().fg(self.theme.fg)),
             ]));
         }
         if let Some(updated) = bead.updated_at {
             lines.push(Line::from(vec![
                 Span::styled("Updated: ", Style::default().fg(self.theme.muted)),
-                Span::styled(
-                    updated.format("%Y-%m-%d %H:%M").to_string(),
-                    Style::default().fg(self.theme.fg),
-                ),
+                Span::styled(self.timestamp(updated), Style::default().fg(self.theme.fg)),
             ]));
         }
 
+        // Close details (only for closed beads)
+        if bead.status == BeadStatus::Closed {
+            let mut closed_line = vec![Span::styled(
+                "Closed: ",
+                Style::default().fg(self.theme.muted),
+            )];
+            if let Some(closed) = bead.closed_at {
+                closed_line.push(Span::styled(
+                    self.timestamp(closed),
+                    Style::default().fg(self.theme.status_closed),
+                ));
+            }
+            lines.push(Line::from(closed_line));
+            if let Some(ref reason) = bead.close_reason {
+                for reason_line in reason.lines() {
+                    lines.push(Line::from(vec![
+                        Span::raw("  "),
+                        Span::styled(
+                            reason_line.to_string(),
+                            Style::default().fg(self.theme.status_closed),
+                        ),
+                    ]));
+                }
+            }
+            // How long it took from creation to close
+            if let (Some(created), Some(closed)) = (bead.created_at, bead.closed_at) {
+                lines.push(Line::from(vec![
+                    Span::styled("Cycle time: ", Style::default().fg(self.theme.muted)),
+                    Span::styled(
+                        format_duration(closed - created),
+                        Style::default().fg(self.theme.fg),
+                    ),
+                ]));
+            }
+        }
+
+        // Comments section
+        if !bead.comments.is_empty() {
+            lines.push(Line::raw(""));
+            lines.push(Line::from(vec![Span::styled(
+                format!("Comments ({}):", bead.comments.len()),
+                Style::default()
+                    .fg(self.theme.fg)
+                    .add_modifier(Modifier::BOLD),
+            )]));
+            for (idx, comment) in bead.comments.iter().enumerate() {
+                if idx > 0 {
+                    // Muted rule between comments, inset to match the comment body
+                    let rule = &glyphs::get().rule;
+                    let rule_width = width.saturating_sub(4).max(1) as usize / rule.width().max(1);
+                    lines.push(Line::from(vec![
+                        Span::raw("  "),
+                        Span::styled(
+                            rule.repeat(rule_width),
+                            Style::default().fg(self.theme.border),
+                        ),
+                    ]));
+                } else {
+                    lines.push(Line::raw(""));
+                }
+                self.render_comment(&mut lines, comment);
+            }
+        }
+
         Text::from(lines)
     }
 }
@@ -298,7 +670,11 @@ impl StatefulWidget for DetailPanel<'_> {
             .borders(Borders::ALL)
             .border_set(border::ROUNDED)
             .border_style(border_style)
-            .title(" Detail ")
+            .title(if self.graph.is_some() {
+                " Dependencies "
+            } else {
+                " Detail "
+            })
             .title_style(
                 Style::default()
                     .fg(self.theme.fg)
@@ -310,13 +686,34 @@ impl StatefulWidget for DetailPanel<'_> {
 
         // Update viewport height in state
         state.viewport_height = inner.height;
+        state.follow(self.bead, self.graph.is_some());
 
+        state.links.clear();
         if let Some(bead) = self.bead {
-            let text = self.render_metadata(bead);
+            let mut links = Vec::new();
+            let text = match self.graph {
+                Some(beads) => self.render_graph(bead, beads, &mut links),
+                None => self.render_metadata(bead, inner.width, &mut links),
+            };
 
             // Update content height in state
             state.content_height = text.lines.len() as u16;
 
+            // Map linked lines to the rows they wrap onto
+            let mut row = 0u16;
+            let mut links = links.into_iter().peekable();
+            for (idx, line) in text.lines.iter().enumerate() {
+                if links.peek().is_some_and(|(link, _)| *link == idx)
+                    && let Some((_, id)) = links.next()
+                {
+                    state.links.push((row, id));
+                }
+                let height = Paragraph::new(line.clone())
+                    .wrap(Wrap { trim: false })
+                    .line_count(inner.width);
+                row = row.saturating_add(height as u16);
+            }
+
             let para = Paragraph::new(text)
                 .wrap(Wrap { trim: false })
                 .scroll((state.scroll, 0));
@@ -332,3 +729,127 @@ impl StatefulWidget for DetailPanel<'_> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bead(id: &str, blocked_by: &[&str]) -> Bead {
+        Bead {
+            id: id.to_string(),
+            blocked_by: blocked_by.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn relative_time_picks_the_largest_unit() {
+        let now = Utc::now();
+        let ago = |secs: i64| relative_time(now - chrono::Duration::seconds(secs), now);
+        assert_eq!(ago(5), "just now");
+        assert_eq!(ago(-30), "just now");
+        assert_eq!(ago(5 * 60), "5m ago");
+        assert_eq!(ago(2 * 3_600 + 59), "2h ago");
+        assert_eq!(ago(86_400), "1 day ago");
+        assert_eq!(ago(3 * 86_400), "3 days ago");
+        assert_eq!(ago(65 * 86_400), "2 months ago");
+        assert_eq!(ago(400 * 86_400), "1 year ago");
+    }
+
+    #[test]
+    fn durations_show_their_two_largest_units() {
+        let minutes = chrono::Duration::minutes;
+        assert_eq!(format_duration(minutes(40)), "40m");
+        assert_eq!(format_duration(minutes(135)), "2h 15m");
+        assert_eq!(format_duration(minutes((4 * 24 + 3) * 60 + 59)), "4d 3h");
+        assert_eq!(format_duration(minutes(-5)), "0m");
+    }
+
+    #[test]
+    fn half_page_follows_viewport_height() {
+        let mut state = DetailState::new();
+        assert_eq!(state.half_page(), 1);
+        state.viewport_height = 31;
+        assert_eq!(state.half_page(), 15);
+    }
+
+    #[test]
+    fn dependency_lines_link_to_their_beads_after_wrapping() {
+        let mut selected = bead("bd-1", &["bd-2"]);
+        // Wraps onto several rows in a narrow panel, pushing the link down
+        selected.description = Some("word ".repeat(20));
+        let area = Rect::new(0, 0, 22, 40);
+        let mut buf = Buffer::empty(area);
+        let mut state = DetailState::new();
+        DetailPanel::new(Some(&selected), &crate::ui::theme::LAZYGIT)
+            .render(area, &mut buf, &mut state);
+
+        let (row, id) = state.links[0].clone();
+        assert_eq!(id, "bd-2");
+        let text: String = (0..area.width)
+            .map(|x| buf[(x, row + 1)].symbol().to_string())
+            .collect();
+        assert!(text.contains("bd-2"), "row {} is {:?}", row, text);
+        assert_eq!(state.link_at(row), Some("bd-2"));
+        assert_eq!(state.link_at(row + 1), None);
+
+        state.scroll_down(2);
+        assert_eq!(state.link_at(row - 2), Some("bd-2"));
+    }
+
+    #[test]
+    fn scroll_is_remembered_per_bead_until_its_content_changes() {
+        let first = bead("bd-1", &[]);
+        let mut second = bead("bd-2", &[]);
+        let mut state = DetailState::new();
+
+        state.follow(Some(&first), false);
+        state.scroll_down(7);
+        state.follow(Some(&second), false);
+        assert_eq!(state.scroll(), 0);
+        state.scroll_down(3);
+
+        state.follow(Some(&first), false);
+        assert_eq!(state.scroll(), 7);
+        // The graph view has its own offset, starting at the top
+        state.follow(Some(&first), true);
+        assert_eq!(state.scroll(), 0);
+        state.follow(Some(&first), false);
+        assert_eq!(state.scroll(), 7);
+
+        second.comments.push(Comment {
+            author: "ann".to_string(),
+            text: "new".to_string(),
+            created_at: None,
+        });
+        state.follow(Some(&second), false);
+        assert_eq!(state.scroll(), 0);
+    }
+
+    #[test]
+    fn graph_walks_transitive_blockers_and_stops_at_cycles() {
+        let beads = [
+            bead("bd-1", &["bd-2", "bd-3"]),
+            bead("bd-2", &["bd-4"]),
+            bead("bd-3", &[]),
+            bead("bd-4", &["bd-1"]),
+        ];
+        let by_id: HashMap<&str, &Bead> = beads.iter().map(|b| (b.id.as_str(), b)).collect();
+
+        let nodes = graph_lines(&by_id, &beads[0], |b| &b.blocked_by);
+        let rendered: Vec<String> = nodes
+            .iter()
+            .map(|n| format!("{}{}{}", n.prefix, n.id, if n.cycle { "*" } else { "" }))
+            .collect();
+
+        assert_eq!(
+            rendered,
+            vec![
+                "\u{251c}\u{2500} bd-2",
+                "\u{2502}  \u{2514}\u{2500} bd-4",
+                "\u{2502}     \u{2514}\u{2500} bd-1*",
+                "\u{2514}\u{2500} bd-3",
+            ]
+        );
+    }
+}
@@ -0,0 +1,202 @@
+//! Label filter picker - every label in use with its bead count
+//!
+//! Several labels can be ticked; the list then keeps beads carrying any (or
+//! all) of them.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    Frame,
+    layout::Rect,
+    style::{Modifier, Style},
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::data::{Bead, LabelMatch, label_counts};
+use crate::ui::Theme;
+use crate::ui::layout::centered_rect;
+
+/// Most labels shown at once; the rest scroll
+const MAX_ROWS: usize = 12;
+
+/// State for the label filter picker
+#[derive(Debug, Clone, Default)]
+pub struct LabelPicker {
+    /// Labels in use with their bead counts, most used first
+    pub labels: Vec<(String, usize)>,
+    /// Highlighted row
+    pub cursor: usize,
+    /// Ticked labels
+    pub chosen: Vec<String>,
+    /// Whether beads need any or all of the chosen labels
+    pub mode: LabelMatch,
+}
+
+/// What to do after the picker handles a key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PickerAction {
+    None,
+    /// Filter by these labels (empty clears the filter)
+    Apply(Vec<String>),
+    Cancelled,
+}
+
+impl LabelPicker {
+    /// Fill the picker with the labels on `beads`, ticking the current filter
+    pub fn open(&mut self, beads: &[Bead], current: &[String], mode: LabelMatch) {
+        self.labels = label_counts(beads);
+        self.chosen = current.to_vec();
+        self.mode = mode;
+        self.cursor = self
+            .labels
+            .iter()
+            .position(|(label, _)| current.contains(label))
+            .unwrap_or(0);
+    }
+
+    /// Labels to filter by: the ticked ones in list order, or the highlighted
+    /// one if none are ticked
+    pub fn selection(&self) -> Vec<String> {
+        if self.chosen.is_empty() {
+            return self
+                .labels
+                .get(self.cursor)
+                .map(|(label, _)| vec![label.clone()])
+                .unwrap_or_default();
+        }
+        self.labels
+            .iter()
+            .map(|(label, _)| label)
+            .filter(|label| self.chosen.contains(label))
+            .cloned()
+            .collect()
+    }
+
+    /// Handle a key event
+    pub fn handle_key(&mut self, key: KeyEvent) -> PickerAction {
+        let last = self.labels.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc => return PickerAction::Cancelled,
+            KeyCode::Enter => return PickerAction::Apply(self.selection()),
+            KeyCode::Backspace => return PickerAction::Apply(Vec::new()),
+            KeyCode::Char('j') | KeyCode::Down => self.cursor = (self.cursor + 1).min(last),
+            KeyCode::Char('k') | KeyCode::Up => self.cursor = self.cursor.saturating_sub(1),
+            KeyCode::Char('g') | KeyCode::Home => self.cursor = 0,
+            KeyCode::Char('G') | KeyCode::End => self.cursor = last,
+            KeyCode::Tab | KeyCode::BackTab => self.mode = self.mode.toggle(),
+            KeyCode::Char(' ') => {
+                if let Some((label, _)) = self.labels.get(self.cursor) {
+                    match self.chosen.iter().position(|c| c == label) {
+                        Some(i) => {
+                            self.chosen.remove(i);
+                        }
+                        None => self.chosen.push(label.clone()),
+                    }
+                }
+            }
+            _ => {}
+        }
+        PickerAction::None
+    }
+}
+
+/// Render the label picker as a centered modal
+pub fn render_label_picker(frame: &mut Frame, area: Rect, theme: &Theme, picker: &LabelPicker) {
+    // Keep the highlighted row in view
+    let rows = picker.labels.len().min(MAX_ROWS);
+    let offset = (picker.cursor + 1).saturating_sub(rows);
+
+    let lines: Vec<Line> = picker
+        .labels
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(rows)
+        .map(|(i, (label, count))| {
+            let ticked = picker.chosen.contains(label);
+            let (style, label_style) = if i == picker.cursor {
+                let style = theme.selection_style().fg(theme.selection_fg);
+                (style, style)
+            } else {
+                (
+                    Style::default().fg(theme.fg),
+                    Style::default().fg(theme.label_color(label)),
+                )
+            };
+            Line::from(vec![
+                Span::styled(if ticked { "[x] " } else { "[ ] " }, style),
+                Span::styled(label.clone(), label_style.add_modifier(Modifier::BOLD)),
+                Span::styled(format!("  {}", count), style.fg(theme.muted)),
+            ])
+        })
+        .collect();
+
+    let width = 44.min(area.width.saturating_sub(4));
+    let modal_area = centered_rect(area, width, rows as u16 + 2);
+
+    frame.render_widget(Clear, modal_area);
+    let para = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_set(border::ROUNDED)
+                .border_style(Style::default().fg(theme.focused_border))
+                .title(format!(" Filter by Label ─── match {} ", picker.mode))
+                .title_style(Style::default().fg(theme.fg).add_modifier(Modifier::BOLD)),
+        )
+        .style(Style::default().bg(theme.bg).fg(theme.fg));
+    frame.render_widget(para, modal_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LabelPicker, PickerAction};
+    use crate::data::{Bead, LabelMatch};
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    fn press(picker: &mut LabelPicker, code: KeyCode) -> PickerAction {
+        picker.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn enter_applies_ticked_labels_or_the_highlighted_one() {
+        let labeled = |labels: &[&str]| Bead {
+            labels: labels.iter().map(|l| l.to_string()).collect(),
+            ..Default::default()
+        };
+        let beads = [
+            labeled(&["ui", "bug"]),
+            labeled(&["ui"]),
+            labeled(&["infra"]),
+        ];
+        let mut picker = LabelPicker::default();
+        picker.open(&beads, &[], LabelMatch::Any);
+        assert_eq!(picker.labels[0], ("ui".to_string(), 2));
+
+        press(&mut picker, KeyCode::Char('j'));
+        assert_eq!(
+            press(&mut picker, KeyCode::Enter),
+            PickerAction::Apply(vec!["bug".to_string()])
+        );
+
+        // Ticked labels come back in list order
+        press(&mut picker, KeyCode::Char(' '));
+        press(&mut picker, KeyCode::Char('k'));
+        press(&mut picker, KeyCode::Char(' '));
+        press(&mut picker, KeyCode::Tab);
+        assert_eq!(picker.mode, LabelMatch::All);
+        assert_eq!(
+            press(&mut picker, KeyCode::Enter),
+            PickerAction::Apply(vec!["ui".to_string(), "bug".to_string()])
+        );
+
+        // Reopening ticks the current filter
+        picker.open(&beads, &["infra".to_string()], LabelMatch::Any);
+        assert_eq!(picker.cursor, 2);
+        assert_eq!(
+            press(&mut picker, KeyCode::Backspace),
+            PickerAction::Apply(Vec::new())
+        );
+    }
+}
@@ -1,19 +1,28 @@
 //! Main layout for beads-tui
 
+use std::ops::RangeInclusive;
+
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     symbols::border,
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
 };
+use tui_textarea::TextArea;
 
 use crate::app::InputMode;
-use crate::data::{Bead, build_tree_order};
+use crate::data::{
+    Bead, BeadStats, BeadStatus, DependencyType, GroupBy, TreeOptions, build_tree_order,
+    filter_regex,
+};
 use crate::ui::Theme;
+use crate::ui::board::{Board, BoardState};
 use crate::ui::create_modal::{CreateModal, render_create_modal};
-use crate::ui::detail::{DetailPanel, DetailState};
-use crate::ui::list::{BeadList, BeadListState};
+use crate::ui::detail::{DetailPanel, DetailState, format_duration};
+use crate::ui::label_picker::{LabelPicker, render_label_picker};
+use crate::ui::list::{BeadList, BeadListState, Density, truncate_to_width};
+use crate::views::View;
 
 /// Which pane is currently focused
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -23,9 +32,43 @@ pub enum Focus {
     Detail,
 }
 
+/// Kind of transient footer message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageKind {
+    Info,
+    Error,
+}
+
+/// Auto-refresh state shown in the footer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefreshState {
+    Live,
+    /// An automatic refresh reloaded the beads a moment ago
+    JustRefreshed,
+    /// Disabled with `--refresh 0` or paused
+    Off,
+}
+
 /// Minimum width to show both panes
 const MIN_DUAL_PANE_WIDTH: u16 = 60;
 
+/// Smallest terminal the layout is drawn in; below it only a notice is shown
+const MIN_WIDTH: u16 = 40;
+const MIN_HEIGHT: u16 = 10;
+
+/// A `width` x `height` rect centered in `area`, shrunk to fit if the
+/// terminal is smaller than that
+pub fn centered_rect(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
 /// Render the main application layout
 /// Returns (list_area, detail_area) for mouse handling
 #[allow(clippy::too_many_arguments)]
@@ -37,34 +80,99 @@ pub fn render_layout(
     theme: &Theme,
     focus: Focus,
     split_percent: u16,
-    filter: Option<&str>,
+    tree_options: &TreeOptions,
     show_help: bool,
-    hide_closed: bool,
+    stats: Option<(&BeadStats, bool)>,
+    board: Option<&mut BoardState>,
     show_labels: bool,
+    show_assignee: bool,
+    show_recency: bool,
+    wrap_titles: bool,
+    density: Density,
     show_detail: bool,
+    show_graph: bool,
+    relative_times: bool,
+    read_only: bool,
     input_mode: InputMode,
-    search_text: &str,
-    search_cursor: usize,
+    search_input: &TextArea<'static>,
     create_modal: &CreateModal,
+    label_picker: &LabelPicker,
     reason_text: &str,
-    reason_cursor: usize,
-    comment_text: &str,
-    comment_cursor: usize,
+    reason_cursor: (usize, usize),
+    comment_input: &TextArea<'static>,
+    assignee_text: &str,
+    assignee_cursor: usize,
+    jump_text: &str,
+    jump_cursor: usize,
+    dep_text: &str,
+    dep_cursor: usize,
+    dep_type: DependencyType,
+    label_text: &str,
+    label_cursor: usize,
+    export_text: &str,
+    export_cursor: usize,
+    export_bead: Option<&str>,
+    views: &[(String, View)],
+    view_pick: usize,
+    view_text: &str,
+    view_cursor: usize,
+    workspaces: &[(String, String)],
+    workspace_pick: usize,
+    marked: Option<RangeInclusive<usize>>,
+    confirm_close_children: &[String],
+    cycles: &[Vec<String>],
+    stale_after: Option<chrono::Duration>,
+    scrolloff: usize,
+    status_message: Option<(&str, MessageKind)>,
+    refresh_state: RefreshState,
+    busy: Option<&str>,
+    banner: Option<&str>,
 ) -> (Rect, Rect) {
     let area = frame.area();
+    if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+        render_too_small(frame, area, theme);
+        return (Rect::default(), Rect::default());
+    }
     let is_narrow = area.width < MIN_DUAL_PANE_WIDTH;
+    let searching = input_mode == InputMode::Search;
+    let summary = filter_summary(tree_options, theme);
 
-    // Main vertical layout: content + footer (no header)
+    // Main vertical layout: content + optional warning banner + what's filtered
+    // + search bar while typing a search + footer (no header)
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Min(3),    // Main content
-            Constraint::Length(1), // Footer
+            Constraint::Min(3),                                        // Main content
+            Constraint::Length(if banner.is_some() { 1 } else { 0 }),  // Banner
+            Constraint::Length(if summary.is_some() { 1 } else { 0 }), // Filters
+            Constraint::Length(if searching { 1 } else { 0 }),         // Search bar
+            Constraint::Length(1),                                     // Footer
         ])
         .split(area);
 
-    // Determine layout based on show_detail and terminal width
-    let (list_area, detail_area) = if !show_detail {
+    if let Some(text) = banner {
+        let banner = Paragraph::new(Line::from(Span::styled(
+            format!(" {} ", text),
+            Style::default()
+                .fg(theme.status_blocked)
+                .add_modifier(Modifier::BOLD),
+        )));
+        frame.render_widget(banner, chunks[1]);
+    }
+
+    if let Some(summary) = summary {
+        frame.render_widget(Paragraph::new(summary), chunks[2]);
+    }
+
+    if searching {
+        render_search_bar(frame, chunks[3], theme, search_input);
+    }
+
+    // Determine layout based on show_detail and terminal width (the board
+    // takes the whole content area instead)
+    let (list_area, detail_area) = if board.is_some() {
+        (Rect::default(), Rect::default())
+    } else if !show_detail {
         // Only show list (full width)
         (chunks[0], Rect::default())
     } else if is_narrow {
@@ -82,39 +190,88 @@ pub fn render_layout(
         (content_chunks[0], content_chunks[1])
     };
 
+    let tree_order = build_tree_order(beads, tree_options);
+    let selected = list_state
+        .selected()
+        .and_then(|i| tree_order.get(i).map(|(b, _)| (i, *b)));
+
     // Render bead list (if visible)
     if list_area.width > 0 {
         let list = BeadList::new(beads, theme)
             .focused(focus == Focus::List)
-            .filter(filter)
-            .hide_closed(hide_closed)
-            .show_labels(show_labels);
+            .options(tree_options)
+            .show_labels(show_labels)
+            .show_assignee(show_assignee)
+            .show_recency(show_recency)
+            .wrap_titles(wrap_titles)
+            .density(density)
+            .read_only(read_only)
+            .marked(marked.clone())
+            .cycles(cycles)
+            .stale_after(stale_after)
+            .scrolloff(scrolloff);
         frame.render_stateful_widget(list, list_area, list_state);
     }
 
+    if let Some(board_state) = board {
+        let board = Board::new(tree_order.iter().map(|(b, _)| *b).collect(), beads, theme)
+            .selected(selected.map(|(_, b)| b.id.as_str()))
+            .closed_hidden(
+                tree_options.hide_closed && tree_options.status != Some(BeadStatus::Closed),
+            );
+        frame.render_stateful_widget(board, chunks[0], board_state);
+    }
+
     // Render detail panel (if visible)
     if detail_area.width > 0 {
-        let tree_order = build_tree_order(beads, hide_closed, filter);
-        let selected_bead = list_state
-            .selected()
-            .and_then(|i| tree_order.get(i).map(|(b, _)| *b));
-        let detail = DetailPanel::new(selected_bead, theme).focused(focus == Focus::Detail);
+        let selected_bead = selected.map(|(_, b)| b);
+        let cycle = selected_bead.and_then(|b| cycles.iter().find(|c| c.contains(&b.id)));
+        let detail = DetailPanel::new(selected_bead, theme)
+            .focused(focus == Focus::Detail)
+            .cycle(cycle.map(Vec::as_slice))
+            .dependency_graph(show_graph.then_some(beads))
+            .relative_times(relative_times);
         frame.render_stateful_widget(detail, detail_area, detail_state);
     }
 
+    // Selected bead's full ID and position, on the list's bottom border (or the
+    // detail pane's when the list is hidden)
+    if let Some((idx, bead)) = selected
+        && (list_area.width > 0 || detail_area.width > 0)
+    {
+        let pane = if list_area.width > 0 {
+            list_area
+        } else {
+            detail_area
+        };
+        render_position(frame, pane, theme, &bead.id, (idx + 1, tree_order.len()));
+    }
+
     // Render footer
+    let visible_count = tree_order.len();
+    let marked_count = marked.map_or(0, |rows| rows.count());
     render_footer(
         frame,
-        chunks[1],
+        chunks[4],
         theme,
         input_mode,
-        search_text,
-        search_cursor,
-        hide_closed,
+        tree_options,
         show_detail,
         focus,
+        status_message,
+        (visible_count, beads.len()),
+        marked_count,
+        read_only,
+        create_modal.confirm_discard,
+        create_modal.offered_draft.is_some(),
+        refresh_state,
+        busy,
     );
 
+    if let Some((stats, visible_only)) = stats {
+        render_stats_overlay(frame, area, theme, stats, visible_only);
+    }
+
     // Render help overlay if needed
     if show_help {
         render_help_overlay(frame, area, theme);
@@ -127,16 +284,34 @@ pub fn render_layout(
 
     // Render reason input modal if closing or reopening
     if input_mode == InputMode::ClosingBead {
+        let title = match marked_count {
+            0 => "Close Bead - Enter Reason".to_string(),
+            n => format!("Close {} Beads - Enter Reason", n),
+        };
+        render_multiline_modal(frame, area, theme, &title, reason_text, reason_cursor);
+    } else if input_mode == InputMode::Exporting {
         render_reason_modal(
             frame,
             area,
             theme,
-            "Close Bead - Enter Reason",
-            reason_text,
-            reason_cursor,
+            &match export_bead {
+                Some(id) => format!("Write {} to Text File", id),
+                None => format!("Export {} Beads to Markdown File", visible_count),
+            },
+            export_text,
+            export_cursor,
         );
-    } else if input_mode == InputMode::ReopeningBead {
+    } else if input_mode == InputMode::AddingLabel {
         render_reason_modal(
+            frame,
+            area,
+            theme,
+            &format!("Add Label to {} Beads", marked_count),
+            label_text,
+            label_cursor,
+        );
+    } else if input_mode == InputMode::ReopeningBead {
+        render_multiline_modal(
             frame,
             area,
             theme,
@@ -145,13 +320,71 @@ pub fn render_layout(
             reason_cursor,
         );
     } else if input_mode == InputMode::AddingComment {
+        render_comment_modal(frame, area, theme, comment_input);
+    } else if input_mode == InputMode::JumpToId {
+        render_reason_modal(
+            frame,
+            area,
+            theme,
+            "Jump to Bead ID",
+            jump_text,
+            jump_cursor,
+        );
+    } else if input_mode == InputMode::FilteringLabels {
+        render_label_picker(frame, area, theme, label_picker);
+    } else if input_mode == InputMode::SavingView {
+        render_reason_modal(frame, area, theme, "Save View As", view_text, view_cursor);
+    } else if input_mode == InputMode::PickingView {
+        let rows: Vec<(String, String)> = views
+            .iter()
+            .map(|(name, view)| {
+                let spec = view.to_string();
+                let spec = if spec.is_empty() {
+                    "(no filters)".to_string()
+                } else {
+                    spec
+                };
+                (name.clone(), spec)
+            })
+            .collect();
+        render_picker(frame, area, theme, " Views ", &rows, view_pick);
+    } else if input_mode == InputMode::PickingWorkspace {
+        render_picker(
+            frame,
+            area,
+            theme,
+            " Workspaces ",
+            workspaces,
+            workspace_pick,
+        );
+    } else if input_mode == InputMode::ConfirmClose {
+        render_confirm_close_modal(frame, area, theme, confirm_close_children);
+    } else if input_mode == InputMode::AddingDependency {
+        render_reason_modal(
+            frame,
+            area,
+            theme,
+            &format!("Add Dependency - {} (Tab: type)", dep_type.describe()),
+            dep_text,
+            dep_cursor,
+        );
+    } else if input_mode == InputMode::RemovingDependency {
+        render_reason_modal(
+            frame,
+            area,
+            theme,
+            "Remove Dependency on Bead ID",
+            dep_text,
+            dep_cursor,
+        );
+    } else if input_mode == InputMode::Assigning {
         render_reason_modal(
             frame,
             area,
             theme,
-            "Add Comment - Enter to Submit",
-            comment_text,
-            comment_cursor,
+            "Assign Bead - Empty to Clear",
+            assignee_text,
+            assignee_cursor,
         );
     }
 
@@ -164,33 +397,92 @@ fn render_footer(
     area: Rect,
     theme: &Theme,
     input_mode: InputMode,
-    input_text: &str,
-    input_cursor: usize,
-    hide_closed: bool,
+    tree_options: &TreeOptions,
     show_detail: bool,
     focus: Focus,
+    status_message: Option<(&str, MessageKind)>,
+    (visible_count, total_count): (usize, usize),
+    marked_count: usize,
+    read_only: bool,
+    confirm_discard: bool,
+    offered_draft: bool,
+    refresh_state: RefreshState,
+    busy: Option<&str>,
 ) {
     // Lazygit-style footer: "Key: desc | Key: desc | ..."
-    let closed_label = if hide_closed {
+    let closed_label = if tree_options.hide_closed {
         "show closed"
     } else {
         "hide closed"
     };
     let keys: Vec<(&str, &str)> = match input_mode {
-        InputMode::Search => vec![("Esc", "cancel"), ("Enter", "confirm")],
+        InputMode::Search => vec![("Esc", "cancel"), ("Enter", "confirm"), ("↑/↓", "history")],
+        InputMode::Creating if offered_draft => {
+            vec![("y", "restore draft"), ("n", "start fresh")]
+        }
+        InputMode::Creating | InputMode::Editing if confirm_discard => {
+            vec![("y", "discard changes"), ("any key", "keep editing")]
+        }
         InputMode::Creating => vec![("Esc", "cancel"), ("Tab", "next field"), ("C-s", "create")],
         InputMode::Editing => vec![("Esc", "cancel"), ("Tab", "next field"), ("C-s", "save")],
         InputMode::ClosingBead | InputMode::ReopeningBead => {
-            vec![("Esc", "cancel"), ("Enter", "confirm")]
+            vec![
+                ("Esc", "cancel"),
+                ("Enter", "confirm"),
+                ("S-Enter/C-j", "new line"),
+            ]
+        }
+        InputMode::AddingComment => {
+            vec![
+                ("Esc", "cancel"),
+                ("Enter", "add"),
+                ("S-Enter/C-j", "new line"),
+            ]
+        }
+        InputMode::Assigning => vec![("Esc", "cancel"), ("Enter", "assign")],
+        InputMode::AddingLabel => vec![("Esc", "cancel"), ("Enter", "label")],
+        InputMode::Exporting => vec![("Esc", "cancel"), ("Enter", "export")],
+        InputMode::SavingView => vec![("Esc", "cancel"), ("Enter", "save")],
+        InputMode::PickingView | InputMode::PickingWorkspace => {
+            vec![("j/k", "move"), ("Enter", "switch"), ("Esc", "cancel")]
+        }
+        InputMode::JumpToId => vec![("Esc", "cancel"), ("Enter", "jump")],
+        InputMode::AddingDependency => {
+            vec![("Esc", "cancel"), ("Tab", "type"), ("Enter", "add")]
         }
-        InputMode::AddingComment => vec![("Esc", "cancel"), ("Enter", "add")],
+        InputMode::RemovingDependency => vec![("Esc", "cancel"), ("Enter", "remove")],
+        InputMode::ConfirmClose => vec![("y", "close anyway"), ("n/Esc", "cancel")],
+        InputMode::ConfirmQuit => vec![("y", "quit bu"), ("any key", "stay")],
+        InputMode::PriorityFilter => vec![("0-4", "max priority"), ("Esc", "clear")],
+        InputMode::FilteringLabels => vec![
+            ("j/k", "move"),
+            ("Space", "tick"),
+            ("Tab", "any/all"),
+            ("Enter", "filter"),
+            ("Bksp", "clear"),
+            ("Esc", "cancel"),
+        ],
+        InputMode::SettingPriority => vec![("0-4", "set priority"), ("Esc", "cancel")],
+        InputMode::Normal if marked_count > 0 => vec![
+            ("j/k", "extend"),
+            ("x", "close"),
+            ("+", "label"),
+            ("p", "priority"),
+            ("V/Esc", "clear"),
+        ],
         InputMode::Normal if show_detail && focus == Focus::Detail => vec![
             ("j/k", "scroll"),
             ("Esc/h", "close"),
             ("c", "comment"),
             ("e", "edit"),
             ("x", "close/reopen"),
+            ("i", "start/stop"),
             ("D", "defer/undefer"),
+            ("@", "assign"),
+            ("+/-", "deps"),
+            ("B", "graph"),
+            ("W", "write"),
+            ("p", "priority"),
             ("L", "labels"),
             ("?", "help"),
             ("q", "quit"),
@@ -199,15 +491,56 @@ fn render_footer(
             ("j/k", "nav"),
             ("Enter/l", "open"),
             ("a", "add"),
+            ("p", "priority"),
             ("c", closed_label),
+            ("s", "status"),
+            ("R", "ready"),
+            ("#", "label filter"),
+            ("o", "sort"),
             ("L", "labels"),
             ("/", "filter"),
+            (":", "jump"),
+            ("y", "copy id"),
             ("?", "help"),
             ("q", "quit"),
         ],
     };
 
+    // Hide hints for actions that read-only mode disables
+    let keys: Vec<(&str, &str)> = if read_only && input_mode == InputMode::Normal {
+        let mutating: &[&str] = if marked_count > 0 {
+            &["x", "+", "p"]
+        } else if show_detail && focus == Focus::Detail {
+            &["c", "e", "x", "i", "D", "@", "+/-", "p"]
+        } else {
+            &["a", "p"]
+        };
+        keys.into_iter()
+            .filter(|(key, _)| !mutating.contains(key))
+            .collect()
+    } else {
+        keys
+    };
+
     let mut spans: Vec<Span> = Vec::new();
+    if read_only {
+        spans.push(Span::styled(
+            "READ-ONLY",
+            Style::default()
+                .fg(theme.status_blocked)
+                .add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::styled("  |  ", Style::default().fg(theme.border)));
+    }
+    if marked_count > 0 && input_mode == InputMode::Normal {
+        spans.push(Span::styled(
+            format!("{} SELECTED", marked_count),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::styled("  |  ", Style::default().fg(theme.border)));
+    }
 
     for (i, (key, desc)) in keys.iter().enumerate() {
         if i > 0 {
@@ -223,52 +556,336 @@ fn render_footer(
         ));
     }
 
-    // Show input text if in search mode
-    if input_mode == InputMode::Search {
-        spans.push(Span::styled("  |  ", Style::default().fg(theme.border)));
-        spans.push(Span::styled("/", Style::default().fg(theme.accent)));
+    // A transient status message replaces the key hints while it is shown.
+    // Errors show in any mode, since a failed action may leave its prompt open.
+    match status_message {
+        Some((message, MessageKind::Error)) => {
+            spans = vec![Span::styled(
+                format!("Error: {}", message),
+                Style::default()
+                    .fg(theme.status_blocked)
+                    .add_modifier(Modifier::BOLD),
+            )];
+        }
+        Some((message, MessageKind::Info)) if input_mode == InputMode::Normal => {
+            spans = vec![Span::styled(
+                message.to_string(),
+                Style::default()
+                    .fg(theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            )];
+        }
+        _ => {}
+    }
 
-        // Show text with cursor
-        let (before, after) = input_text.split_at(input_cursor.min(input_text.len()));
-        spans.push(Span::styled(
-            before.to_string(),
-            Style::default().fg(theme.fg),
-        ));
-        spans.push(Span::styled(
-            "\u{2588}".to_string(), // Block cursor
-            Style::default().fg(theme.accent),
-        ));
-        spans.push(Span::styled(
-            after.to_string(),
-            Style::default().fg(theme.fg),
-        ));
-    } else if input_mode == InputMode::Normal && !input_text.is_empty() {
-        // Show active filter
+    // The filters have their own line above; the sort order goes here
+    if input_mode == InputMode::Normal {
         spans.push(Span::styled("  |  ", Style::default().fg(theme.border)));
         spans.push(Span::styled(
-            format!("filter: {}", input_text),
-            Style::default().fg(theme.fg),
+            format!(
+                "sort: {}{}",
+                tree_options.sort,
+                if tree_options.reverse { " (rev)" } else { "" }
+            ),
+            Style::default().fg(theme.muted),
         ));
+        if tree_options.group_by != GroupBy::None {
+            spans.push(Span::styled(
+                format!("  group: {}", tree_options.group_by),
+                Style::default().fg(theme.muted),
+            ));
+        } else if tree_options.flat {
+            spans.push(Span::styled("  flat", Style::default().fg(theme.muted)));
+        }
     }
 
-    // Calculate left side text width to see if we have room for version
+    // Calculate left side text width to see if we have room for the right side
     let left_text = Line::from(spans.clone());
     let left_width = left_text.width() as u16;
 
-    // Version info (right-aligned if there's room)
+    // Visible/total bead count and version info (right-aligned if there's room).
+    // The count is more useful, so the version is dropped first.
+    let count_text = format!("{}/{}", visible_count, total_count);
     let version = env!("CARGO_PKG_VERSION");
     let version_text = format!("beads-tui {}", version);
-    let version_width = version_text.len() as u16;
+    let count_width = count_text.len() as u16;
+    let both_width = count_width + 2 + version_text.len() as u16;
 
-    // Only show version if there's at least 5 chars of padding between left and right
-    if left_width + version_width + 5 <= area.width {
-        let padding_width = area.width.saturating_sub(left_width + version_width);
+    // The busy spinner and refresh marker go at the far right and always show,
+    // over the end of the key hints if they're too long
+    let mut markers = Vec::new();
+    if let Some(busy) = busy {
+        markers.push(Span::raw("  "));
+        markers.push(Span::styled(busy, Style::default().fg(theme.accent)));
+    }
+    match refresh_state {
+        RefreshState::Live => {}
+        RefreshState::JustRefreshed => {
+            markers.push(Span::raw("  "));
+            markers.push(Span::styled("⟳", Style::default().fg(theme.accent)));
+        }
+        RefreshState::Off => {
+            markers.push(Span::raw("  "));
+            markers.push(Span::styled(
+                "⏸ refresh off",
+                Style::default().fg(theme.status_deferred),
+            ));
+        }
+    }
+    let marker_width = Line::from(markers.clone()).width() as u16;
+    let reserved = left_width + marker_width;
+
+    // Only show the right side if there's at least 5 chars of padding between left and right
+    let mut right = Vec::new();
+    if reserved + both_width + 5 <= area.width {
+        right.push(Span::styled(count_text, Style::default().fg(theme.fg)));
+        right.push(Span::raw("  "));
+        right.push(Span::styled(version_text, Style::default().fg(theme.muted)));
+    } else if reserved + count_width + 5 <= area.width {
+        right.push(Span::styled(count_text, Style::default().fg(theme.fg)));
+    }
+    if !right.is_empty() {
+        let right_width = Line::from(right.clone()).width() as u16 + marker_width;
+        let padding_width = area.width.saturating_sub(left_width + right_width);
         spans.push(Span::raw(" ".repeat(padding_width as usize)));
-        spans.push(Span::styled(version_text, Style::default().fg(theme.muted)));
+        spans.extend(right);
     }
 
     let footer = Paragraph::new(Line::from(spans));
     frame.render_widget(footer, area);
+
+    if !markers.is_empty() {
+        let width = marker_width.min(area.width);
+        let marker_area = Rect::new(area.right() - width, area.y, width, 1);
+        let line = Line::from(markers).right_aligned();
+        frame.render_widget(Clear, marker_area);
+        frame.render_widget(Paragraph::new(line), marker_area);
+    }
+}
+
+/// One line naming every filter narrowing the list (status, labels,
+/// priority, ready, shown closed beads and the search), or None when it's
+/// the default view.
+fn filter_summary(options: &TreeOptions, theme: &Theme) -> Option<Line<'static>> {
+    let mut parts = Vec::new();
+    if let Some(status) = options.status {
+        parts.push(Span::styled(
+            format!("{} {}", status.icon(), status),
+            Style::default().fg(theme.status_color(status)),
+        ));
+    }
+    if !options.labels.is_empty() {
+        let mode = if options.labels.len() > 1 {
+            format!(" ({})", options.label_match)
+        } else {
+            String::new()
+        };
+        parts.push(Span::styled(
+            format!("label:{}{}", options.labels.join(","), mode),
+            Style::default().fg(theme.fg),
+        ));
+    }
+    if let Some(priority) = options.max_priority {
+        parts.push(Span::styled(
+            format!("P\u{2264}{}", priority),
+            Style::default().fg(theme.priority_color(priority)),
+        ));
+    }
+    if options.ready_only {
+        parts.push(Span::styled(
+            "ready only",
+            Style::default().fg(theme.status_in_progress),
+        ));
+    }
+    if !options.hide_closed && options.status.is_none() {
+        parts.push(Span::styled(
+            "+closed",
+            Style::default().fg(theme.status_closed),
+        ));
+    }
+    if let Some(text) = options.filter.as_deref().filter(|text| !text.is_empty()) {
+        parts.push(Span::styled(
+            format!("\"{}\"", text),
+            Style::default().fg(theme.fg),
+        ));
+        // A /regex/ that doesn't compile matches nothing; say so instead of an empty list
+        if filter_regex(text).is_some_and(|re| re.is_err()) {
+            parts.push(Span::styled(
+                "bad regex",
+                Style::default()
+                    .fg(theme.status_blocked)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+    }
+    if parts.is_empty() {
+        return None;
+    }
+    let mut spans = vec![Span::styled(
+        " Filtered: ",
+        Style::default()
+            .fg(theme.accent)
+            .add_modifier(Modifier::BOLD),
+    )];
+    for (idx, part) in parts.into_iter().enumerate() {
+        if idx > 0 {
+            spans.push(Span::raw("  "));
+        }
+        spans.push(part);
+    }
+    spans.push(Span::styled(
+        "  (Ctrl+L clears)",
+        Style::default().fg(theme.muted),
+    ));
+    Some(Line::from(spans))
+}
+
+/// Picker for saved views or workspaces: names with a muted detail after
+/// each (a view's spec, a workspace's directory), `pick` highlighted
+fn render_picker(
+    frame: &mut ratatui::Frame,
+    area: Rect,
+    theme: &Theme,
+    title: &str,
+    items: &[(String, String)],
+    pick: usize,
+) {
+    const MAX_ROWS: usize = 12;
+    let rows = items.len().min(MAX_ROWS);
+    let offset = (pick + 1).saturating_sub(rows);
+    let name_width = items
+        .iter()
+        .map(|(name, _)| name.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    let lines: Vec<Line> = items
+        .iter()
+        .enumerate()
+        .skip(offset)
+        .take(rows)
+        .map(|(i, (name, detail))| {
+            let style = if i == pick {
+                theme.selection_style().fg(theme.selection_fg)
+            } else {
+                Style::default().fg(theme.fg)
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!("{:<width$}  ", name, width = name_width),
+                    style.add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(detail.clone(), style.fg(theme.muted)),
+            ])
+        })
+        .collect();
+
+    let width = 60.min(area.width.saturating_sub(4));
+    let modal_area = centered_rect(area, width, rows as u16 + 2);
+    frame.render_widget(Clear, modal_area);
+    let para = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_set(border::ROUNDED)
+                .border_style(Style::default().fg(theme.focused_border))
+                .title(title.to_string())
+                .title_style(Style::default().fg(theme.fg).add_modifier(Modifier::BOLD)),
+        )
+        .style(Style::default().bg(theme.bg).fg(theme.fg));
+    frame.render_widget(para, modal_area);
+}
+
+/// Stand-in for the whole layout while the terminal is below MIN_WIDTH x MIN_HEIGHT
+fn render_too_small(frame: &mut ratatui::Frame, area: Rect, theme: &Theme) {
+    let message = format!(
+        "Terminal too small ({}x{}, need at least {}x{})",
+        area.width, area.height, MIN_WIDTH, MIN_HEIGHT
+    );
+    // Wrapped onto as many lines as the width needs, then centered vertically
+    let lines = (message.len() as u16).div_ceil(area.width.max(1));
+    let para = Paragraph::new(message)
+        .alignment(ratatui::layout::Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .style(Style::default().fg(theme.status_blocked));
+    frame.render_widget(para, centered_rect(area, area.width, lines));
+}
+
+/// One-line search input above the footer: `/`, the query (scrolling sideways
+/// when it's longer than the bar) and a note when a /regex/ doesn't compile
+fn render_search_bar(
+    frame: &mut ratatui::Frame,
+    area: Rect,
+    theme: &Theme,
+    input: &TextArea<'static>,
+) {
+    let bad_regex = filter_regex(&input.lines().join("")).is_some_and(|re| re.is_err());
+    let note = if bad_regex { " bad regex " } else { "" };
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Min(1),
+            Constraint::Length(note.len() as u16),
+        ])
+        .split(area);
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            " /",
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        )),
+        chunks[0],
+    );
+
+    let mut textarea = input.clone();
+    textarea.set_style(Style::default().fg(theme.fg));
+    textarea.set_cursor_line_style(Style::default()); // Disable underline
+    textarea.set_cursor_style(theme.cursor_style());
+    frame.render_widget(&textarea, chunks[1]);
+
+    frame.render_widget(
+        Paragraph::new(Span::styled(
+            note,
+            Style::default()
+                .fg(theme.status_blocked)
+                .add_modifier(Modifier::BOLD),
+        )),
+        chunks[2],
+    );
+}
+
+/// Draw " bd-abc123 (5/42) " right-aligned on the bottom border of `pane`
+fn render_position(
+    frame: &mut ratatui::Frame,
+    pane: Rect,
+    theme: &Theme,
+    id: &str,
+    (position, len): (usize, usize),
+) {
+    let line = Line::from(vec![
+        Span::raw(" "),
+        Span::styled(
+            id.to_string(),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(" ({}/{}) ", position, len),
+            Style::default().fg(theme.muted),
+        ),
+    ]);
+    let width = line.width() as u16;
+    // Leave the corners and a bit of border on the left
+    if pane.height < 2 || width + 4 > pane.width {
+        return;
+    }
+    let area = Rect::new(pane.right() - 2 - width, pane.bottom() - 1, width, 1);
+    frame.render_widget(Paragraph::new(line), area);
 }
 
 fn render_help_overlay(frame: &mut ratatui::Frame, area: Rect, theme: &Theme) {
@@ -289,9 +906,21 @@ fn render_help_overlay(frame: &mut ratatui::Frame, area: Rect, theme: &Theme) {
             Span::raw("Page up/down (10 lines)"),
         ]),
         Line::from(vec![
-            Span::styled("g/G          ", Style::default().fg(theme.accent)),
+            Span::styled("Ctrl+U/D     ", Style::default().fg(theme.accent)),
+            Span::raw("Half page up/down"),
+        ]),
+        Line::from(vec![
+            Span::styled("gg/G         ", Style::default().fg(theme.accent)),
             Span::raw("First/last item"),
         ]),
+        Line::from(vec![
+            Span::styled("<n>j, <n>G   ", Style::default().fg(theme.accent)),
+            Span::raw("Move n rows / go to row n (vim counts)"),
+        ]),
+        Line::from(vec![
+            Span::styled("Space        ", Style::default().fg(theme.accent)),
+            Span::raw("Collapse/expand children"),
+        ]),
         Line::from(vec![
             Span::styled("Tab          ", Style::default().fg(theme.accent)),
             Span::raw("Switch focus"),
@@ -308,33 +937,161 @@ fn render_help_overlay(frame: &mut ratatui::Frame, area: Rect, theme: &Theme) {
             Span::styled("L            ", Style::default().fg(theme.accent)),
             Span::raw("Toggle labels"),
         ]),
+        Line::from(vec![
+            Span::styled("A            ", Style::default().fg(theme.accent)),
+            Span::raw("Toggle assignees"),
+        ]),
+        Line::from(vec![
+            Span::styled(".            ", Style::default().fg(theme.accent)),
+            Span::raw("Toggle update dots (today / this week / older)"),
+        ]),
+        Line::from(vec![
+            Span::styled("T            ", Style::default().fg(theme.accent)),
+            Span::raw("Relative / absolute timestamps"),
+        ]),
+        Line::from(vec![
+            Span::styled("w            ", Style::default().fg(theme.accent)),
+            Span::raw("Wrap/truncate long titles"),
+        ]),
+        Line::from(vec![
+            Span::styled("m / '        ", Style::default().fg(theme.accent)),
+            Span::raw("Save filters as a view / switch view"),
+        ]),
+        Line::from(vec![
+            Span::styled("Ctrl+W       ", Style::default().fg(theme.accent)),
+            Span::raw("Switch workspace (another beads database)"),
+        ]),
+        Line::from(vec![
+            Span::styled("z            ", Style::default().fg(theme.accent)),
+            Span::raw("Cycle list density (normal/compact/comfortable)"),
+        ]),
         Line::from(vec![
             Span::styled("x            ", Style::default().fg(theme.accent)),
             Span::raw("Close/reopen (detail pane)"),
         ]),
+        Line::from(vec![
+            Span::styled("i            ", Style::default().fg(theme.accent)),
+            Span::raw("Toggle in progress/open (detail pane)"),
+        ]),
         Line::from(vec![
             Span::styled("D            ", Style::default().fg(theme.accent)),
             Span::raw("Defer/undefer (detail pane)"),
         ]),
+        Line::from(vec![
+            Span::styled("@            ", Style::default().fg(theme.accent)),
+            Span::raw("Set assignee (detail pane)"),
+        ]),
+        Line::from(vec![
+            Span::styled("+ / -        ", Style::default().fg(theme.accent)),
+            Span::raw("Add/remove dependency (detail pane)"),
+        ]),
+        Line::from(vec![
+            Span::styled("B            ", Style::default().fg(theme.accent)),
+            Span::raw("Dependency graph / details (detail pane)"),
+        ]),
+        Line::from(vec![
+            Span::styled("U            ", Style::default().fg(theme.accent)),
+            Span::raw("Undo last change"),
+        ]),
+        Line::from(vec![
+            Span::styled("p <0-4>      ", Style::default().fg(theme.accent)),
+            Span::raw("Set priority"),
+        ]),
+        Line::from(vec![
+            Span::styled("V            ", Style::default().fg(theme.accent)),
+            Span::raw("Select rows; then x/+/p close, label, prioritize"),
+        ]),
         Line::from(vec![
             Span::styled("c            ", Style::default().fg(theme.accent)),
             Span::raw("Toggle closed"),
         ]),
+        Line::from(vec![
+            Span::styled("s            ", Style::default().fg(theme.accent)),
+            Span::raw("Cycle status filter"),
+        ]),
+        Line::from(vec![
+            Span::styled("R            ", Style::default().fg(theme.accent)),
+            Span::raw("Ready queue: only unblocked open work"),
+        ]),
+        Line::from(vec![
+            Span::styled("#            ", Style::default().fg(theme.accent)),
+            Span::raw("Filter by labels (Space ticks, Tab any/all)"),
+        ]),
+        Line::from(vec![
+            Span::styled("Ctrl+L       ", Style::default().fg(theme.accent)),
+            Span::raw("Clear search and every filter"),
+        ]),
+        Line::from(vec![
+            Span::styled("o            ", Style::default().fg(theme.accent)),
+            Span::raw("Cycle sort (priority/created/updated/title)"),
+        ]),
+        Line::from(vec![
+            Span::styled("O            ", Style::default().fg(theme.accent)),
+            Span::raw("Reverse sort"),
+        ]),
+        Line::from(vec![
+            Span::styled("=            ", Style::default().fg(theme.accent)),
+            Span::raw("Group by status / type / back to the tree"),
+        ]),
+        Line::from(vec![
+            Span::styled("F            ", Style::default().fg(theme.accent)),
+            Span::raw("Flat list (no tree), in sort order"),
+        ]),
+        Line::from(vec![
+            Span::styled("K            ", Style::default().fg(theme.accent)),
+            Span::raw("Board: h/l switch columns, H/L move the bead there"),
+        ]),
+        Line::from(vec![
+            Span::styled("P <0-4>      ", Style::default().fg(theme.accent)),
+            Span::raw("Show priority <= N (Esc clears)"),
+        ]),
         Line::from(vec![
             Span::styled("/            ", Style::default().fg(theme.accent)),
-            Span::raw("Filter"),
+            Span::raw("Filter ('text, @assignee, ~creator, /regex/, ↑/↓)"),
+        ]),
+        Line::from(vec![
+            Span::styled(":            ", Style::default().fg(theme.accent)),
+            Span::raw("Jump to bead by ID"),
+        ]),
+        Line::from(vec![
+            Span::styled("y / Y        ", Style::default().fg(theme.accent)),
+            Span::raw("Copy ID / \"ID: Title\" to clipboard"),
+        ]),
+        Line::from(vec![
+            Span::styled("!            ", Style::default().fg(theme.accent)),
+            Span::raw("Run open_command from config.toml"),
+        ]),
+        Line::from(vec![
+            Span::styled("E            ", Style::default().fg(theme.accent)),
+            Span::raw("Export visible beads to Markdown"),
+        ]),
+        Line::from(vec![
+            Span::styled("W            ", Style::default().fg(theme.accent)),
+            Span::raw("Write bead + comments to text file (detail pane)"),
         ]),
         Line::from(vec![
             Span::styled("r            ", Style::default().fg(theme.accent)),
             Span::raw("Refresh"),
         ]),
+        Line::from(vec![
+            Span::styled("[ / ]        ", Style::default().fg(theme.accent)),
+            Span::raw("Poll faster / slower"),
+        ]),
+        Line::from(vec![
+            Span::styled("Ctrl+R       ", Style::default().fg(theme.accent)),
+            Span::raw("Pause / resume auto-refresh"),
+        ]),
+        Line::from(vec![
+            Span::styled("S            ", Style::default().fg(theme.accent)),
+            Span::raw("Stats by status, priority, type and assignee"),
+        ]),
         Line::from(vec![
             Span::styled("t            ", Style::default().fg(theme.accent)),
             Span::raw("Cycle theme"),
         ]),
         Line::from(vec![
-            Span::styled("q            ", Style::default().fg(theme.accent)),
-            Span::raw("Quit"),
+            Span::styled("q / Q        ", Style::default().fg(theme.accent)),
+            Span::raw("Quit (q asks first with confirm_quit) / right away"),
         ]),
         Line::raw(""),
         Line::from(vec![Span::styled(
@@ -349,12 +1106,10 @@ fn render_help_overlay(frame: &mut ratatui::Frame, area: Rect, theme: &Theme) {
     ];
 
     // Center a help box sized to fit all help lines (+2 for borders)
-    let help_width = 50.min(area.width.saturating_sub(4));
+    let help_width = 66.min(area.width.saturating_sub(4));
     let desired_help_height = (help_text.len() as u16).saturating_add(2);
     let help_height = desired_help_height.min(area.height.saturating_sub(4));
-    let x = (area.width - help_width) / 2;
-    let y = (area.height - help_height) / 2;
-    let help_area = Rect::new(x, y, help_width, help_height);
+    let help_area = centered_rect(area, help_width, help_height);
 
     // Clear the area
     frame.render_widget(Clear, help_area);
@@ -373,6 +1128,305 @@ fn render_help_overlay(frame: &mut ratatui::Frame, area: Rect, theme: &Theme) {
     frame.render_widget(help, help_area);
 }
 
+/// Project snapshot: bead counts by status, priority, type and assignee as
+/// bars, plus the average cycle time of closed beads
+fn render_stats_overlay(
+    frame: &mut ratatui::Frame,
+    area: Rect,
+    theme: &Theme,
+    stats: &BeadStats,
+    visible_only: bool,
+) {
+    const LABEL_WIDTH: usize = 14;
+    const BAR_WIDTH: usize = 24;
+    const MAX_ASSIGNEES: usize = 6;
+
+    let heading = |text: &str| {
+        Line::from(Span::styled(
+            text.to_string(),
+            Style::default()
+                .fg(theme.accent)
+                .add_modifier(Modifier::BOLD),
+        ))
+    };
+    // One row per entry, bars scaled to the section's largest count
+    let section = |lines: &mut Vec<Line<'static>>, rows: Vec<(String, usize, Color)>| {
+        let max = rows
+            .iter()
+            .map(|(_, count, _)| *count)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        for (label, count, color) in rows {
+            let bar = "█".repeat((count * BAR_WIDTH).div_ceil(max));
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("  {:<width$}", label, width = LABEL_WIDTH),
+                    Style::default().fg(theme.fg),
+                ),
+                Span::styled(format!("{:>4} ", count), Style::default().fg(theme.fg)),
+                Span::styled(bar, Style::default().fg(color)),
+            ]));
+        }
+    };
+
+    let scope = if visible_only { "shown" } else { "loaded" };
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(
+                format!("{} beads ", stats.total),
+                Style::default().fg(theme.fg).add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!("({})", scope), Style::default().fg(theme.muted)),
+        ]),
+        Line::raw(""),
+        heading("Status"),
+    ];
+    let by_status = stats
+        .by_status
+        .iter()
+        .map(|(status, count)| {
+            let color = theme.status_color(*status);
+            (format!("{} {}", status.icon(), status), *count, color)
+        })
+        .collect();
+    section(&mut lines, by_status);
+
+    lines.push(Line::raw(""));
+    lines.push(heading("Priority"));
+    let by_priority = (0u8..)
+        .zip(stats.by_priority)
+        .map(|(level, count)| (format!("P{}", level), count, theme.priority_color(level)))
+        .collect();
+    section(&mut lines, by_priority);
+
+    lines.push(Line::raw(""));
+    lines.push(heading("Type"));
+    let by_type = stats
+        .by_type
+        .iter()
+        .map(|(bead_type, count)| (bead_type.to_string(), *count, theme.accent))
+        .collect();
+    section(&mut lines, by_type);
+
+    lines.push(Line::raw(""));
+    lines.push(heading("Assignee"));
+    let mut by_assignee: Vec<_> = stats
+        .by_assignee
+        .iter()
+        .take(MAX_ASSIGNEES)
+        .map(|(name, count)| {
+            let name = name.as_deref().unwrap_or("unassigned");
+            (
+                truncate_to_width(name, LABEL_WIDTH - 1),
+                *count,
+                theme.accent,
+            )
+        })
+        .collect();
+    if let Some(rest) = stats.by_assignee.len().checked_sub(MAX_ASSIGNEES)
+        && rest > 0
+    {
+        let count = stats.by_assignee[MAX_ASSIGNEES..]
+            .iter()
+            .map(|(_, n)| n)
+            .sum();
+        by_assignee.push((format!("{} others", rest), count, theme.muted));
+    }
+    section(&mut lines, by_assignee);
+
+    lines.push(Line::raw(""));
+    let cycle_time = match stats.cycle_times {
+        (closed, Some(average)) => format!("{} (over {} closed)", format_duration(average), closed),
+        _ => "no closed beads".to_string(),
+    };
+    lines.push(Line::from(vec![
+        Span::styled("Average cycle time: ", Style::default().fg(theme.muted)),
+        Span::styled(cycle_time, Style::default().fg(theme.fg)),
+    ]));
+    lines.push(Line::raw(""));
+    let toggle = if visible_only {
+        "f: all loaded beads"
+    } else {
+        "f: only beads shown"
+    };
+    lines.push(Line::from(Span::styled(
+        format!("{} · any other key closes", toggle),
+        Style::default().fg(theme.muted),
+    )));
+
+    let width = 50.min(area.width.saturating_sub(4));
+    let height = (lines.len() as u16 + 2).min(area.height.saturating_sub(2));
+    let stats_area = centered_rect(area, width, height);
+    frame.render_widget(Clear, stats_area);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_set(border::ROUNDED)
+                    .border_style(Style::default().fg(theme.accent))
+                    .title(" Stats ")
+                    .title_style(Style::default().fg(theme.fg).add_modifier(Modifier::BOLD)),
+            )
+            .style(Style::default().bg(theme.bg)),
+        stats_area,
+    );
+}
+
+/// Render the confirmation shown before closing a bead with open children
+fn render_confirm_close_modal(
+    frame: &mut ratatui::Frame,
+    area: Rect,
+    theme: &Theme,
+    children: &[String],
+) {
+    const MAX_LISTED: usize = 8;
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!("This bead still has {} open child bead(s):", children.len()),
+            Style::default().fg(theme.fg),
+        )),
+        Line::raw(""),
+    ];
+    for id in children.iter().take(MAX_LISTED) {
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled(id.clone(), Style::default().fg(theme.accent)),
+        ]));
+    }
+    if children.len() > MAX_LISTED {
+        lines.push(Line::from(Span::styled(
+            format!("  ... and {} more", children.len() - MAX_LISTED),
+            Style::default().fg(theme.muted),
+        )));
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::from(vec![
+        Span::styled("y", Style::default().fg(theme.accent)),
+        Span::styled(" close anyway  ", Style::default().fg(theme.muted)),
+        Span::styled("n", Style::default().fg(theme.accent)),
+        Span::styled(" cancel", Style::default().fg(theme.muted)),
+    ]));
+
+    let width = 50.min(area.width.saturating_sub(4));
+    let modal_area = centered_rect(area, width, lines.len() as u16 + 2);
+
+    frame.render_widget(Clear, modal_area);
+    let para = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_set(border::ROUNDED)
+                .border_style(Style::default().fg(theme.status_blocked))
+                .title(" Close Bead? ")
+                .title_style(Style::default().fg(theme.fg).add_modifier(Modifier::BOLD)),
+        )
+        .style(Style::default().bg(theme.bg).fg(theme.fg));
+    frame.render_widget(para, modal_area);
+}
+
+/// Comment editor: a tall text area for paragraph-length comments, with the
+/// usual text input keys (Ctrl+A/E, Ctrl+W, Alt+B/F...)
+fn render_comment_modal(
+    frame: &mut ratatui::Frame,
+    area: Rect,
+    theme: &Theme,
+    input: &TextArea<'static>,
+) {
+    let modal_area = centered_rect(area, 72.min(area.width.saturating_sub(4)), 12);
+
+    let chars: usize = input.lines().iter().map(|l| l.chars().count()).sum();
+    let lines = input.lines().len();
+    let counts = format!(
+        " {} {} · {} {} ",
+        chars,
+        if chars == 1 { "char" } else { "chars" },
+        lines,
+        if lines == 1 { "line" } else { "lines" },
+    );
+
+    let mut textarea = input.clone();
+    textarea.set_block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED)
+            .border_style(Style::default().fg(theme.focused_border))
+            .title(" Add Comment ")
+            .title_style(Style::default().fg(theme.fg).add_modifier(Modifier::BOLD))
+            .title_bottom(Line::styled(
+                " Enter: submit · Shift+Enter / Ctrl+J: new line ",
+                Style::default().fg(theme.muted),
+            ))
+            .title_bottom(Line::styled(counts, Style::default().fg(theme.muted)).right_aligned()),
+    );
+    textarea.set_style(Style::default().bg(theme.bg).fg(theme.fg));
+    textarea.set_cursor_line_style(Style::default()); // Disable underline
+
+    frame.render_widget(Clear, modal_area);
+    frame.render_widget(&textarea, modal_area);
+}
+
+/// Most lines a multi-line input box grows to before it scrolls
+const MAX_INPUT_LINES: usize = 8;
+
+/// A text input box that grows with its lines (added with Shift+Enter or
+/// Ctrl+J) up to MAX_INPUT_LINES, scrolled to keep the cursor's row in view
+fn render_multiline_modal(
+    frame: &mut ratatui::Frame,
+    area: Rect,
+    theme: &Theme,
+    title: &str,
+    text: &str,
+    (row, col): (usize, usize),
+) {
+    let cursor_style = Style::default().fg(theme.focused_border);
+    let lines: Vec<Line> = text
+        .split('\n')
+        .enumerate()
+        .map(|(i, line)| {
+            if i != row {
+                return Line::raw(line);
+            }
+            // The cursor column counts chars, not bytes
+            let split = line
+                .char_indices()
+                .nth(col)
+                .map_or(line.len(), |(byte, _)| byte);
+            let (before, after) = line.split_at(split);
+            Line::from(vec![
+                Span::raw(before),
+                Span::styled("\u{2588}", cursor_style), // Block cursor - green
+                Span::raw(after),
+            ])
+        })
+        .collect();
+
+    let visible = lines.len().min(MAX_INPUT_LINES);
+    let scroll = (row + 1).saturating_sub(visible) as u16;
+    let width = 60.min(area.width.saturating_sub(4));
+    let modal_area = centered_rect(area, width, visible as u16 + 2);
+
+    frame.render_widget(Clear, modal_area);
+    let input = Paragraph::new(lines)
+        .scroll((scroll, 0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_set(border::ROUNDED)
+                .border_style(Style::default().fg(theme.focused_border))
+                .title(format!(" {} ", title))
+                .title_style(Style::default().fg(theme.fg).add_modifier(Modifier::BOLD))
+                .title_bottom(Line::styled(
+                    " Shift+Enter / Ctrl+J: new line ",
+                    Style::default().fg(theme.muted),
+                )),
+        )
+        .style(Style::default().bg(theme.bg).fg(theme.fg));
+    frame.render_widget(input, modal_area);
+}
+
 fn render_reason_modal(
     frame: &mut ratatui::Frame,
     area: Rect,
@@ -383,10 +1437,7 @@ fn render_reason_modal(
 ) {
     // Center a modal (just the input field, no outer border)
     let width = 60.min(area.width.saturating_sub(4));
-    let height = 3; // Just the input field
-    let x = (area.width - width) / 2;
-    let y = (area.height - height) / 2;
-    let modal_area = Rect::new(x, y, width, height);
+    let modal_area = centered_rect(area, width, 3); // Just the input field
 
     // Clear the area
     frame.render_widget(Clear, modal_area);
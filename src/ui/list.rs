@@ -4,14 +4,24 @@
 
 use ratatui::{
     buffer::Buffer,
-    layout::Rect,
-    style::{Color, Modifier, Style},
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
     symbols::border,
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState, StatefulWidget},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, StatefulWidget, Widget, Wrap},
 };
 
-use crate::data::{Bead, BeadStatus, build_tree_order};
+use fuzzy_matcher::skim::SkimMatcherV2;
+use serde::Deserialize;
+use std::fmt;
+use std::ops::{Range, RangeInclusive};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::data::{
+    Bead, BeadStatus, DependencyType, Recency, TreeOptions, TreeRow, build_tree_rows,
+    filter_match_indices, not_closed_ids,
+};
+use crate::glyphs;
 use crate::ui::Theme;
 
 /// State for the bead list
@@ -19,6 +29,8 @@ use crate::ui::Theme;
 pub struct BeadListState {
     list_state: ListState,
     offset: usize,
+    /// Rendered height of each item (for mapping mouse rows to items)
+    item_heights: Vec<u16>,
 }
 
 impl BeadListState {
@@ -64,6 +76,23 @@ impl BeadListState {
         self.list_state.select(Some(i));
     }
 
+    /// Index of the item drawn at `row` lines below the top of the list
+    pub fn index_at_row(&self, row: u16) -> Option<usize> {
+        let mut top = 0u16;
+        for (idx, height) in self
+            .item_heights
+            .iter()
+            .enumerate()
+            .skip(self.list_state.offset())
+        {
+            top = top.saturating_add(*height);
+            if row < top {
+                return Some(idx);
+            }
+        }
+        None
+    }
+
     pub fn first(&mut self) {
         self.list_state.select(Some(0));
     }
@@ -75,14 +104,89 @@ impl BeadListState {
     }
 }
 
+/// Collapse indicator drawn before a bead
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Fold {
+    /// No children (blank, keeps the columns aligned)
+    Leaf,
+    Expanded,
+    Collapsed,
+}
+
+impl Fold {
+    /// `joined` rows hang off a tree connector, so a leaf's blank gutter
+    /// carries the connector's line on to the icon instead
+    fn symbol(self, joined: bool) -> String {
+        let glyphs = glyphs::get();
+        // As wide as the markers, so titles line up
+        let width = glyphs.expanded.width().max(glyphs.collapsed.width());
+        match self {
+            Fold::Leaf if joined => {
+                let fill = glyphs.rule.repeat(width / glyphs.rule.width().max(1));
+                format!(
+                    "{}{}",
+                    fill,
+                    " ".repeat(width + 1 - fill.width().min(width))
+                )
+            }
+            Fold::Leaf => " ".repeat(width + 1),
+            Fold::Expanded => format!("{} ", glyphs.expanded),
+            Fold::Collapsed => format!("{} ", glyphs.collapsed),
+        }
+    }
+}
+
+/// How much each bead row shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Density {
+    /// Type icon and title only, without the priority and ID
+    Compact,
+    #[default]
+    Normal,
+    /// A blank line before each top-level bead
+    Comfortable,
+}
+
+impl Density {
+    /// Next density in the cycle
+    pub fn next(self) -> Self {
+        match self {
+            Density::Normal => Density::Compact,
+            Density::Compact => Density::Comfortable,
+            Density::Comfortable => Density::Normal,
+        }
+    }
+}
+
+impl fmt::Display for Density {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Density::Compact => write!(f, "compact"),
+            Density::Normal => write!(f, "normal"),
+            Density::Comfortable => write!(f, "comfortable"),
+        }
+    }
+}
+
 /// A list widget for displaying beads
 pub struct BeadList<'a> {
     beads: &'a [Bead],
     theme: &'a Theme,
     focused: bool,
-    filter: Option<&'a str>,
-    hide_closed: bool,
+    options: Option<&'a TreeOptions>,
     show_labels: bool,
+    show_assignee: bool,
+    show_recency: bool,
+    wrap_titles: bool,
+    density: Density,
+    read_only: bool,
+    scrolloff: usize,
+    marked: Option<RangeInclusive<usize>>,
+    cycles: &'a [Vec<String>],
+    stale_after: Option<chrono::Duration>,
+    now: chrono::DateTime<chrono::Utc>,
+    matcher: SkimMatcherV2,
 }
 
 impl<'a> BeadList<'a> {
@@ -91,9 +195,19 @@ impl<'a> BeadList<'a> {
             beads,
             theme,
             focused: true,
-            filter: None,
-            hide_closed: false,
+            options: None,
             show_labels: true,
+            show_assignee: false,
+            show_recency: false,
+            wrap_titles: false,
+            density: Density::default(),
+            read_only: false,
+            scrolloff: 0,
+            marked: None,
+            cycles: &[],
+            stale_after: None,
+            now: chrono::Utc::now(),
+            matcher: SkimMatcherV2::default(),
         }
     }
 
@@ -102,46 +216,126 @@ impl<'a> BeadList<'a> {
         self
     }
 
-    pub fn filter(mut self, filter: Option<&'a str>) -> Self {
-        self.filter = filter;
+    pub fn options(mut self, options: &'a TreeOptions) -> Self {
+        self.options = Some(options);
         self
     }
 
-    pub fn hide_closed(mut self, hide: bool) -> Self {
-        self.hide_closed = hide;
+    pub fn show_labels(mut self, show: bool) -> Self {
+        self.show_labels = show;
         self
     }
 
-    pub fn show_labels(mut self, show: bool) -> Self {
-        self.show_labels = show;
+    pub fn show_assignee(mut self, show: bool) -> Self {
+        self.show_assignee = show;
+        self
+    }
+
+    /// Lead each row with a dot for how recently the bead was updated
+    pub fn show_recency(mut self, show: bool) -> Self {
+        self.show_recency = show;
+        self
+    }
+
+    /// Wrap long titles onto continuation lines instead of truncating them
+    pub fn wrap_titles(mut self, wrap: bool) -> Self {
+        self.wrap_titles = wrap;
+        self
+    }
+
+    pub fn density(mut self, density: Density) -> Self {
+        self.density = density;
+        self
+    }
+
+    /// Beads can't be created, so an empty database doesn't suggest it
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Beads to keep in view above and below the selection while scrolling
+    /// (as many as fit, so a large number keeps it centered)
+    pub fn scrolloff(mut self, beads: usize) -> Self {
+        self.scrolloff = beads;
+        self
+    }
+
+    /// Rows in the visual selection, drawn with the marked background
+    pub fn marked(mut self, rows: Option<RangeInclusive<usize>>) -> Self {
+        self.marked = rows;
+        self
+    }
+
+    /// Dependency cycles; beads in one get a warning marker
+    pub fn cycles(mut self, cycles: &'a [Vec<String>]) -> Self {
+        self.cycles = cycles;
+        self
+    }
+
+    /// Mark open beads that haven't been updated for this long
+    pub fn stale_after(mut self, age: Option<chrono::Duration>) -> Self {
+        self.stale_after = age;
         self
     }
 
     /// Get color for the combined type+status icon
     fn type_status_color(&self, status: &BeadStatus) -> ratatui::style::Color {
-        match status {
-            BeadStatus::Open => self.theme.status_open,
-            BeadStatus::InProgress => self.theme.status_in_progress,
-            BeadStatus::Blocked => self.theme.status_blocked,
-            BeadStatus::Deferred => self.theme.status_deferred,
-            BeadStatus::Closed => self.theme.status_closed,
-        }
+        self.theme.status_color(*status)
     }
 
     fn priority_style(&self, priority: u8) -> Style {
         Style::default().fg(self.theme.priority_color(priority))
     }
 
-    fn render_bead(&self, bead: &Bead, depth: usize) -> ListItem<'static> {
+    /// What to say in place of rows when none are shown: how to get started
+    /// in an empty database, or why everything is hidden
+    fn empty_hint(&self, options: &TreeOptions) -> &'static str {
+        if self.beads.is_empty() && self.read_only {
+            "No beads yet"
+        } else if self.beads.is_empty() {
+            "No beads yet \u{2014} press 'a' to create one"
+        } else if options.is_filtered() {
+            "No beads match your filter"
+        } else {
+            "Every bead is closed \u{2014} c shows them"
+        }
+    }
+
+    /// Header line above a group: its name and size, then a rule to the edge
+    fn group_header(&self, heading: &str, count: usize, width: usize) -> Line<'static> {
+        let label = format!("{} ({}) ", heading, count);
+        let glyph = &glyphs::get().rule;
+        let rule = glyph.repeat(width.saturating_sub(label.width()) / glyph.width().max(1));
+        Line::from(vec![
+            Span::styled(
+                label,
+                Style::default()
+                    .fg(self.theme.accent)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(rule, Style::default().fg(self.theme.border)),
+        ])
+    }
+
+    /// Render one row. `status` is the bead's effective status, so open beads
+    /// with open blockers look blocked; `guide` is its tree connector and
+    /// `fold` is None when no bead in the list has children.
+    fn render_bead(
+        &self,
+        row: &TreeRow,
+        status: BeadStatus,
+        guide: String,
+        fold: Option<Fold>,
+        width: usize,
+    ) -> Vec<Line<'static>> {
+        let bead = row.bead;
         // Combined type+status icon: shape = type, color = status
-        let type_icon = bead.bead_type.icon_for_status(&bead.status);
-        let icon_color = self.type_status_color(&bead.status);
+        let type_icon = bead.bead_type.icon_for_status(&status);
+        let icon_color = self.type_status_color(&status);
         let priority_style = self.priority_style(bead.priority);
         let is_deferred = bead.is_deferred();
 
-        // Indentation: 2 spaces per depth level
-        let indent = "  ".repeat(depth);
-
         // Deferred beads get dim + italic styling on all text spans
         let deferred_mod = if is_deferred {
             Modifier::DIM | Modifier::ITALIC
@@ -149,64 +343,401 @@ impl<'a> BeadList<'a> {
             Modifier::empty()
         };
 
-        let mut spans = vec![
-            Span::raw(indent),
+        // Beads nested under a blocker rather than a parent get a red
+        // connector and a marker, so they don't read as sub-tasks
+        let joined = !guide.is_empty();
+        let blocked_edge = row.edge == Some(DependencyType::Blocks);
+        let guide_style = if blocked_edge {
+            Style::default().fg(self.theme.status_blocked)
+        } else {
+            Style::default().fg(self.theme.muted)
+        };
+        let mut prefix = Vec::new();
+        if self.show_recency {
+            let recency = bead.recency(self.now);
+            let dots = &glyphs::get().recency;
+            let dot = match recency {
+                Recency::Today => &dots[0],
+                Recency::ThisWeek => &dots[1],
+                Recency::Older => &dots[2],
+            };
+            prefix.push(Span::styled(
+                format!("{} ", dot),
+                Style::default().fg(self.theme.recency_color(recency)),
+            ));
+        }
+        prefix.extend([
+            Span::styled(guide, guide_style),
             Span::styled(
-                format!("{} ", type_icon),
-                Style::default().fg(icon_color).add_modifier(deferred_mod),
+                fold.map_or(String::new(), |fold| fold.symbol(joined)),
+                guide_style,
             ),
-            Span::styled(
-                format!("P{} ", bead.priority),
+        ]);
+        if blocked_edge {
+            prefix.push(Span::styled(
+                format!("{} ", glyphs::get().blocked_by),
+                guide_style.add_modifier(Modifier::BOLD),
+            ));
+        }
+        prefix.extend([Span::styled(
+            format!("{} ", type_icon),
+            Style::default().fg(icon_color).add_modifier(deferred_mod),
+        )]);
+        // Compact rows go straight from the icon (and markers) to the title
+        let compact = self.density == Density::Compact;
+        if !compact {
+            prefix.push(Span::styled(
+                format!("{} ", bead.priority_label()),
                 priority_style
                     .add_modifier(Modifier::BOLD)
                     .add_modifier(deferred_mod),
-            ),
-            Span::styled(
+            ));
+            prefix.push(Span::styled(
                 bead.id.clone(),
                 Style::default()
                     .fg(self.theme.muted)
                     .add_modifier(deferred_mod),
-            ),
-            Span::raw(": "),
-            Span::styled(
-                bead.title.clone(),
+            ));
+        }
+        let marker = |symbol: &str| {
+            if compact {
+                format!("{} ", symbol)
+            } else {
+                format!(" {}", symbol)
+            }
+        };
+        if self.cycles.iter().any(|c| c.contains(&bead.id)) {
+            prefix.push(Span::styled(
+                marker(&glyphs::get().cycle),
                 Style::default()
-                    .fg(self.theme.fg)
+                    .fg(self.theme.status_blocked)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+        if self
+            .stale_after
+            .is_some_and(|age| bead.is_stale(age, self.now))
+        {
+            prefix.push(Span::styled(
+                marker(&glyphs::get().stale),
+                Style::default().fg(self.theme.muted),
+            ));
+        }
+        if !compact {
+            prefix.push(Span::raw(": "));
+        }
+        let title_style = Style::default()
+            .fg(self.theme.fg)
+            .add_modifier(deferred_mod);
+
+        let mut suffix = Vec::new();
+        if row.child_count > 0 {
+            suffix.push(Span::styled(
+                format!(" ({})", row.child_count),
+                Style::default()
+                    .fg(self.theme.muted)
                     .add_modifier(deferred_mod),
-            ),
-        ];
+            ));
+        }
+        if self.show_assignee
+            && let Some(ref assignee) = bead.assignee
+        {
+            suffix.push(Span::styled(
+                format!(" @{}", assignee),
+                Style::default()
+                    .fg(self.theme.accent)
+                    .add_modifier(deferred_mod),
+            ));
+        }
 
         if self.show_labels && !bead.labels.is_empty() {
-            spans.push(Span::raw(" "));
+            suffix.push(Span::raw(" "));
             for (idx, label) in bead.labels.iter().enumerate() {
                 if idx > 0 {
-                    spans.push(Span::raw(" "));
+                    suffix.push(Span::raw(" "));
                 }
-                spans.push(Span::styled(
+                suffix.push(Span::styled(
                     format!("[{}]", label),
                     Style::default()
-                        .fg(Color::Yellow)
+                        .fg(self.theme.label_color(label))
                         .add_modifier(deferred_mod),
                 ));
             }
         }
 
-        let line = Line::from(spans);
+        let prefix_width: usize = prefix.iter().map(Span::width).sum();
+        let suffix_width: usize = suffix.iter().map(Span::width).sum();
+        let title_width = width.saturating_sub(prefix_width);
+
+        // Characters of the title that matched the text filter
+        let hits = self
+            .options
+            .and_then(|o| o.filter.as_deref())
+            .map(|f| filter_match_indices(&bead.title, f, &self.matcher))
+            .unwrap_or_default();
+        let hit_style = title_style
+            .fg(self.theme.accent)
+            .add_modifier(Modifier::BOLD);
+        let chars: Vec<char> = bead.title.chars().collect();
+
+        if self.wrap_titles {
+            // Continuation lines align under the title column
+            let mut lines: Vec<Vec<Span<'static>>> = Vec::new();
+            for (idx, range) in wrap_to_width(&bead.title, title_width)
+                .into_iter()
+                .enumerate()
+            {
+                let mut line = if idx == 0 {
+                    prefix.clone()
+                } else {
+                    vec![Span::raw(" ".repeat(prefix_width))]
+                };
+                line.extend(highlight_spans(
+                    &chars,
+                    range,
+                    &hits,
+                    title_style,
+                    hit_style,
+                ));
+                lines.push(line);
+            }
+            if let Some(last) = lines.last_mut() {
+                last.extend(suffix);
+            }
+            return lines.into_iter().map(Line::from).collect();
+        }
+
+        // Leave room for assignee/labels unless that would squeeze the title to nothing
+        let budget = match title_width.saturating_sub(suffix_width) {
+            b if b >= MIN_TITLE_WIDTH => b,
+            _ => title_width,
+        };
+        let mut spans = prefix;
+        match truncated_len(&bead.title, budget) {
+            None => spans.extend(highlight_spans(
+                &chars,
+                0..chars.len(),
+                &hits,
+                title_style,
+                hit_style,
+            )),
+            Some(kept) => {
+                spans.extend(highlight_spans(
+                    &chars,
+                    0..kept,
+                    &hits,
+                    title_style,
+                    hit_style,
+                ));
+                if budget > 0 {
+                    spans.push(Span::styled("\u{2026}", title_style));
+                }
+            }
+        }
+        spans.extend(suffix);
 
-        ListItem::new(line)
+        vec![Line::from(spans)]
     }
 }
 
+/// Narrowest title worth showing before dropping trailing assignee/labels
+const MIN_TITLE_WIDTH: usize = 10;
+
+/// Split `chars[range]` into spans, styling the characters in `hits` with `hit`
+fn highlight_spans(
+    chars: &[char],
+    range: Range<usize>,
+    hits: &[usize],
+    base: Style,
+    hit: Style,
+) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    let mut run = String::new();
+    let mut run_is_hit = false;
+    for idx in range {
+        let is_hit = hits.contains(&idx);
+        if is_hit != run_is_hit && !run.is_empty() {
+            let style = if run_is_hit { hit } else { base };
+            spans.push(Span::styled(std::mem::take(&mut run), style));
+        }
+        run_is_hit = is_hit;
+        run.push(chars[idx]);
+    }
+    if !run.is_empty() {
+        spans.push(Span::styled(run, if run_is_hit { hit } else { base }));
+    }
+    spans
+}
+
+/// Number of characters to keep so `text` plus an ellipsis fits in `width`
+/// columns, or `None` if it already fits
+fn truncated_len(text: &str, width: usize) -> Option<usize> {
+    if text.width() <= width {
+        return None;
+    }
+    let mut used = 0;
+    let mut kept = 0;
+    for c in text.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w + 1 > width {
+            break;
+        }
+        used += w;
+        kept += 1;
+    }
+    Some(kept)
+}
+
+/// Cut `text` to at most `width` columns, marking the cut with an ellipsis
+pub fn truncate_to_width(text: &str, width: usize) -> String {
+    match truncated_len(text, width) {
+        None => text.to_string(),
+        Some(kept) => {
+            let mut out: String = text.chars().take(kept).collect();
+            if width > 0 {
+                out.push('\u{2026}');
+            }
+            out
+        }
+    }
+}
+
+/// Greedy word wrap to `width` columns, returning the character range of each
+/// line; words longer than a line are split
+fn wrap_to_width(text: &str, width: usize) -> Vec<Range<usize>> {
+    let width = width.max(1);
+    let chars: Vec<char> = text.chars().collect();
+    let char_width = |c: char| c.width().unwrap_or(0);
+
+    // Words as character ranges
+    let mut words = Vec::new();
+    let mut start = None;
+    for (idx, c) in chars.iter().enumerate() {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some(idx),
+            (true, Some(s)) => {
+                words.push(s..idx);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        words.push(s..chars.len());
+    }
+
+    let mut lines: Vec<Range<usize>> = Vec::new();
+    let mut current: Option<Range<usize>> = None;
+    let mut used = 0;
+    for word in words {
+        let word_width: usize = chars[word.clone()].iter().map(|c| char_width(*c)).sum();
+        if let Some(line) = current.as_mut() {
+            // Keep the original spacing between words on the same line
+            let gap: usize = chars[line.end..word.start]
+                .iter()
+                .map(|c| char_width(*c))
+                .sum();
+            if used + gap + word_width <= width {
+                line.end = word.end;
+                used += gap + word_width;
+                continue;
+            }
+            lines.extend(current.take());
+        }
+        let mut line_start = word.start;
+        used = 0;
+        for idx in word.clone() {
+            let w = char_width(chars[idx]);
+            if used + w > width && used > 0 {
+                lines.push(line_start..idx);
+                line_start = idx;
+                used = 0;
+            }
+            used += w;
+        }
+        current = Some(line_start..word.end);
+    }
+    lines.extend(current);
+    if lines.is_empty() {
+        lines.push(0..0);
+    }
+    lines
+}
+
+/// Tree connector before each row: a trunk for every ancestor with siblings
+/// still to come, then the row's own branch (closed off for a last child)
+fn tree_guides(rows: &[TreeRow]) -> Vec<String> {
+    let glyphs = glyphs::get();
+    let branch = glyphs.branch.trim_end();
+    let last_branch = glyphs.last_branch.trim_end();
+    // Each level is as wide as a branch, without the branch's trailing space
+    let level = branch.width().max(last_branch.width());
+    let pad = |glyph: &str| format!("{}{}", glyph, " ".repeat(level - glyph.width().min(level)));
+    let trunk = pad(glyphs.trunk.trim_end());
+    let blank = " ".repeat(level);
+
+    // Whether the ancestor at each depth below the root has more siblings
+    let mut continues: Vec<bool> = Vec::new();
+    rows.iter()
+        .map(|row| {
+            if row.depth == 0 {
+                continues.clear();
+                return String::new();
+            }
+            continues.truncate(row.depth - 1);
+            let mut guide: String = continues
+                .iter()
+                .map(|&more| if more { trunk.as_str() } else { blank.as_str() })
+                .collect();
+            guide.push_str(&pad(if row.last_child { last_branch } else { branch }));
+            continues.push(!row.last_child);
+            guide
+        })
+        .collect()
+}
+
 impl<'a> StatefulWidget for BeadList<'a> {
     type State = BeadListState;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
         // Build tree-ordered list with depths
-        let tree_order = build_tree_order(self.beads, self.hide_closed, self.filter);
+        let default_options = TreeOptions::default();
+        let options = self.options.unwrap_or(&default_options);
+        let tree_order = build_tree_rows(self.beads, options);
+        let not_closed = not_closed_ids(self.beads);
+        let inner_width = area.width.saturating_sub(2) as usize; // minus borders
+        let marked_style = self.theme.marked_style();
+        // Only reserve the collapse gutter when something can be collapsed
+        let any_children = tree_order.iter().any(|row| row.has_children());
+        let guides = tree_guides(&tree_order);
         let items: Vec<ListItem<'static>> = tree_order
             .iter()
-            .map(|(b, depth)| self.render_bead(b, *depth))
+            .enumerate()
+            .map(|(idx, row)| {
+                let fold = any_children.then(|| match row.has_children() {
+                    false => Fold::Leaf,
+                    true if options.collapsed.contains(&row.bead.id) => Fold::Collapsed,
+                    true => Fold::Expanded,
+                });
+                let status = row.bead.effective_status(&not_closed);
+                let mut lines =
+                    self.render_bead(row, status, guides[idx].clone(), fold, inner_width);
+                // Grouped lists get a header above the first bead of each group
+                if let Some((heading, count)) = row.group_start {
+                    lines.insert(0, self.group_header(heading, count, inner_width));
+                }
+                if self.density == Density::Comfortable && row.depth == 0 && idx > 0 {
+                    lines.insert(0, Line::raw(""));
+                }
+                let item = ListItem::new(lines);
+                if self.marked.as_ref().is_some_and(|rows| rows.contains(&idx)) {
+                    item.style(marked_style)
+                } else {
+                    item
+                }
+            })
             .collect();
+        state.item_heights = items.iter().map(|item| item.height() as u16).collect();
 
         let border_style = if self.focused {
             Style::default().fg(self.theme.focused_border)
@@ -214,10 +745,16 @@ impl<'a> StatefulWidget for BeadList<'a> {
             Style::default().fg(self.theme.border)
         };
 
+        // The ready queue is easy to forget about, so name it in the title too
+        let ready = if self.options.is_some_and(|o| o.ready_only) {
+            "Ready "
+        } else {
+            ""
+        };
         let title = if tree_order.is_empty() {
-            " Beads ".to_string()
+            format!(" {}Beads ", ready)
         } else {
-            format!(" {} Beads ", tree_order.len())
+            format!(" {} {}Beads ", tree_order.len(), ready)
         };
 
         let block = Block::default()
@@ -232,19 +769,327 @@ impl<'a> StatefulWidget for BeadList<'a> {
             );
 
         // Only set background for highlight - preserve span foreground colors
-        let highlight_style = Style::default().bg(self.theme.selection_bg);
+        let highlight_style = self.theme.selection_style();
 
+        let inner = block.inner(area);
         let list = List::new(items)
             .block(block)
-            .highlight_style(highlight_style);
+            .highlight_style(highlight_style)
+            .scroll_padding(self.scrolloff);
 
         StatefulWidget::render(list, area, buf, &mut state.list_state);
+
+        // An empty pane gets a hint centered in it instead
+        if tree_order.is_empty() && inner.height > 0 {
+            let hint = Paragraph::new(self.empty_hint(options))
+                .style(Style::default().fg(self.theme.muted))
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true });
+            let height = (hint.line_count(inner.width) as u16).min(inner.height);
+            let hint_area = Rect {
+                y: inner.y + (inner.height - height) / 2,
+                height,
+                ..inner
+            };
+            hint.render(hint_area, buf);
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::BeadListState;
+    use super::{
+        BeadList, BeadListState, Density, highlight_spans, truncate_to_width, wrap_to_width,
+    };
+    use crate::data::{Bead, BeadStatus, GroupBy, TreeOptions};
+    use crate::ui::theme::LAZYGIT;
+    use ratatui::{
+        buffer::Buffer,
+        layout::Rect,
+        style::{Modifier, Style},
+        widgets::StatefulWidget,
+    };
+
+    /// Render the list and return its inner text rows, trimmed
+    fn render_rows(beads: &[Bead], density: Density) -> (Vec<String>, BeadListState) {
+        let area = Rect::new(0, 0, 40, 8);
+        let mut buf = Buffer::empty(area);
+        let mut state = BeadListState::new();
+        BeadList::new(beads, &LAZYGIT)
+            .density(density)
+            .render(area, &mut buf, &mut state);
+        let rows = (1..area.height - 1)
+            .map(|y| {
+                (1..area.width - 1)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect();
+        (rows, state)
+    }
+
+    #[test]
+    fn density_changes_what_each_row_shows() {
+        let bead = |id: &str| Bead {
+            id: id.to_string(),
+            title: format!("Title {}", id),
+            status: BeadStatus::Open,
+            ..Default::default()
+        };
+        let mut child = bead("bd-3");
+        child.parent_ids.push("bd-1".to_string());
+        let beads = [bead("bd-1"), bead("bd-2"), child];
+
+        let (rows, _) = render_rows(&beads, Density::Normal);
+        assert!(rows[0].contains("P2 bd-1: Title bd-1"), "{:?}", rows);
+
+        let (rows, _) = render_rows(&beads, Density::Compact);
+        assert!(rows[0].ends_with("\u{25b7} Title bd-1 (1)"), "{:?}", rows);
+        assert!(!rows.iter().any(|r| r.contains("bd-1:")));
+
+        // Blank lines before the second and later top-level beads, not before children
+        let (rows, state) = render_rows(&beads, Density::Comfortable);
+        assert!(rows[1].contains("bd-3"), "{:?}", rows);
+        assert_eq!(rows[2], "");
+        assert!(rows[3].contains("bd-2"), "{:?}", rows);
+        assert_eq!(state.item_heights, vec![1, 1, 2]);
+    }
+
+    #[test]
+    fn children_hang_off_connectors_to_their_parent() {
+        let bead = |id: &str, parent: Option<&str>| Bead {
+            id: id.to_string(),
+            title: id.to_string(),
+            status: BeadStatus::Open,
+            parent_ids: parent.into_iter().map(String::from).collect(),
+            ..Default::default()
+        };
+        let beads = [
+            bead("bd-1", None),
+            bead("bd-2", Some("bd-1")),
+            bead("bd-3", Some("bd-2")),
+            bead("bd-4", Some("bd-1")),
+            bead("bd-5", None),
+        ];
+
+        let (rows, _) = render_rows(&beads, Density::Compact);
+        let prefixes: Vec<String> = rows[..5]
+            .iter()
+            .map(|row| row.chars().take(8).collect())
+            .collect();
+        assert_eq!(
+            prefixes,
+            vec![
+                "\u{25be} \u{25b7} bd-1",
+                "\u{251c}\u{2500}\u{25be} \u{25b7} bd",
+                "\u{2502} \u{2514}\u{2500}\u{2500} \u{25b7} ",
+                "\u{2514}\u{2500}\u{2500} \u{25b7} bd",
+                "  \u{25b7} bd-5",
+            ],
+            "{:?}",
+            rows
+        );
+    }
+
+    #[test]
+    fn grouped_list_puts_a_header_on_each_groups_first_row() {
+        let bead = |id: &str, status| Bead {
+            id: id.to_string(),
+            title: format!("Title {}", id),
+            status,
+            ..Default::default()
+        };
+        let beads = [
+            bead("bd-1", BeadStatus::Open),
+            bead("bd-2", BeadStatus::InProgress),
+            bead("bd-3", BeadStatus::Open),
+        ];
+        let options = TreeOptions {
+            group_by: GroupBy::Status,
+            ..Default::default()
+        };
+        let area = Rect::new(0, 0, 40, 8);
+        let mut buf = Buffer::empty(area);
+        let mut state = BeadListState::new();
+        BeadList::new(&beads, &LAZYGIT)
+            .options(&options)
+            .render(area, &mut buf, &mut state);
+        let rows: Vec<String> = (1..area.height - 1)
+            .map(|y| (1..area.width - 1).map(|x| buf[(x, y)].symbol()).collect())
+            .collect();
+
+        assert!(
+            rows[0].starts_with("In Progress (1) \u{2500}"),
+            "{:?}",
+            rows
+        );
+        assert!(rows[1].contains("bd-2"), "{:?}", rows);
+        assert!(rows[2].starts_with("Open (2) "), "{:?}", rows);
+        assert!(
+            rows[3].contains("bd-1") && rows[4].contains("bd-3"),
+            "{:?}",
+            rows
+        );
+        // Headers ride along with the bead below, so selection never lands on one
+        assert_eq!(state.item_heights, vec![2, 2, 1]);
+    }
+
+    #[test]
+    fn truncate_marks_cut_with_ellipsis() {
+        assert_eq!(truncate_to_width("short", 10), "short");
+        assert_eq!(truncate_to_width("a long title", 7), "a long\u{2026}");
+        assert_eq!(truncate_to_width("abc", 0), "");
+    }
+
+    fn wrapped(text: &str, width: usize) -> Vec<String> {
+        let chars: Vec<char> = text.chars().collect();
+        wrap_to_width(text, width)
+            .into_iter()
+            .map(|range| chars[range].iter().collect())
+            .collect()
+    }
+
+    #[test]
+    fn wrap_breaks_on_words_and_splits_long_words() {
+        assert_eq!(
+            wrapped("fix the flaky login test", 10),
+            vec!["fix the", "flaky", "login test"]
+        );
+        assert_eq!(wrapped("abcdefgh", 3), vec!["abc", "def", "gh"]);
+        assert_eq!(wrapped("", 5), vec![""]);
+    }
+
+    #[test]
+    fn highlight_groups_matched_characters() {
+        let chars: Vec<char> = "button".chars().collect();
+        let base = Style::default();
+        let hit = Style::default().add_modifier(Modifier::BOLD);
+        let spans = highlight_spans(&chars, 0..6, &[0, 1, 5], base, hit);
+
+        let parts: Vec<(&str, bool)> = spans
+            .iter()
+            .map(|s| (s.content.as_ref(), s.style == hit))
+            .collect();
+        assert_eq!(parts, vec![("bu", true), ("tto", false), ("n", true)]);
+    }
+
+    #[test]
+    fn scrolloff_keeps_beads_in_view_around_the_selection() {
+        let beads: Vec<Bead> = (0..10)
+            .map(|n| Bead {
+                id: format!("bd-{}", n),
+                title: format!("Title {}", n),
+                ..Default::default()
+            })
+            .collect();
+        // Four rows fit inside the borders
+        let offset = |scrolloff: usize| {
+            let area = Rect::new(0, 0, 40, 6);
+            let mut buf = Buffer::empty(area);
+            let mut state = BeadListState::new();
+            state.select(Some(5));
+            BeadList::new(&beads, &LAZYGIT)
+                .scrolloff(scrolloff)
+                .render(area, &mut buf, &mut state);
+            state.list_state.offset()
+        };
+
+        assert_eq!(offset(0), 2);
+        assert_eq!(offset(1), 3);
+        // Only one bead fits on each side, so a large scrolloff centers
+        assert_eq!(offset(99), 3);
+    }
+
+    #[test]
+    fn an_empty_list_says_why() {
+        let hint = |beads: &[Bead], options: &TreeOptions, read_only: bool| {
+            let area = Rect::new(0, 0, 60, 7);
+            let mut buf = Buffer::empty(area);
+            BeadList::new(beads, &LAZYGIT)
+                .options(options)
+                .read_only(read_only)
+                .render(area, &mut buf, &mut BeadListState::new());
+            (0..area.height)
+                .map(|y| {
+                    (0..area.width)
+                        .map(|x| buf[(x, y)].symbol())
+                        .collect::<String>()
+                })
+                .find(|row| row.contains("beads") || row.contains("bead "))
+                .map(|row| {
+                    row.trim_matches(|c: char| c == ' ' || c == '\u{2502}')
+                        .to_string()
+                })
+        };
+        let hide_closed = TreeOptions {
+            hide_closed: true,
+            ..Default::default()
+        };
+        let closed = [Bead {
+            id: "bd-1".to_string(),
+            title: "Done".to_string(),
+            status: BeadStatus::Closed,
+            ..Default::default()
+        }];
+
+        assert_eq!(
+            hint(&[], &hide_closed, false).as_deref(),
+            Some("No beads yet \u{2014} press 'a' to create one")
+        );
+        assert_eq!(
+            hint(&[], &hide_closed, true).as_deref(),
+            Some("No beads yet")
+        );
+        assert_eq!(
+            hint(&closed, &hide_closed, false).as_deref(),
+            Some("Every bead is closed \u{2014} c shows them")
+        );
+        let searching = TreeOptions {
+            filter: Some("nope".to_string()),
+            hide_closed: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            hint(&closed, &searching, false).as_deref(),
+            Some("No beads match your filter")
+        );
+    }
+
+    #[test]
+    fn index_at_row_accounts_for_multi_line_items() {
+        let mut state = BeadListState::new();
+        state.item_heights = vec![1, 2, 1];
+
+        assert_eq!(state.index_at_row(0), Some(0));
+        assert_eq!(state.index_at_row(1), Some(1));
+        assert_eq!(state.index_at_row(2), Some(1));
+        assert_eq!(state.index_at_row(3), Some(2));
+        assert_eq!(state.index_at_row(4), None);
+    }
+
+    #[test]
+    fn index_at_row_counts_from_the_scroll_offset() {
+        let beads: Vec<Bead> = (0..10)
+            .map(|n| Bead {
+                id: format!("bd-{}", n),
+                title: format!("Title {}", n),
+                ..Default::default()
+            })
+            .collect();
+        // Four rows fit, so selecting the last bead scrolls the first six away
+        let area = Rect::new(0, 0, 40, 6);
+        let mut buf = Buffer::empty(area);
+        let mut state = BeadListState::new();
+        state.select(Some(9));
+        BeadList::new(&beads, &LAZYGIT).render(area, &mut buf, &mut state);
+
+        assert_eq!(state.list_state.offset(), 6);
+        assert_eq!(state.index_at_row(0), Some(6));
+        assert_eq!(state.index_at_row(3), Some(9));
+        assert_eq!(state.index_at_row(4), None);
+    }
 
     #[test]
     fn next_does_not_wrap_at_end() {
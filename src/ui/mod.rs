@@ -1,13 +1,16 @@
 //! UI components for beads-tui
 
+pub mod board;
 mod create_modal;
 mod detail;
+mod label_picker;
 pub mod layout;
 pub mod list;
 mod theme;
 
 pub use create_modal::{CreateModal, ModalAction};
 pub use detail::DetailState;
+pub use label_picker::{LabelPicker, PickerAction};
 pub use layout::render_layout;
 pub use list::BeadListState;
-pub use theme::{THEMES, Theme};
+pub use theme::{MONOCHROME, Theme, load_themes, theme_index};
@@ -7,13 +7,19 @@
 
 #![allow(dead_code)]
 
-use ratatui::style::Color;
+use anyhow::{Context, Result};
+use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::path::Path;
+
+use crate::data::{BeadStatus, Priority, Recency};
 
 /// A color theme for the application
 #[derive(Debug, Clone)]
 pub struct Theme {
     /// Theme name
-    pub name: &'static str,
+    pub name: Cow<'static, str>,
     /// Background color
     pub bg: Color,
     /// Primary foreground color
@@ -30,6 +36,8 @@ pub struct Theme {
     pub selection_bg: Color,
     /// Selection foreground
     pub selection_fg: Color,
+    /// Background for rows in a visual (multi-row) selection
+    pub marked_bg: Color,
     /// Status: open
     pub status_open: Color,
     /// Status: in progress
@@ -48,11 +56,13 @@ pub struct Theme {
     pub priority_medium: Color,
     /// Priority low (P3+)
     pub priority_low: Color,
+    /// No colors at all: selections use reversed video and labels go uncolored
+    pub monochrome: bool,
 }
 
 /// Lazygit-inspired theme (default) - neutral with green focused borders
 pub const LAZYGIT: Theme = Theme {
-    name: "Lazygit",
+    name: Cow::Borrowed("Lazygit"),
     bg: Color::Reset, // Use terminal default
     fg: Color::White,
     muted: Color::Gray, // Lighter than DarkGray for visibility on selection
@@ -61,6 +71,7 @@ pub const LAZYGIT: Theme = Theme {
     focused_border: Color::Green, // Focused pane (lazygit signature)
     selection_bg: Color::DarkGray,
     selection_fg: Color::Cyan,
+    marked_bg: Color::Indexed(17), // Navy, distinct from the cursor row
     status_open: Color::White,
     status_in_progress: Color::Cyan,
     status_blocked: Color::Red,
@@ -70,11 +81,12 @@ pub const LAZYGIT: Theme = Theme {
     priority_high: Color::Yellow,
     priority_medium: Color::White,
     priority_low: Color::Gray, // Lighter for visibility on selection
+    monochrome: false,
 };
 
 /// Tokyo Night theme
 pub const TOKYO_NIGHT: Theme = Theme {
-    name: "Tokyo Night",
+    name: Cow::Borrowed("Tokyo Night"),
     bg: Color::Rgb(26, 27, 38),
     fg: Color::Rgb(169, 177, 214),
     muted: Color::Rgb(86, 95, 137),
@@ -83,6 +95,7 @@ pub const TOKYO_NIGHT: Theme = Theme {
     focused_border: Color::Rgb(158, 206, 106), // Green
     selection_bg: Color::Rgb(41, 46, 66),
     selection_fg: Color::Rgb(192, 202, 245),
+    marked_bg: Color::Rgb(40, 52, 87),
     status_open: Color::Rgb(169, 177, 214),
     status_in_progress: Color::Rgb(125, 207, 255),
     status_blocked: Color::Rgb(247, 118, 142),
@@ -92,11 +105,12 @@ pub const TOKYO_NIGHT: Theme = Theme {
     priority_high: Color::Rgb(255, 158, 100),
     priority_medium: Color::Rgb(224, 175, 104),
     priority_low: Color::Rgb(158, 206, 106),
+    monochrome: false,
 };
 
 /// Dracula theme
 pub const DRACULA: Theme = Theme {
-    name: "Dracula",
+    name: Cow::Borrowed("Dracula"),
     bg: Color::Rgb(40, 42, 54),
     fg: Color::Rgb(248, 248, 242),
     muted: Color::Rgb(98, 114, 164),
@@ -105,6 +119,7 @@ pub const DRACULA: Theme = Theme {
     focused_border: Color::Rgb(80, 250, 123), // Green
     selection_bg: Color::Rgb(68, 71, 90),
     selection_fg: Color::Rgb(248, 248, 242),
+    marked_bg: Color::Rgb(73, 56, 99),
     status_open: Color::Rgb(248, 248, 242),
     status_in_progress: Color::Rgb(139, 233, 253),
     status_blocked: Color::Rgb(255, 85, 85),
@@ -114,11 +129,12 @@ pub const DRACULA: Theme = Theme {
     priority_high: Color::Rgb(255, 184, 108),
     priority_medium: Color::Rgb(241, 250, 140),
     priority_low: Color::Rgb(80, 250, 123),
+    monochrome: false,
 };
 
 /// Nord theme
 pub const NORD: Theme = Theme {
-    name: "Nord",
+    name: Cow::Borrowed("Nord"),
     bg: Color::Rgb(46, 52, 64),
     fg: Color::Rgb(216, 222, 233),
     muted: Color::Rgb(76, 86, 106),
@@ -127,6 +143,7 @@ pub const NORD: Theme = Theme {
     focused_border: Color::Rgb(163, 190, 140), // Green
     selection_bg: Color::Rgb(67, 76, 94),
     selection_fg: Color::Rgb(236, 239, 244),
+    marked_bg: Color::Rgb(52, 70, 94),
     status_open: Color::Rgb(216, 222, 233),
     status_in_progress: Color::Rgb(136, 192, 208),
     status_blocked: Color::Rgb(191, 97, 106),
@@ -136,19 +153,366 @@ pub const NORD: Theme = Theme {
     priority_high: Color::Rgb(208, 135, 112),
     priority_medium: Color::Rgb(235, 203, 139),
     priority_low: Color::Rgb(163, 190, 140),
+    monochrome: false,
+};
+
+/// Colorblind-friendly theme: blue and orange from the Okabe-Ito palette
+/// instead of red and green, on the terminal's background
+pub const COLORBLIND: Theme = Theme {
+    name: Cow::Borrowed("Colorblind"),
+    bg: Color::Reset,
+    fg: Color::White,
+    muted: Color::Gray,
+    accent: Color::Rgb(86, 180, 233), // Sky blue
+    border: Color::DarkGray,
+    focused_border: Color::Rgb(230, 159, 0), // Orange
+    selection_bg: Color::Rgb(0, 60, 100),
+    selection_fg: Color::White,
+    marked_bg: Color::Rgb(70, 45, 0),
+    status_open: Color::White,
+    status_in_progress: Color::Rgb(86, 180, 233), // Sky blue
+    status_blocked: Color::Rgb(230, 159, 0),      // Orange
+    status_deferred: Color::DarkGray,
+    status_closed: Color::Rgb(0, 114, 178),    // Blue
+    priority_critical: Color::Rgb(213, 94, 0), // Vermillion
+    priority_high: Color::Rgb(230, 159, 0),    // Orange
+    priority_medium: Color::Rgb(240, 228, 66), // Yellow
+    priority_low: Color::Gray,
+    monochrome: false,
+};
+
+/// Monochrome theme for `NO_COLOR` and `--no-color`: the terminal's own
+/// colors, with bold, dim and reversed text doing the highlighting
+pub const MONOCHROME: Theme = Theme {
+    name: Cow::Borrowed("Monochrome"),
+    bg: Color::Reset,
+    fg: Color::Reset,
+    muted: Color::Reset,
+    accent: Color::Reset,
+    border: Color::Reset,
+    focused_border: Color::Reset,
+    selection_bg: Color::Reset,
+    selection_fg: Color::Reset,
+    marked_bg: Color::Reset,
+    status_open: Color::Reset,
+    status_in_progress: Color::Reset,
+    status_blocked: Color::Reset,
+    status_deferred: Color::Reset,
+    status_closed: Color::Reset,
+    priority_critical: Color::Reset,
+    priority_high: Color::Reset,
+    priority_medium: Color::Reset,
+    priority_low: Color::Reset,
+    monochrome: true,
 };
 
 /// All available themes (Lazygit is default)
-pub const THEMES: &[Theme] = &[LAZYGIT, TOKYO_NIGHT, DRACULA, NORD];
+pub const THEMES: &[Theme] = &[LAZYGIT, TOKYO_NIGHT, DRACULA, NORD, COLORBLIND, MONOCHROME];
+
+/// Colors labels are drawn in. Red is left out so labels don't read as
+/// blocked, and the grays so they don't read as muted.
+const LABEL_PALETTE: &[Color] = &[
+    Color::Yellow,
+    Color::Cyan,
+    Color::Magenta,
+    Color::Green,
+    Color::Blue,
+    Color::LightYellow,
+    Color::LightCyan,
+    Color::LightMagenta,
+    Color::LightGreen,
+    Color::LightBlue,
+];
+
+/// Color for a label, the same for a given name in every bead and session
+pub fn label_color(label: &str) -> Color {
+    // FNV-1a: tiny and, unlike std's hasher, guaranteed not to change between releases
+    let hash = label.bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    });
+    LABEL_PALETTE[hash as usize % LABEL_PALETTE.len()]
+}
+
+/// Find a theme by name, ignoring case, spaces, dashes and underscores
+/// (so "tokyo-night" matches "Tokyo Night")
+pub fn theme_index(themes: &[Theme], name: &str) -> Option<usize> {
+    let normalize = |s: &str| {
+        s.chars()
+            .filter(|c| !matches!(c, ' ' | '-' | '_'))
+            .collect::<String>()
+            .to_lowercase()
+    };
+    let wanted = normalize(name);
+    themes.iter().position(|t| normalize(&t.name) == wanted)
+}
+
+/// Built-in themes followed by any user themes from `themes.toml` in the config dir
+pub fn load_themes() -> Result<Vec<Theme>> {
+    let mut themes = THEMES.to_vec();
+    if let Some(path) = crate::config::config_dir().map(|dir| dir.join("themes.toml"))
+        && path.exists()
+    {
+        themes.extend(load_custom_themes(&path)?);
+    }
+    Ok(themes)
+}
+
+/// A user theme as written in `themes.toml`. Every color is optional and
+/// falls back to the `base` theme (Lazygit if not given).
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeDef {
+    name: String,
+    base: Option<String>,
+    bg: Option<String>,
+    fg: Option<String>,
+    muted: Option<String>,
+    accent: Option<String>,
+    border: Option<String>,
+    focused_border: Option<String>,
+    selection_bg: Option<String>,
+    selection_fg: Option<String>,
+    marked_bg: Option<String>,
+    status_open: Option<String>,
+    status_in_progress: Option<String>,
+    status_blocked: Option<String>,
+    status_deferred: Option<String>,
+    status_closed: Option<String>,
+    priority_critical: Option<String>,
+    priority_high: Option<String>,
+    priority_medium: Option<String>,
+    priority_low: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ThemeFile {
+    #[serde(default)]
+    theme: Vec<ThemeDef>,
+}
+
+/// Parse user themes from a TOML file of `[[theme]]` tables.
+///
+/// Colors accept hex strings (`"#1a1b26"`), names (`"cyan"`) or 256-color indices.
+pub fn load_custom_themes(path: &Path) -> Result<Vec<Theme>> {
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read themes file {:?}", path))?;
+    parse_custom_themes(&text).with_context(|| format!("Invalid themes file {:?}", path))
+}
+
+fn parse_custom_themes(text: &str) -> Result<Vec<Theme>> {
+    let file: ThemeFile = toml::from_str(text)?;
+    file.theme.into_iter().map(ThemeDef::into_theme).collect()
+}
+
+impl ThemeDef {
+    fn into_theme(self) -> Result<Theme> {
+        let mut theme = match self.base.as_deref() {
+            Some(base) => theme_index(THEMES, base)
+                .map(|idx| THEMES[idx].clone())
+                .with_context(|| format!("Theme {:?}: unknown base theme {:?}", self.name, base))?,
+            None => LAZYGIT,
+        };
+        theme.name = Cow::Owned(self.name.clone());
+
+        let name = &self.name;
+        let set = |slot: &mut Color, value: Option<String>, field: &str| -> Result<()> {
+            if let Some(value) = value {
+                *slot = value.parse().map_err(|_| {
+                    anyhow::anyhow!("Theme {:?}: invalid color {:?} for {}", name, value, field)
+                })?;
+            }
+            Ok(())
+        };
+        set(&mut theme.bg, self.bg, "bg")?;
+        set(&mut theme.fg, self.fg, "fg")?;
+        set(&mut theme.muted, self.muted, "muted")?;
+        set(&mut theme.accent, self.accent, "accent")?;
+        set(&mut theme.border, self.border, "border")?;
+        set(
+            &mut theme.focused_border,
+            self.focused_border,
+            "focused_border",
+        )?;
+        set(&mut theme.selection_bg, self.selection_bg, "selection_bg")?;
+        set(&mut theme.selection_fg, self.selection_fg, "selection_fg")?;
+        set(&mut theme.marked_bg, self.marked_bg, "marked_bg")?;
+        set(&mut theme.status_open, self.status_open, "status_open")?;
+        set(
+            &mut theme.status_in_progress,
+            self.status_in_progress,
+            "status_in_progress",
+        )?;
+        set(
+            &mut theme.status_blocked,
+            self.status_blocked,
+            "status_blocked",
+        )?;
+        set(
+            &mut theme.status_deferred,
+            self.status_deferred,
+            "status_deferred",
+        )?;
+        set(
+            &mut theme.status_closed,
+            self.status_closed,
+            "status_closed",
+        )?;
+        set(
+            &mut theme.priority_critical,
+            self.priority_critical,
+            "priority_critical",
+        )?;
+        set(
+            &mut theme.priority_high,
+            self.priority_high,
+            "priority_high",
+        )?;
+        set(
+            &mut theme.priority_medium,
+            self.priority_medium,
+            "priority_medium",
+        )?;
+        set(&mut theme.priority_low, self.priority_low, "priority_low")?;
+        Ok(theme)
+    }
+}
 
 impl Theme {
     /// Get a color for a priority level
     pub fn priority_color(&self, priority: u8) -> Color {
-        match priority {
-            0 => self.priority_critical,
-            1 => self.priority_high,
-            2 => self.priority_medium,
-            _ => self.priority_low,
+        match Priority::from(priority) {
+            Priority::Critical => self.priority_critical,
+            Priority::High => self.priority_high,
+            Priority::Medium => self.priority_medium,
+            Priority::Low | Priority::Backlog => self.priority_low,
+        }
+    }
+
+    /// Get a color for a status
+    pub fn status_color(&self, status: BeadStatus) -> Color {
+        match status {
+            BeadStatus::Open => self.status_open,
+            BeadStatus::InProgress => self.status_in_progress,
+            BeadStatus::Blocked => self.status_blocked,
+            BeadStatus::Deferred => self.status_deferred,
+            BeadStatus::Closed => self.status_closed,
+        }
+    }
+
+    /// Color for a bead's update recency: green today, yellow this week
+    pub fn recency_color(&self, recency: Recency) -> Color {
+        match recency {
+            Recency::Today => self.status_closed,
+            Recency::ThisWeek => self.priority_high,
+            Recency::Older => self.muted,
+        }
+    }
+
+    /// Style for the cursor row and other selected items
+    pub fn selection_style(&self) -> Style {
+        if self.monochrome {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().bg(self.selection_bg)
+        }
+    }
+
+    /// Style for rows in a visual (multi-row) selection
+    pub fn marked_style(&self) -> Style {
+        if self.monochrome {
+            Style::default().add_modifier(Modifier::UNDERLINED)
+        } else {
+            Style::default().bg(self.marked_bg)
+        }
+    }
+
+    /// Style for the cursor in text inputs
+    pub fn cursor_style(&self) -> Style {
+        if self.monochrome {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default().fg(self.bg).bg(self.accent)
+        }
+    }
+
+    /// Color for a label (see [`label_color`]), or none in the monochrome theme
+    pub fn label_color(&self, label: &str) -> Color {
+        if self.monochrome {
+            Color::Reset
+        } else {
+            label_color(label)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn label_colors_are_stable_and_spread_out() {
+        assert_eq!(label_color("bug"), label_color("bug"));
+        // Pinned so a change to the hash (which would recolor everyone's labels) is noticed
+        assert_eq!(
+            label_color(""),
+            LABEL_PALETTE[0x811c_9dc5 % LABEL_PALETTE.len()]
+        );
+        let colors: std::collections::HashSet<Color> = ["bug", "infra", "ui", "docs", "backend"]
+            .iter()
+            .map(|label| label_color(label))
+            .collect();
+        assert!(colors.len() > 1);
+    }
+
+    #[test]
+    fn monochrome_highlights_with_modifiers_only() {
+        let styles = [
+            MONOCHROME.selection_style(),
+            MONOCHROME.marked_style(),
+            MONOCHROME.cursor_style(),
+        ];
+        for style in styles {
+            assert_eq!((style.fg, style.bg), (None, None));
+            assert!(!style.add_modifier.is_empty());
         }
+        assert_eq!(MONOCHROME.label_color("bug"), Color::Reset);
+        assert_eq!(LAZYGIT.label_color("bug"), label_color("bug"));
+    }
+
+    #[test]
+    fn theme_index_ignores_case_and_separators() {
+        assert_eq!(theme_index(THEMES, "lazygit"), Some(0));
+        assert_eq!(theme_index(THEMES, "Tokyo Night"), Some(1));
+        assert_eq!(theme_index(THEMES, "tokyo-night"), Some(1));
+        assert_eq!(theme_index(THEMES, "TOKYONIGHT"), Some(1));
+        assert_eq!(theme_index(THEMES, "colorblind"), Some(4));
+        assert_eq!(theme_index(THEMES, "solarized"), None);
+    }
+
+    #[test]
+    fn custom_theme_parses_hex_colors_over_base() {
+        let themes = parse_custom_themes(
+            r##"
+            [[theme]]
+            name = "Midnight"
+            base = "nord"
+            bg = "#1a1b26"
+            accent = "magenta"
+            "##,
+        )
+        .expect("parse themes");
+
+        assert_eq!(themes.len(), 1);
+        let theme = &themes[0];
+        assert_eq!(theme.name, "Midnight");
+        assert_eq!(theme.bg, Color::Rgb(0x1a, 0x1b, 0x26));
+        assert_eq!(theme.accent, Color::Magenta);
+        assert_eq!(theme.fg, NORD.fg);
+    }
+
+    #[test]
+    fn custom_theme_rejects_bad_colors_and_bases() {
+        assert!(parse_custom_themes("[[theme]]\nname = \"X\"\nbg = \"#zzz\"").is_err());
+        assert!(parse_custom_themes("[[theme]]\nname = \"X\"\nbase = \"nope\"").is_err());
     }
 }
@@ -0,0 +1,332 @@
+//! Saved views: named combinations of filters and sort order
+//!
+//! Views live in `views.toml` as `name = "spec"` lines, where the spec is a
+//! space-separated list like `status:open label:sprint search:"login bug"`.
+//! Values with spaces go in double quotes. Words that aren't filters become
+//! the search text too, for hand-written specs.
+
+use anyhow::{Context, Result};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config;
+use crate::data::{BeadStatus, LabelMatch, Priority, SortMode};
+
+/// Filters and sort order captured by a view
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct View {
+    /// Search text, as typed after `/`
+    pub search: String,
+    /// Show closed beads (hidden by default)
+    pub show_closed: bool,
+    pub status: Option<BeadStatus>,
+    /// Only beads at this priority level or more urgent
+    pub max_priority: Option<u8>,
+    pub ready_only: bool,
+    pub labels: Vec<String>,
+    pub label_match: LabelMatch,
+    pub sort: SortMode,
+    pub reverse: bool,
+}
+
+/// Split a spec into words at unquoted whitespace. Double quotes group text
+/// into one word, and `\` inside them escapes the next character. Each word
+/// comes with whether any of it was quoted.
+fn split_words(spec: &str) -> Result<Vec<(String, bool)>> {
+    let mut words = Vec::new();
+    let mut word: Option<(String, bool)> = None;
+    let mut chars = spec.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                let (text, quoted) = word.get_or_insert_default();
+                *quoted = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') if let Some(c) = chars.next() => text.push(c),
+                        Some(c) => text.push(c),
+                        None => anyhow::bail!("Unterminated quote in: {}", spec),
+                    }
+                }
+            }
+            c if c.is_whitespace() => words.extend(word.take()),
+            c => word.get_or_insert_default().0.push(c),
+        }
+    }
+    words.extend(word);
+    Ok(words)
+}
+
+/// `text` in double quotes, escaping quotes and backslashes
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+impl std::str::FromStr for View {
+    type Err = anyhow::Error;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        let mut view = View::default();
+        let mut search: Vec<String> = Vec::new();
+        for (word, quoted) in split_words(spec)? {
+            let word = word.as_str();
+            match word.split_once(':') {
+                Some(("status", status)) => view.status = Some(status.parse()?),
+                Some(("priority", level)) => {
+                    let level = level.trim_start_matches(['p', 'P']);
+                    match level.parse::<u8>() {
+                        Ok(level) if level <= Priority::MAX_LEVEL => {
+                            view.max_priority = Some(level)
+                        }
+                        _ => anyhow::bail!("Unknown priority: {}", level),
+                    }
+                }
+                Some(("label", label)) if !label.is_empty() => view.labels.push(label.to_string()),
+                Some(("labels", "all")) => view.label_match = LabelMatch::All,
+                Some(("labels", "any")) => view.label_match = LabelMatch::Any,
+                Some(("sort", sort)) => view.sort = sort.parse()?,
+                Some(("search", text)) => search.push(text.to_string()),
+                _ if quoted => search.push(word.to_string()),
+                _ => match word {
+                    "closed" => view.show_closed = true,
+                    "ready" => view.ready_only = true,
+                    "reverse" => view.reverse = true,
+                    _ => search.push(word.to_string()),
+                },
+            }
+        }
+        view.search = search.join(" ");
+        Ok(view)
+    }
+}
+
+impl fmt::Display for View {
+    /// The spec that parses back to this view, leaving out defaults
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut words = Vec::new();
+        if let Some(status) = self.status {
+            words.push(format!("status:{}", status));
+        }
+        if let Some(level) = self.max_priority {
+            words.push(format!("priority:{}", level));
+        }
+        if self.ready_only {
+            words.push("ready".to_string());
+        }
+        if self.show_closed {
+            words.push("closed".to_string());
+        }
+        for label in &self.labels {
+            words.push(format!("label:{}", quote(label)));
+        }
+        if self.label_match == LabelMatch::All {
+            words.push("labels:all".to_string());
+        }
+        if self.sort != SortMode::default() {
+            words.push(format!("sort:{}", self.sort));
+        }
+        if self.reverse {
+            words.push("reverse".to_string());
+        }
+        if !self.search.is_empty() {
+            words.push(format!("search:{}", quote(&self.search)));
+        }
+        write!(f, "{}", words.join(" "))
+    }
+}
+
+/// Saved views, sorted by name
+#[derive(Debug, Clone, Default)]
+pub struct Views {
+    views: Vec<(String, View)>,
+    /// Problems with views.toml, reported once the UI is up
+    warnings: Vec<String>,
+}
+
+impl Views {
+    /// Default location of the views file
+    pub fn path() -> Option<PathBuf> {
+        config::config_dir().map(|dir| dir.join("views.toml"))
+    }
+
+    /// Load views from the default location. Views that can't be parsed are
+    /// skipped with a warning instead of failing.
+    pub fn load() -> Self {
+        match Self::path() {
+            Some(path) if path.exists() => Self::load_from(&path),
+            _ => Self::default(),
+        }
+    }
+
+    /// Load views from a file
+    pub fn load_from(path: &Path) -> Self {
+        let table = fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| {
+                text.parse::<toml::Table>()
+                    .map_err(|e| e.message().to_string())
+            });
+        let table = match table {
+            Ok(table) => table,
+            Err(e) => {
+                return Self {
+                    warnings: vec![format!("{}: {}", path.display(), e)],
+                    ..Self::default()
+                };
+            }
+        };
+
+        let mut views = Self::default();
+        for (name, spec) in table {
+            match spec.as_str().map(str::parse::<View>) {
+                Some(Ok(view)) => views.views.push((name, view)),
+                Some(Err(e)) => views.warnings.push(format!("{}: {}", name, e)),
+                None => views.warnings.push(format!("{}: expected a string", name)),
+            }
+        }
+        views
+    }
+
+    /// Save views to the default location
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path().context("No config directory (HOME is not set)")?;
+        self.save_to(&path)
+    }
+
+    /// Save views to a file, creating its directory if needed
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create config directory {:?}", dir))?;
+        }
+        let table: toml::Table = self
+            .views
+            .iter()
+            .map(|(name, view)| (name.clone(), toml::Value::String(view.to_string())))
+            .collect();
+        let text = toml::to_string(&table).context("Failed to serialize views")?;
+        fs::write(path, text).with_context(|| format!("Failed to write views file {:?}", path))
+    }
+
+    /// Problems found while loading views.toml
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
+    /// Saved views with their names
+    pub fn list(&self) -> &[(String, View)] {
+        &self.views
+    }
+
+    /// Save `view` as `name`, replacing any view already called that
+    pub fn insert(&mut self, name: &str, view: View) {
+        match self.views.iter_mut().find(|(n, _)| n == name) {
+            Some((_, existing)) => *existing = view,
+            None => {
+                self.views.push((name.to_string(), view));
+                self.views.sort_by(|(a, _), (b, _)| a.cmp(b));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn spec_parses_filters_and_leaves_the_rest_as_search() {
+        let view: View = "status:open label:sprint sort:updated reverse 'login bug"
+            .parse()
+            .expect("parse view");
+        assert_eq!(view.status, Some(BeadStatus::Open));
+        assert_eq!(view.labels, vec!["sprint"]);
+        assert_eq!(view.sort, SortMode::Updated);
+        assert!(view.reverse);
+        assert_eq!(view.search, "'login bug");
+        assert_eq!(
+            view.to_string(),
+            "status:open label:\"sprint\" sort:updated reverse search:\"'login bug\""
+        );
+
+        // Display gives a spec that parses back to the same view
+        assert_eq!(view.to_string().parse::<View>().expect("reparse"), view);
+        assert_eq!(View::default().to_string(), "");
+
+        assert!("status:nope".parse::<View>().is_err());
+        assert!("priority:7".parse::<View>().is_err());
+        assert!("search:\"open".parse::<View>().is_err());
+        assert_eq!(
+            "priority:P1".parse::<View>().expect("parse").max_priority,
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn searches_and_labels_that_look_like_filters_round_trip() {
+        for search in [
+            "closed",
+            "ready",
+            "reverse",
+            "word:with:colon",
+            "status:open",
+            "say \"hi\" \\ bye",
+            "  spaced  out ",
+        ] {
+            let view = View {
+                search: search.to_string(),
+                ..View::default()
+            };
+            let spec = view.to_string();
+            assert_eq!(spec.parse::<View>().expect("reparse"), view, "{}", spec);
+        }
+
+        let view = View {
+            labels: vec!["needs review".to_string(), "front:end".to_string()],
+            show_closed: true,
+            ..View::default()
+        };
+        let spec = view.to_string();
+        assert_eq!(spec, "closed label:\"needs review\" label:\"front:end\"");
+        assert_eq!(spec.parse::<View>().expect("reparse"), view);
+    }
+
+    #[test]
+    fn views_round_trip_and_skip_bad_specs() {
+        let dir = TempDir::new().expect("temp dir");
+        let path = dir.path().join("views.toml");
+        fs::write(
+            &path,
+            "ready = \"status:open label:sprint\"\nbroken = \"sort:sideways\"\n",
+        )
+        .expect("write views");
+
+        let mut views = Views::load_from(&path);
+        assert_eq!(views.list().len(), 1);
+        assert_eq!(views.warnings().len(), 1);
+
+        let triage = "label:bug closed".parse().expect("parse view");
+        views.insert("bugs triage", triage);
+        views.insert("ready", View::default());
+        views.save_to(&path).expect("save views");
+
+        let names: Vec<_> = Views::load_from(&path)
+            .list()
+            .iter()
+            .map(|(name, view)| (name.clone(), view.to_string()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                (
+                    "bugs triage".to_string(),
+                    "closed label:\"bug\"".to_string()
+                ),
+                ("ready".to_string(), String::new()),
+            ]
+        );
+    }
+}
@@ -2,6 +2,9 @@
 //!
 //! Provides a harness for TUI testing using tmux.
 
+// Each integration test crate only uses a subset of these helpers.
+#![allow(dead_code)]
+
 use std::path::{Path, PathBuf};
 use std::process::{Command, Output};
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -31,8 +34,8 @@ impl TestProject {
     /// Create a new test project with beads initialized.
     pub fn new() -> Self {
         let count = PROJECT_COUNTER.fetch_add(1, Ordering::SeqCst);
-        let dir = TempDir::with_prefix(&format!("bu-test-{}-", count))
-            .expect("Failed to create temp dir");
+        let dir =
+            TempDir::with_prefix(format!("bu-test-{}-", count)).expect("Failed to create temp dir");
         let path = dir.path().to_path_buf();
 
         let project = Self { dir, path };
@@ -55,8 +58,7 @@ impl TestProject {
 
     /// Create a new test project with a custom name (for debugging).
     pub fn with_name(name: &str) -> Self {
-        let dir =
-            TempDir::with_prefix(&format!("bu-{}-", name)).expect("Failed to create temp dir");
+        let dir = TempDir::with_prefix(format!("bu-{}-", name)).expect("Failed to create temp dir");
         let path = dir.path().to_path_buf();
 
         let project = Self { dir, path };
@@ -152,6 +154,8 @@ impl TuiHarness {
         let count = TUI_SESSION_COUNTER.fetch_add(1, Ordering::SeqCst);
         let session_name = format!("bu-tui-{}-{}", std::process::id(), count);
         let bin = bu_bin();
+        // Keep saved UI state out of the user's real config dir
+        let config_env = format!("XDG_CONFIG_HOME={}", project.path.join(".config").display());
 
         // Start tmux session with TUI
         let status = Command::new("tmux")
@@ -164,6 +168,8 @@ impl TuiHarness {
                 "100",
                 "-y",
                 "30",
+                "-e",
+                &config_env,
                 &bin.display().to_string(),
             ])
             .current_dir(&project.path)